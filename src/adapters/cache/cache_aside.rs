@@ -0,0 +1,289 @@
+use std::future::Future;
+use std::sync::Arc;
+
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use tracing::{debug, warn};
+
+use crate::adapters::cache::envelope::CacheEnvelope;
+use crate::adapters::shared::SingleFlight;
+use crate::error::Result;
+use crate::ports::cache::{
+    CachePolicy, CacheRead, ListingCache, fresh_requested, record_cache_read,
+};
+
+/// The cache-aside read shared by every `AirbnbClient` method of both adapters.
+///
+/// Values are stored in a [`CacheEnvelope`] tagged with `source`. A hit is recorded
+/// with [`record_cache_read`] so the tool can mention when it was fetched; a fresh hit
+/// is returned as is, a stale one (past its TTL, within its grace period) is returned
+/// right away and refreshed by a background task. An entry of another schema version
+/// is purged and read as a miss. A miss is fetched, with concurrent fetches of the same
+/// key coalesced through [`SingleFlight`], and stored; the flight looks at the cache
+/// once more first, in case another caller's fetch stored the value in the meantime. Inside
+/// [`fetch_fresh`](crate::ports::cache::fetch_fresh) every read is treated as a miss.
+#[derive(Clone)]
+pub struct CacheAside {
+    cache: Arc<dyn ListingCache>,
+    source: &'static str,
+    flights: Arc<SingleFlight>,
+}
+
+impl CacheAside {
+    /// `source` names the adapter in stored envelopes, e.g. `scraper`.
+    pub fn new(cache: Arc<dyn ListingCache>, source: &'static str) -> Self {
+        Self {
+            cache,
+            source,
+            flights: Arc::new(SingleFlight::new()),
+        }
+    }
+
+    /// The value under `key`, from the cache or from `fetch`.
+    ///
+    /// `fetch` must own what it uses: on a stale hit it runs after the caller returns.
+    pub async fn get_or_fetch<T, F, Fut>(
+        &self,
+        key: String,
+        policy: CachePolicy,
+        fetch: F,
+    ) -> Result<T>
+    where
+        T: Serialize + DeserializeOwned + Clone + Send + Sync + 'static,
+        F: FnOnce() -> Fut + Send + 'static,
+        Fut: Future<Output = Result<T>> + Send + 'static,
+    {
+        if !fresh_requested()
+            && let Some(hit) = self.cache.get(&key)
+        {
+            let Some(envelope) = CacheEnvelope::<T>::decode(&hit.value) else {
+                warn!(key, "Purging cache entry of an incompatible schema");
+                self.cache.purge_incompatible(&key);
+                return self.fetch(&key, policy, fetch).await;
+            };
+            record_cache_read(CacheRead {
+                key: key.clone(),
+                fetched_at: envelope.fetched_at,
+                stale: hit.stale,
+            });
+            if hit.stale {
+                debug!(
+                    key,
+                    age_secs = hit.age.as_secs(),
+                    "Serving stale cache entry while refreshing"
+                );
+                self.refresh_in_background(key, policy, fetch);
+            } else {
+                debug!(key, source = envelope.source, "Cache hit");
+            }
+            return Ok(envelope.data);
+        }
+        self.fetch(&key, policy, fetch).await
+    }
+
+    async fn fetch<T, F, Fut>(&self, key: &str, policy: CachePolicy, fetch: F) -> Result<T>
+    where
+        T: Serialize + DeserializeOwned + Clone + Send + Sync + 'static,
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<T>>,
+    {
+        self.flights
+            .run(key, || async {
+                // A flight for this key may have stored it since the caller missed
+                if !fresh_requested()
+                    && let Some(value) = self.fresh_hit(key)
+                {
+                    debug!(key, "Cache filled by a concurrent fetch");
+                    return Ok(value);
+                }
+                let value = fetch().await?;
+                if let Ok(json) = serde_json::to_string(&CacheEnvelope::new(self.source, &value)) {
+                    self.cache
+                        .set_with_grace(key, &json, policy.ttl, policy.grace);
+                }
+                Ok(value)
+            })
+            .await
+    }
+
+    /// The value under `key` if it is cached, not stale and of this schema version.
+    fn fresh_hit<T: DeserializeOwned>(&self, key: &str) -> Option<T> {
+        let hit = self.cache.peek(key).filter(|hit| !hit.stale)?;
+        let envelope = CacheEnvelope::<T>::decode(&hit.value)?;
+        record_cache_read(CacheRead {
+            key: key.to_string(),
+            fetched_at: envelope.fetched_at,
+            stale: false,
+        });
+        Some(envelope.data)
+    }
+
+    /// Refresh `key` on its own task; a refresh already running for it is joined.
+    fn refresh_in_background<T, F, Fut>(&self, key: String, policy: CachePolicy, fetch: F)
+    where
+        T: Serialize + DeserializeOwned + Clone + Send + Sync + 'static,
+        F: FnOnce() -> Fut + Send + 'static,
+        Fut: Future<Output = Result<T>> + Send + 'static,
+    {
+        let this = self.clone();
+        tokio::spawn(async move {
+            if let Err(e) = this.fetch(&key, policy, fetch).await {
+                warn!(key, error = %e, "Background cache refresh failed, keeping stale entry");
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    use super::*;
+    use crate::adapters::cache::envelope::CACHE_SCHEMA_VERSION;
+    use crate::adapters::cache::memory_cache::MemoryCache;
+    use crate::error::AirbnbError;
+    use crate::ports::cache::{fetch_fresh, track_cache_reads};
+
+    const POLICY: CachePolicy = CachePolicy {
+        ttl: Duration::from_mins(1),
+        grace: Duration::ZERO,
+    };
+
+    fn stored(value: &str) -> String {
+        serde_json::to_string(&CacheEnvelope::new("test", value)).unwrap()
+    }
+
+    fn cached_data(cache: &MemoryCache, key: &str) -> String {
+        let hit = cache.get(key).unwrap();
+        CacheEnvelope::<String>::decode(&hit.value).unwrap().data
+    }
+
+    #[tokio::test]
+    async fn miss_fetches_and_stores() {
+        let cache = Arc::new(MemoryCache::new(10));
+        let aside = CacheAside::new(cache.clone(), "test");
+        let value: String = aside
+            .get_or_fetch("detail:1".into(), POLICY, || async {
+                Ok("fetched".to_string())
+            })
+            .await
+            .unwrap();
+        assert_eq!(value, "fetched");
+        let envelope = CacheEnvelope::<String>::decode(&cache.get("detail:1").unwrap().value);
+        let envelope = envelope.unwrap();
+        assert_eq!(envelope.data, "fetched");
+        assert_eq!(envelope.source, "test");
+        assert_eq!(envelope.schema, CACHE_SCHEMA_VERSION);
+
+        let cached: String = aside
+            .get_or_fetch("detail:1".into(), POLICY, || async {
+                Err(AirbnbError::RateLimited)
+            })
+            .await
+            .unwrap();
+        assert_eq!(cached, "fetched");
+    }
+
+    #[tokio::test]
+    async fn fresh_skips_the_cached_value() {
+        let cache = Arc::new(MemoryCache::new(10));
+        cache.set("detail:1", &stored("old"), Duration::from_mins(1));
+        let aside = CacheAside::new(cache.clone(), "test");
+        let value: String = fetch_fresh(aside.get_or_fetch("detail:1".into(), POLICY, || async {
+            Ok("new".to_string())
+        }))
+        .await
+        .unwrap();
+        assert_eq!(value, "new");
+        assert_eq!(cached_data(&cache, "detail:1"), "new");
+    }
+
+    #[tokio::test]
+    async fn stale_hit_is_served_and_refreshed_in_background() {
+        let cache = Arc::new(MemoryCache::new(10));
+        cache.set_with_grace(
+            "detail:1",
+            &stored("old"),
+            Duration::ZERO,
+            Duration::from_mins(1),
+        );
+        let aside = CacheAside::new(cache.clone(), "test");
+        let calls = Arc::new(AtomicUsize::new(0));
+
+        let fetch_calls = Arc::clone(&calls);
+        let (value, reads) =
+            track_cache_reads(
+                aside.get_or_fetch("detail:1".into(), POLICY, move || async move {
+                    fetch_calls.fetch_add(1, Ordering::SeqCst);
+                    Ok("new".to_string())
+                }),
+            )
+            .await;
+        assert_eq!(value.unwrap(), "old");
+        assert_eq!(reads.len(), 1);
+        assert_eq!(reads[0].key, "detail:1");
+        assert!(reads[0].stale);
+
+        for _ in 0..100 {
+            if calls.load(Ordering::SeqCst) == 1 && !cache.get("detail:1").unwrap().stale {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(5)).await;
+        }
+        assert!(!cache.get("detail:1").unwrap().stale);
+        assert_eq!(cached_data(&cache, "detail:1"), "new");
+    }
+
+    #[tokio::test]
+    async fn flight_uses_a_value_stored_after_the_miss() {
+        let cache = Arc::new(MemoryCache::new(10));
+        let aside = CacheAside::new(cache.clone(), "test");
+        // The caller missed; another caller's fetch stored the value before this one
+        // reached the flight
+        cache.set("detail:1", &stored("cached"), Duration::from_mins(1));
+        let (value, reads) = track_cache_reads(aside.fetch("detail:1", POLICY, || async {
+            Err::<String, _>(AirbnbError::RateLimited)
+        }))
+        .await;
+        assert_eq!(value.unwrap(), "cached");
+        assert_eq!(reads.len(), 1);
+        // The caller's miss was already counted; the second look is not
+        let stats = cache.stats();
+        assert_eq!((stats.hits, stats.misses), (0, 0));
+
+        let value: String =
+            fetch_fresh(aside.fetch("detail:1", POLICY, || async { Ok("new".to_string()) }))
+                .await
+                .unwrap();
+        assert_eq!(value, "new");
+    }
+
+    #[tokio::test]
+    async fn incompatible_entries_are_purged_and_refetched() {
+        let cache = Arc::new(MemoryCache::new(10));
+        let mut newer = CacheEnvelope::new("test", "future");
+        newer.schema = CACHE_SCHEMA_VERSION + 1;
+        cache.set(
+            "detail:1",
+            &serde_json::to_string(&newer).unwrap(),
+            Duration::from_mins(1),
+        );
+        cache.set("detail:2", "\"bare\"", Duration::from_mins(1));
+        let aside = CacheAside::new(cache.clone(), "test");
+
+        for key in ["detail:1", "detail:2"] {
+            let ((), reads) = track_cache_reads(async {
+                let value: String = aside
+                    .get_or_fetch(key.into(), POLICY, || async { Ok("new".to_string()) })
+                    .await
+                    .unwrap();
+                assert_eq!(value, "new");
+            })
+            .await;
+            assert!(reads.is_empty());
+            assert_eq!(cached_data(&cache, key), "new");
+        }
+        assert_eq!(cache.stats().incompatible, 2);
+    }
+}
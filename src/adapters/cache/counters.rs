@@ -0,0 +1,54 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use crate::ports::cache::{CacheStats, CachedValue};
+
+/// Hit, miss, eviction and purge counts of one cache, for [`ListingCache::stats`].
+///
+/// [`ListingCache::stats`]: crate::ports::cache::ListingCache::stats
+#[derive(Debug, Default)]
+pub struct CacheCounters {
+    hits: AtomicU64,
+    stale_hits: AtomicU64,
+    misses: AtomicU64,
+    evictions: AtomicU64,
+    incompatible: AtomicU64,
+}
+
+impl CacheCounters {
+    /// Count the outcome of a `get`, passing it through.
+    pub fn record_read(&self, read: Option<CachedValue>) -> Option<CachedValue> {
+        match read {
+            Some(ref hit) => {
+                self.hits.fetch_add(1, Ordering::Relaxed);
+                if hit.stale {
+                    self.stale_hits.fetch_add(1, Ordering::Relaxed);
+                }
+            }
+            None => {
+                self.misses.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+        read
+    }
+
+    pub fn record_eviction(&self) {
+        self.evictions.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn record_incompatible(&self) {
+        self.incompatible.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// The counts, with no entries; the cache adds those.
+    pub fn stats(&self, capacity: usize) -> CacheStats {
+        CacheStats {
+            hits: self.hits.load(Ordering::Relaxed),
+            stale_hits: self.stale_hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+            evictions: self.evictions.load(Ordering::Relaxed),
+            incompatible: self.incompatible.load(Ordering::Relaxed),
+            capacity,
+            ..CacheStats::default()
+        }
+    }
+}
@@ -0,0 +1,606 @@
+use std::collections::{BTreeSet, HashMap};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, mpsc};
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+use crate::adapters::cache::counters::CacheCounters;
+use crate::ports::cache::{CacheStats, CachedValue, ListingCache};
+
+/// One cached value, stored as `<hash of key>.json` in the cache directory.
+#[derive(Serialize, Deserialize)]
+struct DiskEntry {
+    /// The full key, checked on read so a hash collision is a miss, not a wrong value.
+    key: String,
+    /// Unix times in milliseconds. `stored_at` and `stale_until` are missing (0) in
+    /// entries written before grace periods existed.
+    #[serde(default)]
+    stored_at: u64,
+    expires_at: u64,
+    #[serde(default)]
+    stale_until: u64,
+    value: String,
+}
+
+impl DiskEntry {
+    /// When the file can be deleted: the end of the grace period.
+    fn evict_at(&self) -> u64 {
+        self.stale_until.max(self.expires_at)
+    }
+}
+
+/// A value read from disk, with the timings needed to copy it into another tier.
+#[derive(Debug)]
+pub struct DiskHit {
+    pub value: String,
+    pub age: Duration,
+    /// How long the value stays fresh; zero once stale.
+    pub fresh_for: Duration,
+    /// How long it may be served as stale after `fresh_for`.
+    pub grace: Duration,
+}
+
+/// What the index knows about one file.
+struct Indexed {
+    key: String,
+    evict_at: u64,
+    /// File size in bytes.
+    size: u64,
+    /// The entry while the writer has not put it on disk yet; reads are served from it.
+    pending: Option<Arc<DiskEntry>>,
+}
+
+/// The live files, also ordered by end of grace period so eviction finds the next one
+/// without scanning.
+#[derive(Default)]
+struct Index {
+    files: HashMap<String, Indexed>,
+    by_eviction: BTreeSet<(u64, String)>,
+}
+
+impl Index {
+    fn insert(&mut self, name: String, indexed: Indexed) {
+        self.by_eviction.insert((indexed.evict_at, name.clone()));
+        if let Some(old) = self.files.insert(name.clone(), indexed)
+            && old.evict_at != self.files[&name].evict_at
+        {
+            self.by_eviction.remove(&(old.evict_at, name));
+        }
+    }
+
+    fn remove(&mut self, name: &str) -> Option<Indexed> {
+        let indexed = self.files.remove(name)?;
+        self.by_eviction
+            .remove(&(indexed.evict_at, name.to_string()));
+        Some(indexed)
+    }
+
+    /// The file closest to the end of its grace period.
+    fn soonest(&self) -> Option<String> {
+        self.by_eviction.first().map(|(_, name)| name.clone())
+    }
+}
+
+/// File operations, run in order on the writer thread.
+enum Job {
+    Write {
+        name: String,
+        entry: Arc<DiskEntry>,
+        bytes: Vec<u8>,
+    },
+    Remove(Vec<String>),
+    Flush(mpsc::Sender<()>),
+}
+
+/// Numbers the temporary files, so concurrent writes never share one.
+static WRITES: AtomicU64 = AtomicU64::new(0);
+
+/// A `ListingCache` that survives restarts: one JSON file per entry in a directory.
+///
+/// Expiry uses wall-clock time so entries written by a previous run keep their TTL.
+/// An in-memory index of file name → key and end of grace period avoids touching the
+/// disk for misses, stats and invalidation, and picks the entry closest to expiry when
+/// `max_entries` is exceeded. Writes and removals update the index right away and are
+/// queued for a writer thread, so callers on the async runtime never wait on the disk.
+/// Files are written to a temporary name and renamed, so a crash never leaves a
+/// half-written entry.
+pub struct DiskCache {
+    dir: PathBuf,
+    max_entries: usize,
+    index: Arc<Mutex<Index>>,
+    jobs: mpsc::Sender<Job>,
+    counters: CacheCounters,
+}
+
+impl DiskCache {
+    /// Open (creating if needed) the cache in `dir`, dropping expired, unreadable and
+    /// half-written entries left by earlier runs.
+    pub fn open(dir: impl Into<PathBuf>, max_entries: usize) -> io::Result<Self> {
+        let dir = dir.into();
+        fs::create_dir_all(&dir)?;
+        let now = now_millis();
+        let mut index = Index::default();
+        for file in fs::read_dir(&dir)? {
+            let path = file?.path();
+            let Some(name) = path.file_name().and_then(|n| n.to_str()).map(String::from) else {
+                continue;
+            };
+            match path.extension().and_then(|e| e.to_str()) {
+                Some("json") => {}
+                Some("tmp") => {
+                    let _ = fs::remove_file(&path);
+                    continue;
+                }
+                _ => continue,
+            }
+            match read_entry(&path) {
+                Some(entry) if entry.evict_at() > now => {
+                    let evict_at = entry.evict_at();
+                    let size = fs::metadata(&path).map_or(0, |meta| meta.len());
+                    index.insert(
+                        name,
+                        Indexed {
+                            key: entry.key,
+                            evict_at,
+                            size,
+                            pending: None,
+                        },
+                    );
+                }
+                _ => {
+                    let _ = fs::remove_file(&path);
+                }
+            }
+        }
+        let index = Arc::new(Mutex::new(index));
+        let (jobs, queue) = mpsc::channel();
+        let writer = Writer {
+            dir: dir.clone(),
+            index: Arc::clone(&index),
+        };
+        thread::Builder::new()
+            .name("disk-cache-writer".into())
+            .spawn(move || writer.run(&queue))?;
+        let cache = Self {
+            dir,
+            max_entries: max_entries.max(1),
+            index,
+            jobs,
+            counters: CacheCounters::default(),
+        };
+        cache.evict_over_capacity();
+        Ok(cache)
+    }
+
+    /// Number of live entries on disk.
+    pub fn len(&self) -> usize {
+        self.index.lock().map_or(0, |index| index.files.len())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Block until every write and removal queued so far has reached the disk.
+    pub fn flush(&self) {
+        let (done, flushed) = mpsc::channel();
+        if self.jobs.send(Job::Flush(done)).is_ok() {
+            let _ = flushed.recv();
+        }
+    }
+
+    /// The value under `key`, while fresh or within its grace period.
+    pub fn get_hit(&self, key: &str) -> Option<DiskHit> {
+        let name = file_name(key);
+        let (evict_at, pending) = {
+            let index = self.index.lock().ok()?;
+            let indexed = index.files.get(&name)?;
+            (indexed.evict_at, indexed.pending.clone())
+        };
+        let now = now_millis();
+        if evict_at <= now {
+            self.remove(&name);
+            return None;
+        }
+        let entry = if let Some(entry) = pending {
+            entry
+        } else {
+            let Some(entry) = read_entry(&self.dir.join(&name)) else {
+                self.remove(&name);
+                return None;
+            };
+            Arc::new(entry)
+        };
+        if entry.key != key {
+            return None;
+        }
+        let fresh_for = entry.expires_at.saturating_sub(now);
+        Some(DiskHit {
+            age: Duration::from_millis(now.saturating_sub(entry.stored_at.min(now))),
+            fresh_for: Duration::from_millis(fresh_for),
+            grace: Duration::from_millis(entry.evict_at().saturating_sub(now) - fresh_for),
+            value: entry.value.clone(),
+        })
+    }
+
+    fn remove(&self, name: &str) {
+        if let Ok(mut index) = self.index.lock() {
+            index.remove(name);
+            self.queue(Job::Remove(vec![name.to_string()]));
+        }
+    }
+
+    /// Hand `job` to the writer. Called with the index locked, so the writer sees the
+    /// operations on a file in the order they changed the index.
+    fn queue(&self, job: Job) {
+        if self.jobs.send(job).is_err() {
+            tracing::warn!("Disk cache writer stopped, skipping a file operation");
+        }
+    }
+
+    /// Drop the entries closest to the end of their grace period until at most `max_entries` remain.
+    fn evict_over_capacity(&self) {
+        let Ok(mut index) = self.index.lock() else {
+            return;
+        };
+        let mut evicted = Vec::new();
+        while index.files.len() > self.max_entries {
+            let Some(name) = index.soonest() else {
+                break;
+            };
+            index.remove(&name);
+            evicted.push(name);
+            self.counters.record_eviction();
+        }
+        if !evicted.is_empty() {
+            self.queue(Job::Remove(evicted));
+        }
+    }
+}
+
+impl Drop for DiskCache {
+    /// Queued writes are finished before the cache goes away, so a restart finds them.
+    fn drop(&mut self) {
+        self.flush();
+    }
+}
+
+/// Runs the queued [`Job`]s of one [`DiskCache`] until the cache is dropped.
+struct Writer {
+    dir: PathBuf,
+    index: Arc<Mutex<Index>>,
+}
+
+impl Writer {
+    fn run(&self, queue: &mpsc::Receiver<Job>) {
+        for job in queue {
+            match job {
+                Job::Write { name, entry, bytes } => self.write(&name, &entry, &bytes),
+                Job::Remove(names) => {
+                    for name in names {
+                        let _ = fs::remove_file(self.dir.join(name));
+                    }
+                }
+                Job::Flush(done) => {
+                    let _ = done.send(());
+                }
+            }
+        }
+    }
+
+    /// Write `bytes` to the file `name` and stop serving `entry` from the index. A
+    /// failed write also drops the entry, so an older file can't come back on restart.
+    fn write(&self, name: &str, entry: &Arc<DiskEntry>, bytes: &[u8]) {
+        let path = self.dir.join(name);
+        let tmp = self.dir.join(format!(
+            "{name}.{}.{}.tmp",
+            std::process::id(),
+            WRITES.fetch_add(1, Ordering::Relaxed)
+        ));
+        let written = fs::write(&tmp, bytes).and_then(|()| fs::rename(&tmp, &path));
+        let Ok(mut index) = self.index.lock() else {
+            return;
+        };
+        // A newer write of the key is queued behind this one, or the key was removed
+        let current = index
+            .files
+            .get(name)
+            .and_then(|indexed| indexed.pending.as_ref())
+            .is_some_and(|pending| Arc::ptr_eq(pending, entry));
+        match written {
+            Ok(()) if current => {
+                if let Some(indexed) = index.files.get_mut(name) {
+                    indexed.pending = None;
+                }
+            }
+            Ok(()) => {}
+            Err(e) => {
+                tracing::warn!(key = entry.key, error = %e, "Disk cache write failed, dropping the entry");
+                let _ = fs::remove_file(&tmp);
+                if current {
+                    index.remove(name);
+                    let _ = fs::remove_file(&path);
+                }
+            }
+        }
+    }
+}
+
+impl ListingCache for DiskCache {
+    fn get(&self, key: &str) -> Option<CachedValue> {
+        self.counters.record_read(self.peek(key))
+    }
+
+    fn peek(&self, key: &str) -> Option<CachedValue> {
+        self.get_hit(key).map(|hit| CachedValue {
+            stale: hit.fresh_for.is_zero(),
+            age: hit.age,
+            value: hit.value,
+        })
+    }
+
+    fn set_with_grace(&self, key: &str, value: &str, ttl: Duration, grace: Duration) {
+        let name = file_name(key);
+        let now = now_millis();
+        let expires_at = now.saturating_add(millis(ttl));
+        let entry = Arc::new(DiskEntry {
+            key: key.to_string(),
+            stored_at: now,
+            expires_at,
+            stale_until: expires_at.saturating_add(millis(grace)),
+            value: value.to_string(),
+        });
+        let bytes = match serde_json::to_vec(&*entry) {
+            Ok(bytes) => bytes,
+            Err(e) => {
+                tracing::warn!(key, error = %e, "Disk cache entry not serializable, skipping");
+                return;
+            }
+        };
+        if let Ok(mut index) = self.index.lock() {
+            index.insert(
+                name.clone(),
+                Indexed {
+                    evict_at: entry.evict_at(),
+                    key: entry.key.clone(),
+                    size: bytes.len() as u64,
+                    pending: Some(Arc::clone(&entry)),
+                },
+            );
+            self.queue(Job::Write { name, entry, bytes });
+        }
+        self.evict_over_capacity();
+    }
+
+    fn stats(&self) -> CacheStats {
+        let mut stats = self.counters.stats(self.max_entries);
+        let now = now_millis();
+        if let Ok(index) = self.index.lock() {
+            for indexed in index
+                .files
+                .values()
+                .filter(|indexed| indexed.evict_at > now)
+            {
+                stats.add_entry(&indexed.key);
+                stats.bytes += indexed.size;
+            }
+        }
+        stats
+    }
+
+    fn invalidate_prefix(&self, prefix: &str) -> usize {
+        let Ok(mut index) = self.index.lock() else {
+            return 0;
+        };
+        let names: Vec<String> = index
+            .files
+            .iter()
+            .filter(|(_, indexed)| indexed.key.starts_with(prefix))
+            .map(|(name, _)| name.clone())
+            .collect();
+        for name in &names {
+            index.remove(name);
+        }
+        let removed = names.len();
+        if removed > 0 {
+            self.queue(Job::Remove(names));
+        }
+        removed
+    }
+
+    fn purge_incompatible(&self, key: &str) {
+        let name = file_name(key);
+        let Ok(mut index) = self.index.lock() else {
+            return;
+        };
+        let indexed = index.remove(&name);
+        self.queue(Job::Remove(vec![name]));
+        if indexed.is_some() {
+            self.counters.record_incompatible();
+        }
+    }
+}
+
+fn read_entry(path: &Path) -> Option<DiskEntry> {
+    let bytes = fs::read(path).ok()?;
+    serde_json::from_slice(&bytes).ok()
+}
+
+/// File name for `key`: its 64-bit FNV-1a hash, which (unlike `DefaultHasher`) is stable
+/// across Rust versions, so files written by an older build are still found.
+fn file_name(key: &str) -> String {
+    let hash = key.bytes().fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+    });
+    format!("{hash:016x}.json")
+}
+
+fn millis(duration: Duration) -> u64 {
+    u64::try_from(duration.as_millis()).unwrap_or(u64::MAX)
+}
+
+fn now_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, millis)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn set_then_get_returns_value() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = DiskCache::open(dir.path(), 10).unwrap();
+        assert!(cache.get("key1").is_none());
+        cache.set("key1", "value1", Duration::from_mins(1));
+        assert_eq!(cache.get("key1").unwrap().value, "value1");
+    }
+
+    #[test]
+    fn entries_survive_reopen() {
+        let dir = tempfile::tempdir().unwrap();
+        {
+            let cache = DiskCache::open(dir.path(), 10).unwrap();
+            cache.set("detail:42", "{\"id\":\"42\"}", Duration::from_mins(1));
+            cache.set("gone", "x", Duration::ZERO);
+        }
+        let cache = DiskCache::open(dir.path(), 10).unwrap();
+        assert_eq!(cache.len(), 1);
+        assert_eq!(cache.get("detail:42").unwrap().value, "{\"id\":\"42\"}");
+        assert!(cache.get("gone").is_none());
+    }
+
+    #[test]
+    fn expired_entry_returns_none_and_is_removed() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = DiskCache::open(dir.path(), 10).unwrap();
+        cache.set("key1", "value1", Duration::from_millis(0));
+        std::thread::sleep(Duration::from_millis(2));
+        assert!(cache.get("key1").is_none());
+        assert!(cache.is_empty());
+        cache.flush();
+        assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 0);
+    }
+
+    #[test]
+    fn evicts_entries_closest_to_expiry_at_capacity() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = DiskCache::open(dir.path(), 2).unwrap();
+        cache.set("short", "1", Duration::from_mins(1));
+        cache.set("long", "2", Duration::from_hours(1));
+        cache.set("medium", "3", Duration::from_mins(30));
+        assert!(cache.get("short").is_none());
+        assert_eq!(cache.get("long").unwrap().value, "2");
+        assert_eq!(cache.get("medium").unwrap().value, "3");
+    }
+
+    #[test]
+    fn stats_and_invalidation_survive_reopen() {
+        let dir = tempfile::tempdir().unwrap();
+        {
+            let cache = DiskCache::open(dir.path(), 2).unwrap();
+            cache.set("detail:1", "1", Duration::from_mins(1));
+            cache.set("gql:detail:1", "1", Duration::from_hours(1));
+            cache.set("search:paris", "[]", Duration::from_hours(1));
+            assert_eq!(cache.stats().evictions, 1);
+        }
+        let cache = DiskCache::open(dir.path(), 2).unwrap();
+        assert!(cache.get("search:paris").is_some());
+        assert!(cache.get("detail:1").is_none());
+        let stats = cache.stats();
+        assert_eq!((stats.hits, stats.misses, stats.entries), (1, 1, 2));
+        assert!(stats.bytes > 0);
+        assert_eq!(stats.entries_by_prefix["gql:detail:"], 1);
+
+        assert_eq!(cache.invalidate_prefix("gql:"), 1);
+        assert!(cache.get("gql:detail:1").is_none());
+        cache.flush();
+        assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 1);
+
+        cache.purge_incompatible("search:paris");
+        cache.purge_incompatible("search:paris");
+        assert_eq!(cache.stats().incompatible, 1);
+        cache.flush();
+        assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 0);
+    }
+
+    #[test]
+    fn concurrent_writes_of_one_key_all_land() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = DiskCache::open(dir.path(), 10).unwrap();
+        thread::scope(|scope| {
+            for writer in 0..4 {
+                let cache = &cache;
+                scope.spawn(move || {
+                    for i in 0..25 {
+                        cache.set("detail:1", &format!("{writer}-{i}"), Duration::from_mins(1));
+                    }
+                });
+            }
+        });
+        let last = cache.get("detail:1").unwrap().value;
+        drop(cache);
+
+        // Only the entry is left, holding the last value written
+        let files: Vec<_> = fs::read_dir(dir.path()).unwrap().collect();
+        assert_eq!(files.len(), 1);
+        let cache = DiskCache::open(dir.path(), 10).unwrap();
+        assert_eq!(cache.get("detail:1").unwrap().value, last);
+    }
+
+    #[test]
+    fn expired_entry_within_grace_is_stale_across_reopen() {
+        let dir = tempfile::tempdir().unwrap();
+        DiskCache::open(dir.path(), 10).unwrap().set_with_grace(
+            "calendar:1",
+            "[]",
+            Duration::ZERO,
+            Duration::from_mins(1),
+        );
+        std::thread::sleep(Duration::from_millis(2));
+        let cache = DiskCache::open(dir.path(), 10).unwrap();
+        let hit = cache.get("calendar:1").unwrap();
+        assert!(hit.stale);
+        assert!(hit.age >= Duration::from_millis(2));
+    }
+
+    #[test]
+    fn entries_without_grace_fields_still_load() {
+        let dir = tempfile::tempdir().unwrap();
+        let entry = serde_json::json!({
+            "key": "host:1",
+            "expires_at": now_millis() + 60_000,
+            "value": "{}",
+        });
+        fs::write(dir.path().join(file_name("host:1")), entry.to_string()).unwrap();
+        let cache = DiskCache::open(dir.path(), 10).unwrap();
+        let hit = cache.get("host:1").unwrap();
+        assert!(!hit.stale);
+        assert_eq!(hit.value, "{}");
+    }
+
+    #[test]
+    fn corrupt_and_temporary_files_are_cleaned_up() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join(file_name("bad")), "not json").unwrap();
+        fs::write(dir.path().join("0000.json.1.tmp"), "{").unwrap();
+        let cache = DiskCache::open(dir.path(), 10).unwrap();
+        assert!(cache.is_empty());
+        assert!(cache.get("bad").is_none());
+        assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 0);
+    }
+
+    #[test]
+    fn file_names_are_stable() {
+        // Changing the hash would orphan every cache written by earlier versions
+        assert_eq!(file_name(""), "cbf29ce484222325.json");
+        assert_eq!(file_name("a"), "af63dc4c8601ec8c.json");
+    }
+}
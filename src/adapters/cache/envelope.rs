@@ -0,0 +1,67 @@
+use chrono::{DateTime, Utc};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+/// Version of what [`CacheAside`] stores: the envelope and the serde shape of the domain
+/// types inside it. Bump it when either changes incompatibly, so entries written by an
+/// older build (say, in a persistent disk cache) are purged instead of misread.
+///
+/// [`CacheAside`]: crate::adapters::cache::cache_aside::CacheAside
+pub const CACHE_SCHEMA_VERSION: u32 = 1;
+
+/// A cached value with the schema version it was written in, when it was fetched from
+/// Airbnb and which adapter fetched it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CacheEnvelope<T> {
+    pub schema: u32,
+    pub fetched_at: DateTime<Utc>,
+    /// The adapter that fetched `data`, e.g. `scraper` or `graphql`.
+    pub source: String,
+    pub data: T,
+}
+
+impl<T> CacheEnvelope<T> {
+    /// Wrap `data` just fetched by `source`.
+    pub fn new(source: &str, data: T) -> Self {
+        Self {
+            schema: CACHE_SCHEMA_VERSION,
+            fetched_at: Utc::now(),
+            source: source.to_string(),
+            data,
+        }
+    }
+}
+
+impl<T: DeserializeOwned> CacheEnvelope<T> {
+    /// Parse a stored envelope. `None` when it was written in another schema version or
+    /// does not decode as `T`, including bare values stored before envelopes existed.
+    pub fn decode(stored: &str) -> Option<Self> {
+        serde_json::from_str::<Self>(stored)
+            .ok()
+            .filter(|envelope| envelope.schema == CACHE_SCHEMA_VERSION)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips_the_current_schema() {
+        let envelope = CacheEnvelope::new("graphql", vec![1, 2]);
+        let stored = serde_json::to_string(&envelope).unwrap();
+        assert_eq!(CacheEnvelope::decode(&stored), Some(envelope));
+    }
+
+    #[test]
+    fn rejects_other_versions_and_bare_values() {
+        let mut envelope = CacheEnvelope::new("scraper", "x".to_string());
+        envelope.schema = CACHE_SCHEMA_VERSION + 1;
+        let newer = serde_json::to_string(&envelope).unwrap();
+        assert_eq!(CacheEnvelope::<String>::decode(&newer), None);
+        assert_eq!(CacheEnvelope::<String>::decode("\"x\""), None);
+
+        let current = serde_json::to_string(&CacheEnvelope::new("scraper", "x")).unwrap();
+        assert_eq!(CacheEnvelope::<u32>::decode(&current), None);
+    }
+}
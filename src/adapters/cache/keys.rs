@@ -0,0 +1,209 @@
+use std::fmt::Display;
+
+use percent_encoding::{AsciiSet, CONTROLS, utf8_percent_encode};
+
+use crate::domain::search_params::SearchParams;
+use crate::ports::cache::GRAPHQL_KEY_TAG;
+
+/// Bumped whenever the key layout changes, so entries written by an older build are
+/// never read back as if they answered today's request. Changes to the shape of cached
+/// values bump [`CACHE_SCHEMA_VERSION`](super::envelope::CACHE_SCHEMA_VERSION) instead.
+pub const CACHE_KEY_VERSION: u32 = 1;
+
+/// Characters escaped in key values: the separators `:` and `=`, `%` itself, and
+/// control characters. Escaping them keeps every field boundary unambiguous, so two
+/// different requests cannot render to the same key.
+const VALUE: &AsciiSet = &CONTROLS.add(b':').add(b'=').add(b'%');
+
+/// Builds the cache key of every [`AirbnbClient`] operation.
+///
+/// A key names the operation, its subject, every other input that shapes the request,
+/// the locale and currency the answer is in, and [`CACHE_KEY_VERSION`]:
+///
+/// ```text
+/// [gql:]search:paris:ci=2025-06-01:a=2:cur=abc:l=en:c=USD:v=1
+/// [gql:]detail:42:l=en:c=USD:v=1
+/// ```
+///
+/// Keys start with the operation (`search:`, `detail:`, ...), prefixed with `gql:` for
+/// the GraphQL adapter, which is what [`key_prefix`] groups them by and what
+/// `airbnb_cache_invalidate` matches. Location and property type are lowercased, as
+/// Airbnb treats them case-insensitively.
+///
+/// [`AirbnbClient`]: crate::ports::airbnb_client::AirbnbClient
+/// [`key_prefix`]: crate::ports::cache::key_prefix
+#[derive(Debug, Clone)]
+pub struct CacheKeys {
+    source: &'static str,
+    locale: String,
+    currency: String,
+}
+
+impl CacheKeys {
+    /// Keys for the HTML scraper.
+    pub fn scraper(locale: &str, currency: &str) -> Self {
+        Self::new("", locale, currency)
+    }
+
+    /// Keys for the GraphQL client, tagged `gql:` so they never collide with the
+    /// scraper's (the two parse different pages into the same types).
+    pub fn graphql(locale: &str, currency: &str) -> Self {
+        Self::new(GRAPHQL_KEY_TAG, locale, currency)
+    }
+
+    fn new(source: &'static str, locale: &str, currency: &str) -> Self {
+        Self {
+            source,
+            locale: locale.to_string(),
+            currency: currency.to_string(),
+        }
+    }
+
+    pub fn search(&self, params: &SearchParams) -> String {
+        let mut key = self.start("search", &params.location.to_lowercase());
+        push_field(&mut key, "ci", params.checkin.as_ref());
+        push_field(&mut key, "co", params.checkout.as_ref());
+        push_field(&mut key, "a", params.adults);
+        push_field(&mut key, "ch", params.children);
+        push_field(&mut key, "inf", params.infants);
+        push_field(&mut key, "p", params.pets);
+        push_field(&mut key, "min", params.min_price);
+        push_field(&mut key, "max", params.max_price);
+        push_field(
+            &mut key,
+            "pt",
+            params.property_type.as_deref().map(str::to_lowercase),
+        );
+        push_field(&mut key, "cur", params.cursor.as_ref());
+        self.finish(key)
+    }
+
+    pub fn detail(&self, id: &str) -> String {
+        self.finish(self.start("detail", id))
+    }
+
+    pub fn reviews(&self, id: &str, cursor: Option<&str>) -> String {
+        let mut key = self.start("reviews", id);
+        push_field(&mut key, "cur", cursor);
+        self.finish(key)
+    }
+
+    pub fn calendar(&self, id: &str, months: u32) -> String {
+        let mut key = self.start("calendar", id);
+        push_field(&mut key, "m", Some(months));
+        self.finish(key)
+    }
+
+    pub fn host(&self, listing_id: &str) -> String {
+        self.finish(self.start("host", listing_id))
+    }
+
+    fn start(&self, operation: &str, subject: &str) -> String {
+        format!(
+            "{}{operation}:{}",
+            self.source,
+            utf8_percent_encode(subject, VALUE)
+        )
+    }
+
+    fn finish(&self, mut key: String) -> String {
+        push_field(&mut key, "l", Some(&self.locale));
+        push_field(&mut key, "c", Some(&self.currency));
+        push_field(&mut key, "v", Some(CACHE_KEY_VERSION));
+        key
+    }
+}
+
+/// Append `:{name}={value}` when the input was given.
+fn push_field(key: &mut String, name: &str, value: Option<impl Display>) {
+    if let Some(value) = value {
+        key.push(':');
+        key.push_str(name);
+        key.push('=');
+        key.extend(utf8_percent_encode(&value.to_string(), VALUE));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn keys() -> CacheKeys {
+        CacheKeys::scraper("en", "USD")
+    }
+
+    fn paris() -> SearchParams {
+        SearchParams {
+            location: "Paris".into(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn search_key_with_location_only() {
+        assert_eq!(keys().search(&paris()), "search:paris:l=en:c=USD:v=1");
+    }
+
+    #[test]
+    fn search_key_includes_all_params() {
+        let params = SearchParams {
+            checkin: Some("2025-06-01".into()),
+            checkout: Some("2025-06-05".into()),
+            adults: Some(2),
+            children: Some(1),
+            infants: Some(1),
+            pets: Some(1),
+            min_price: Some(50),
+            max_price: Some(200),
+            property_type: Some("Entire home".into()),
+            cursor: Some("page2".into()),
+            ..paris()
+        };
+        assert_eq!(
+            keys().search(&params),
+            "search:paris:ci=2025-06-01:co=2025-06-05:a=2:ch=1:inf=1:p=1:min=50:max=200\
+             :pt=entire home:cur=page2:l=en:c=USD:v=1"
+        );
+    }
+
+    #[test]
+    fn separators_in_values_are_escaped() {
+        let params = SearchParams {
+            location: "a:ci=b".into(),
+            ..Default::default()
+        };
+        assert_eq!(keys().search(&params), "search:a%3Aci%3Db:l=en:c=USD:v=1");
+        assert_ne!(
+            keys().reviews("1", Some("x:l=fr")),
+            keys().reviews("1:cur=x", Some("l=fr"))
+        );
+    }
+
+    #[test]
+    fn locale_currency_and_adapter_are_part_of_the_key() {
+        let search = paris();
+        assert_ne!(
+            keys().search(&search),
+            CacheKeys::scraper("fr", "USD").search(&search)
+        );
+        assert_ne!(
+            keys().search(&search),
+            CacheKeys::scraper("en", "EUR").search(&search)
+        );
+        assert_eq!(
+            CacheKeys::graphql("en", "USD").detail("42"),
+            "gql:detail:42:l=en:c=USD:v=1"
+        );
+    }
+
+    #[test]
+    fn listing_keys() {
+        assert_eq!(keys().reviews("42", None), "reviews:42:l=en:c=USD:v=1");
+        assert_eq!(
+            keys().reviews("42", Some("first")),
+            "reviews:42:cur=first:l=en:c=USD:v=1"
+        );
+        assert_eq!(keys().calendar("42", 3), "calendar:42:m=3:l=en:c=USD:v=1");
+        assert_eq!(keys().host("42"), "host:42:l=en:c=USD:v=1");
+    }
+}
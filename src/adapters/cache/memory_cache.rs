@@ -4,15 +4,87 @@ use std::time::{Duration, Instant};
 use lru::LruCache;
 use std::num::NonZeroUsize;
 
-use crate::ports::cache::ListingCache;
+use crate::adapters::cache::counters::CacheCounters;
+use crate::config::types::CacheCompression;
+use crate::ports::cache::{CacheStats, CachedValue, ListingCache};
+
+/// Values shorter than this are stored plain even with compression on; LZ4 saves
+/// little on them and costs a copy on every read.
+const COMPRESS_MIN_BYTES: usize = 512;
 
 struct CacheEntry {
-    value: String,
+    value: StoredValue,
+    stored_at: Instant,
     expires_at: Instant,
+    /// End of the grace period; equal to `expires_at` when there is none.
+    stale_until: Instant,
+}
+
+/// A value as held in memory.
+enum StoredValue {
+    Plain(String),
+    /// LZ4 block with the uncompressed length prepended.
+    Lz4(Box<[u8]>),
+}
+
+impl StoredValue {
+    fn new(value: &str, compression: CacheCompression) -> Self {
+        if compression == CacheCompression::Lz4 && value.len() >= COMPRESS_MIN_BYTES {
+            let compressed = lz4_flex::compress_prepend_size(value.as_bytes());
+            if compressed.len() < value.len() {
+                return Self::Lz4(compressed.into_boxed_slice());
+            }
+        }
+        Self::Plain(value.to_string())
+    }
+
+    fn len(&self) -> usize {
+        match self {
+            Self::Plain(value) => value.len(),
+            Self::Lz4(bytes) => bytes.len(),
+        }
+    }
+
+    fn decode(&self) -> Option<String> {
+        match self {
+            Self::Plain(value) => Some(value.clone()),
+            Self::Lz4(bytes) => lz4_flex::decompress_size_prepended(bytes)
+                .ok()
+                .and_then(|raw| String::from_utf8(raw).ok()),
+        }
+    }
+}
+
+/// The LRU and the bytes it holds, kept in step under one lock.
+struct Entries {
+    lru: LruCache<String, CacheEntry>,
+    /// Sum of [`entry_size`] over `lru`.
+    bytes: usize,
 }
 
+impl Entries {
+    fn pop(&mut self, key: &str) -> bool {
+        let Some(entry) = self.lru.pop(key) else {
+            return false;
+        };
+        self.bytes -= entry_size(key, &entry);
+        true
+    }
+}
+
+/// What an entry counts against `max_bytes`: its key and stored value.
+fn entry_size(key: &str, entry: &CacheEntry) -> usize {
+    key.len() + entry.value.len()
+}
+
+/// An in-process LRU cache bounded by entry count and, optionally, by the bytes of
+/// its keys and stored values. With [`CacheCompression::Lz4`], large values are kept
+/// compressed and decompressed on read.
 pub struct MemoryCache {
-    inner: RwLock<LruCache<String, CacheEntry>>,
+    inner: RwLock<Entries>,
+    max_bytes: Option<usize>,
+    compression: CacheCompression,
+    counters: CacheCounters,
 }
 
 impl MemoryCache {
@@ -22,39 +94,175 @@ impl MemoryCache {
             NonZeroUsize::new(100).unwrap()
         });
         Self {
-            inner: RwLock::new(LruCache::new(cap)),
+            inner: RwLock::new(Entries {
+                lru: LruCache::new(cap),
+                bytes: 0,
+            }),
+            max_bytes: None,
+            compression: CacheCompression::default(),
+            counters: CacheCounters::default(),
         }
     }
-}
 
-impl ListingCache for MemoryCache {
-    fn get(&self, key: &str) -> Option<String> {
-        let mut cache = self.inner.write().map_or_else(
+    /// Also evict once the stored keys and values exceed `max_bytes`.
+    #[must_use]
+    pub fn with_max_bytes(mut self, max_bytes: Option<u64>) -> Self {
+        self.max_bytes = max_bytes.map(|max| usize::try_from(max).unwrap_or(usize::MAX));
+        self
+    }
+
+    #[must_use]
+    pub fn with_compression(mut self, compression: CacheCompression) -> Self {
+        self.compression = compression;
+        self
+    }
+
+    /// Store a value that is already `age` old, fresh for `ttl` and stale for a further
+    /// `grace` from now. Used to copy entries from another tier without resetting them.
+    pub fn insert_aged(
+        &self,
+        key: &str,
+        value: &str,
+        age: Duration,
+        ttl: Duration,
+        grace: Duration,
+    ) {
+        let now = Instant::now();
+        let expires_at = now + ttl;
+        let entry = CacheEntry {
+            value: StoredValue::new(value, self.compression),
+            stored_at: now.checked_sub(age).unwrap_or(now),
+            expires_at,
+            stale_until: expires_at + grace,
+        };
+        let size = entry_size(key, &entry);
+        let Ok(mut entries) = self.inner.write() else {
+            tracing::error!("Cache lock poisoned on set('{key}'), skipping write");
+            return;
+        };
+        if self.max_bytes.is_some_and(|max| size > max) {
+            // The previous value is outdated, so it goes too
+            entries.pop(key);
+            tracing::debug!(key, size, "Value larger than cache.max_bytes, not cached");
+            return;
+        }
+        entries.bytes += size;
+        if let Some((evicted_key, evicted)) = entries.lru.push(key.to_string(), entry) {
+            entries.bytes -= entry_size(&evicted_key, &evicted);
+            if evicted_key != key {
+                self.counters.record_eviction();
+            }
+        }
+        self.shrink_to_budget(&mut entries);
+    }
+
+    /// Bring `entries` within `max_bytes`: expired entries go first (they are not
+    /// counted as evictions), then the least recently used.
+    fn shrink_to_budget(&self, entries: &mut Entries) {
+        let Some(max) = self.max_bytes else {
+            return;
+        };
+        if entries.bytes <= max {
+            return;
+        }
+        let now = Instant::now();
+        let expired: Vec<String> = entries
+            .lru
+            .iter()
+            .filter(|(_, entry)| now > entry.stale_until)
+            .map(|(key, _)| key.clone())
+            .collect();
+        for key in &expired {
+            entries.pop(key);
+        }
+        while entries.bytes > max {
+            let Some((key, entry)) = entries.lru.pop_lru() else {
+                break;
+            };
+            entries.bytes -= entry_size(&key, &entry);
+            self.counters.record_eviction();
+        }
+    }
+
+    fn read(&self, key: &str) -> Option<CachedValue> {
+        let mut entries = self.inner.write().map_or_else(
             |_| {
                 tracing::error!("Cache lock poisoned on get('{key}'), returning miss");
                 None
             },
             Some,
         )?;
-        let entry = cache.get(key)?;
-        if Instant::now() > entry.expires_at {
-            cache.pop(key);
+        let entry = entries.lru.get(key)?;
+        let now = Instant::now();
+        if now > entry.stale_until {
+            entries.pop(key);
             return None;
         }
-        Some(entry.value.clone())
+        let hit = entry.value.decode().map(|value| CachedValue {
+            value,
+            age: now.duration_since(entry.stored_at),
+            stale: now > entry.expires_at,
+        });
+        if hit.is_none() {
+            tracing::warn!(key, "Undecodable cache entry, dropping it");
+            entries.pop(key);
+        }
+        hit
     }
+}
 
-    fn set(&self, key: &str, value: &str, ttl: Duration) {
-        if let Ok(mut cache) = self.inner.write() {
-            cache.put(
-                key.to_string(),
-                CacheEntry {
-                    value: value.to_string(),
-                    expires_at: Instant::now() + ttl,
-                },
-            );
-        } else {
-            tracing::error!("Cache lock poisoned on set('{key}'), skipping write");
+impl ListingCache for MemoryCache {
+    fn get(&self, key: &str) -> Option<CachedValue> {
+        self.counters.record_read(self.read(key))
+    }
+
+    fn peek(&self, key: &str) -> Option<CachedValue> {
+        self.read(key)
+    }
+
+    fn set_with_grace(&self, key: &str, value: &str, ttl: Duration, grace: Duration) {
+        self.insert_aged(key, value, Duration::ZERO, ttl, grace);
+    }
+
+    fn stats(&self) -> CacheStats {
+        let Ok(entries) = self.inner.read() else {
+            return CacheStats::default();
+        };
+        let mut stats = self.counters.stats(entries.lru.cap().get());
+        let now = Instant::now();
+        for (key, entry) in &entries.lru {
+            if now <= entry.stale_until {
+                stats.add_entry(key);
+            }
+        }
+        stats.bytes = entries.bytes as u64;
+        stats.max_bytes = self.max_bytes.map(|max| max as u64);
+        stats
+    }
+
+    fn invalidate_prefix(&self, prefix: &str) -> usize {
+        let Ok(mut entries) = self.inner.write() else {
+            tracing::error!("Cache lock poisoned on invalidate('{prefix}'), skipping");
+            return 0;
+        };
+        let keys: Vec<String> = entries
+            .lru
+            .iter()
+            .map(|(key, _)| key)
+            .filter(|key| key.starts_with(prefix))
+            .cloned()
+            .collect();
+        for key in &keys {
+            entries.pop(key);
+        }
+        keys.len()
+    }
+
+    fn purge_incompatible(&self, key: &str) {
+        if let Ok(mut entries) = self.inner.write()
+            && entries.pop(key)
+        {
+            self.counters.record_incompatible();
         }
     }
 }
@@ -73,7 +281,9 @@ mod tests {
     fn set_then_get_returns_value() {
         let cache = MemoryCache::new(10);
         cache.set("key1", "value1", Duration::from_mins(1));
-        assert_eq!(cache.get("key1"), Some("value1".to_string()));
+        let hit = cache.get("key1").unwrap();
+        assert_eq!(hit.value, "value1");
+        assert!(!hit.stale);
     }
 
     #[test]
@@ -85,6 +295,30 @@ mod tests {
         assert!(cache.get("key1").is_none());
     }
 
+    #[test]
+    fn expired_entry_within_grace_is_stale() {
+        let cache = MemoryCache::new(10);
+        cache.set_with_grace("key1", "value1", Duration::ZERO, Duration::from_mins(1));
+        std::thread::sleep(Duration::from_millis(2));
+        let hit = cache.get("key1").unwrap();
+        assert_eq!(hit.value, "value1");
+        assert!(hit.stale);
+        assert!(hit.age >= Duration::from_millis(2));
+    }
+
+    #[test]
+    fn insert_aged_keeps_age() {
+        let cache = MemoryCache::new(10);
+        cache.insert_aged(
+            "key1",
+            "value1",
+            Duration::from_mins(5),
+            Duration::from_mins(1),
+            Duration::ZERO,
+        );
+        assert!(cache.get("key1").unwrap().age >= Duration::from_mins(5));
+    }
+
     #[test]
     fn cache_eviction_at_capacity() {
         let cache = MemoryCache::new(2);
@@ -93,8 +327,114 @@ mod tests {
         cache.set("c", "3", Duration::from_mins(1));
         // "a" should be evicted (LRU)
         assert!(cache.get("a").is_none());
-        assert_eq!(cache.get("b"), Some("2".to_string()));
-        assert_eq!(cache.get("c"), Some("3".to_string()));
+        assert_eq!(cache.get("b").unwrap().value, "2");
+        assert_eq!(cache.get("c").unwrap().value, "3");
+    }
+
+    #[test]
+    fn stats_count_reads_evictions_and_prefixes() {
+        let cache = MemoryCache::new(3);
+        cache.set("detail:1", "1", Duration::from_mins(1));
+        cache.set("detail:2", "2", Duration::from_mins(1));
+        cache.set("detail:2", "2", Duration::from_mins(1));
+        cache.set("gql:detail:3", "3", Duration::from_mins(1));
+        cache.set("search:paris", "[]", Duration::from_mins(1));
+        cache.set_with_grace("calendar:1", "[]", Duration::ZERO, Duration::from_mins(1));
+        std::thread::sleep(Duration::from_millis(2));
+        assert!(cache.get("calendar:1").unwrap().stale);
+        assert!(cache.get("search:paris").is_some());
+        assert!(cache.get("detail:1").is_none());
+
+        let stats = cache.stats();
+        assert_eq!(stats.hits, 2);
+        assert_eq!(stats.stale_hits, 1);
+        assert_eq!(stats.misses, 1);
+        // Overwriting "detail:2" is not an eviction
+        assert_eq!(stats.evictions, 2);
+        assert_eq!(stats.entries, 3);
+        assert_eq!(stats.capacity, 3);
+        assert_eq!(
+            stats.entries_by_prefix.into_iter().collect::<Vec<_>>(),
+            [
+                ("calendar:".to_string(), 1),
+                ("gql:detail:".to_string(), 1),
+                ("search:".to_string(), 1),
+            ]
+        );
+    }
+
+    #[test]
+    fn invalidate_prefix_removes_matching_keys() {
+        let cache = MemoryCache::new(10);
+        cache.set("detail:1", "1", Duration::from_mins(1));
+        cache.set("detail:2", "2", Duration::from_mins(1));
+        cache.set("gql:detail:1", "1", Duration::from_mins(1));
+        assert_eq!(cache.invalidate_prefix("detail:"), 2);
+        assert!(cache.get("detail:1").is_none());
+        assert!(cache.get("gql:detail:1").is_some());
+        assert_eq!(cache.invalidate_prefix(""), 1);
+        assert_eq!(cache.stats().entries, 0);
+    }
+
+    #[test]
+    fn byte_budget_evicts_least_recently_used() {
+        let cache = MemoryCache::new(100).with_max_bytes(Some(30));
+        cache.set("a", &"1".repeat(9), Duration::from_mins(1));
+        cache.set("b", &"2".repeat(9), Duration::from_mins(1));
+        cache.set("c", &"3".repeat(9), Duration::from_mins(1));
+        assert!(cache.get("a").is_some());
+        // 40 bytes: "b" is the least recently used
+        cache.set("d", &"4".repeat(9), Duration::from_mins(1));
+        assert!(cache.get("b").is_none());
+        assert!(cache.get("a").is_some());
+        let stats = cache.stats();
+        assert_eq!((stats.entries, stats.bytes, stats.evictions), (3, 30, 1));
+        assert_eq!(stats.max_bytes, Some(30));
+    }
+
+    #[test]
+    fn byte_budget_drops_expired_entries_first() {
+        let cache = MemoryCache::new(100).with_max_bytes(Some(30));
+        cache.set("old", &"0".repeat(12), Duration::ZERO);
+        cache.set("a", &"1".repeat(9), Duration::from_mins(1));
+        std::thread::sleep(Duration::from_millis(2));
+        cache.set("b", &"2".repeat(9), Duration::from_mins(1));
+        assert!(cache.get("a").is_some());
+        assert_eq!(cache.stats().evictions, 0);
+    }
+
+    #[test]
+    fn values_over_the_budget_are_not_cached() {
+        let cache = MemoryCache::new(10).with_max_bytes(Some(16));
+        cache.set("key", "small", Duration::from_mins(1));
+        cache.set("key", &"x".repeat(20), Duration::from_mins(1));
+        assert!(cache.get("key").is_none());
+        assert_eq!(cache.stats().bytes, 0);
+    }
+
+    #[test]
+    fn compressed_values_round_trip_and_count_compressed_size() {
+        let cache = MemoryCache::new(10).with_compression(CacheCompression::Lz4);
+        let json = format!(
+            "[{}]",
+            vec!["{\"price\":120,\"available\":true}"; 100].join(",")
+        );
+        cache.set("calendar:1", &json, Duration::from_mins(1));
+        cache.set("host:1", "{}", Duration::from_mins(1));
+        assert_eq!(cache.get("calendar:1").unwrap().value, json);
+        assert_eq!(cache.get("host:1").unwrap().value, "{}");
+        let stats = cache.stats();
+        assert!(stats.bytes < json.len() as u64 / 4, "{} bytes", stats.bytes);
+    }
+
+    #[test]
+    fn overwrite_and_invalidate_keep_byte_count() {
+        let cache = MemoryCache::new(10);
+        cache.set("k", "12345", Duration::from_mins(1));
+        cache.set("k", "12", Duration::from_mins(1));
+        assert_eq!(cache.stats().bytes, 3);
+        cache.invalidate_prefix("");
+        assert_eq!(cache.stats().bytes, 0);
     }
 
     #[test]
@@ -102,7 +442,7 @@ mod tests {
         let cache = MemoryCache::new(10);
         cache.set("key", "old_value", Duration::from_mins(1));
         cache.set("key", "new_value", Duration::from_mins(1));
-        assert_eq!(cache.get("key"), Some("new_value".to_string()));
+        assert_eq!(cache.get("key").unwrap().value, "new_value");
     }
 
     #[test]
@@ -110,7 +450,7 @@ mod tests {
         // max_entries=0 should fall back to NonZeroUsize(100), not panic
         let cache = MemoryCache::new(0);
         cache.set("key", "value", Duration::from_mins(1));
-        assert_eq!(cache.get("key"), Some("value".to_string()));
+        assert_eq!(cache.get("key").unwrap().value, "value");
     }
 
     #[test]
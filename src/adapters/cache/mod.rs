@@ -1 +1,7 @@
+pub mod cache_aside;
+pub mod counters;
+pub mod disk_cache;
+pub mod envelope;
+pub mod keys;
 pub mod memory_cache;
+pub mod tiered_cache;
@@ -0,0 +1,157 @@
+use std::time::Duration;
+
+use crate::adapters::cache::counters::CacheCounters;
+use crate::adapters::cache::disk_cache::DiskCache;
+use crate::adapters::cache::memory_cache::MemoryCache;
+use crate::ports::cache::{CacheStats, CachedValue, ListingCache};
+
+/// A `MemoryCache` in front of a `DiskCache`: hot entries are served from memory, and
+/// everything is also written to disk so it survives a restart.
+///
+/// A disk hit is copied into memory with its age and remaining TTL and grace period,
+/// so both tiers expire it at the same moment.
+///
+/// Stats count reads of either tier as one; entries, evictions and purges are the
+/// disk's, since the memory tier only holds copies.
+pub struct TieredCache {
+    memory: MemoryCache,
+    disk: DiskCache,
+    counters: CacheCounters,
+}
+
+impl TieredCache {
+    pub fn new(memory: MemoryCache, disk: DiskCache) -> Self {
+        Self {
+            memory,
+            disk,
+            counters: CacheCounters::default(),
+        }
+    }
+
+    fn read(&self, key: &str) -> Option<CachedValue> {
+        if let Some(hit) = self.memory.peek(key) {
+            return Some(hit);
+        }
+        let hit = self.disk.get_hit(key)?;
+        self.memory
+            .insert_aged(key, &hit.value, hit.age, hit.fresh_for, hit.grace);
+        Some(CachedValue {
+            stale: hit.fresh_for.is_zero(),
+            age: hit.age,
+            value: hit.value,
+        })
+    }
+}
+
+impl ListingCache for TieredCache {
+    fn get(&self, key: &str) -> Option<CachedValue> {
+        self.counters.record_read(self.read(key))
+    }
+
+    fn peek(&self, key: &str) -> Option<CachedValue> {
+        self.read(key)
+    }
+
+    fn set_with_grace(&self, key: &str, value: &str, ttl: Duration, grace: Duration) {
+        self.memory.set_with_grace(key, value, ttl, grace);
+        self.disk.set_with_grace(key, value, ttl, grace);
+    }
+
+    fn stats(&self) -> CacheStats {
+        let reads = self.counters.stats(0);
+        CacheStats {
+            hits: reads.hits,
+            stale_hits: reads.stale_hits,
+            misses: reads.misses,
+            ..self.disk.stats()
+        }
+    }
+
+    fn invalidate_prefix(&self, prefix: &str) -> usize {
+        self.memory.invalidate_prefix(prefix);
+        self.disk.invalidate_prefix(prefix)
+    }
+
+    fn purge_incompatible(&self, key: &str) {
+        self.memory.purge_incompatible(key);
+        self.disk.purge_incompatible(key);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn disk_hits_are_promoted_to_memory() {
+        let dir = tempfile::tempdir().unwrap();
+        DiskCache::open(dir.path(), 10).unwrap().set(
+            "detail:1",
+            "from disk",
+            Duration::from_mins(1),
+        );
+
+        let cache = TieredCache::new(
+            MemoryCache::new(10),
+            DiskCache::open(dir.path(), 10).unwrap(),
+        );
+        assert!(cache.memory.get("detail:1").is_none());
+        assert_eq!(cache.get("detail:1").unwrap().value, "from disk");
+        assert_eq!(cache.memory.get("detail:1").unwrap().value, "from disk");
+    }
+
+    #[test]
+    fn writes_go_to_both_tiers() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = TieredCache::new(
+            MemoryCache::new(1),
+            DiskCache::open(dir.path(), 10).unwrap(),
+        );
+        cache.set("a", "1", Duration::from_mins(1));
+        cache.set("b", "2", Duration::from_mins(1));
+        // "a" fell out of the one-entry memory tier but is still on disk
+        assert!(cache.memory.get("a").is_none());
+        assert_eq!(cache.get("a").unwrap().value, "1");
+        assert_eq!(cache.disk.len(), 2);
+    }
+
+    #[test]
+    fn stats_and_invalidation_cover_both_tiers() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = TieredCache::new(
+            MemoryCache::new(1),
+            DiskCache::open(dir.path(), 10).unwrap(),
+        );
+        cache.set("detail:1", "1", Duration::from_mins(1));
+        cache.set("detail:2", "2", Duration::from_mins(1));
+        assert!(cache.get("detail:1").is_some()); // from disk
+        assert!(cache.get("detail:1").is_some()); // from memory
+        assert!(cache.get("detail:3").is_none());
+        let stats = cache.stats();
+        assert_eq!((stats.hits, stats.misses), (2, 1));
+        assert_eq!((stats.entries, stats.evictions), (2, 0));
+
+        assert_eq!(cache.invalidate_prefix("detail:"), 2);
+        assert!(cache.memory.get("detail:1").is_none());
+        assert!(cache.get("detail:1").is_none());
+    }
+
+    #[test]
+    fn promoted_stale_entries_stay_stale() {
+        let dir = tempfile::tempdir().unwrap();
+        DiskCache::open(dir.path(), 10).unwrap().set_with_grace(
+            "calendar:1",
+            "[]",
+            Duration::ZERO,
+            Duration::from_mins(1),
+        );
+        std::thread::sleep(Duration::from_millis(2));
+
+        let cache = TieredCache::new(
+            MemoryCache::new(10),
+            DiskCache::open(dir.path(), 10).unwrap(),
+        );
+        assert!(cache.get("calendar:1").unwrap().stale);
+        assert!(cache.memory.get("calendar:1").unwrap().stale);
+    }
+}
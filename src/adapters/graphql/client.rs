@@ -7,9 +7,12 @@ use reqwest::Client;
 use tracing::{debug, trace};
 use url::Url;
 
+use crate::adapters::cache::cache_aside::CacheAside;
+use crate::adapters::cache::keys::CacheKeys;
 use crate::adapters::scraper::rate_limiter::RateLimiter;
-use crate::adapters::shared::ApiKeyManager;
-use crate::config::types::{CacheConfig, GraphQLHashes, ScraperConfig};
+use crate::adapters::shared::{ApiKeyManager, Swappable};
+use crate::config::reload::{Commit, Reloadable};
+use crate::config::types::{CacheConfig, Config, GraphQLHashes, ScraperConfig};
 use crate::domain::analytics::{self, HostProfile, NeighborhoodStats, OccupancyEstimate};
 use crate::domain::calendar::PriceCalendar;
 use crate::domain::listing::{ListingDetail, SearchResult};
@@ -17,18 +20,62 @@ use crate::domain::review::ReviewsPage;
 use crate::domain::search_params::SearchParams;
 use crate::error::{AirbnbError, Result};
 use crate::ports::airbnb_client::AirbnbClient;
-use crate::ports::cache::ListingCache;
+use crate::ports::cache::{CacheKind, ListingCache};
 
 use super::parsers;
 
+/// Cheap to clone: clones share settings, rate limiter and cache, so a background
+/// cache refresh can own one.
+#[derive(Clone)]
 pub struct AirbnbGraphQLClient {
-    http: Client,
-    rate_limiter: RateLimiter,
-    cache: Arc<dyn ListingCache>,
+    settings: Arc<Swappable<Settings>>,
+    rate_limiter: Arc<RateLimiter>,
+    cache: CacheAside,
+    keys: CacheKeys,
     base_url: String,
+    locale: String,
+    currency: String,
+    api_key_manager: Arc<ApiKeyManager>,
+}
+
+/// The part of the client a config reload replaces.
+struct Settings {
+    http: Client,
+    user_agent: String,
+    request_timeout_secs: u64,
     hashes: GraphQLHashes,
     cache_config: CacheConfig,
-    api_key_manager: Arc<ApiKeyManager>,
+}
+
+impl Settings {
+    /// Reuses `previous`'s HTTP client (and its cookies) unless the user agent or the
+    /// timeout changed.
+    fn new(
+        config: &ScraperConfig,
+        cache_config: CacheConfig,
+        previous: Option<&Self>,
+    ) -> std::result::Result<Self, reqwest::Error> {
+        let http = match previous {
+            Some(previous)
+                if previous.user_agent == config.user_agent
+                    && previous.request_timeout_secs == config.request_timeout_secs =>
+            {
+                previous.http.clone()
+            }
+            _ => Client::builder()
+                .user_agent(&config.user_agent)
+                .timeout(Duration::from_secs(config.request_timeout_secs))
+                .cookie_store(true)
+                .build()?,
+        };
+        Ok(Self {
+            http,
+            user_agent: config.user_agent.clone(),
+            request_timeout_secs: config.request_timeout_secs,
+            hashes: config.graphql_hashes.clone(),
+            cache_config,
+        })
+    }
 }
 
 impl AirbnbGraphQLClient {
@@ -38,21 +85,17 @@ impl AirbnbGraphQLClient {
         cache: Arc<dyn ListingCache>,
         api_key_manager: Arc<ApiKeyManager>,
     ) -> std::result::Result<Self, reqwest::Error> {
-        let http = Client::builder()
-            .user_agent(&config.user_agent)
-            .timeout(Duration::from_secs(config.request_timeout_secs))
-            .cookie_store(true)
-            .build()?;
-
+        let settings = Settings::new(config, cache_config, None)?;
         let rate_limiter = RateLimiter::new(config.rate_limit_per_second);
 
         Ok(Self {
-            http,
-            rate_limiter,
-            cache,
+            settings: Arc::new(Swappable::new(settings)),
+            rate_limiter: Arc::new(rate_limiter),
+            cache: CacheAside::new(cache, "graphql"),
+            keys: CacheKeys::graphql(&config.locale, &config.currency),
             base_url: config.base_url.clone(),
-            hashes: config.graphql_hashes.clone(),
-            cache_config,
+            locale: config.locale.clone(),
+            currency: config.currency.clone(),
             api_key_manager,
         })
     }
@@ -77,8 +120,8 @@ impl AirbnbGraphQLClient {
         let mut url = Url::parse(&endpoint)?;
         url.query_pairs_mut()
             .append_pair("operationName", operation_name)
-            .append_pair("locale", "en")
-            .append_pair("currency", "USD")
+            .append_pair("locale", &self.locale)
+            .append_pair("currency", &self.currency)
             .append_pair("variables", &variables.to_string())
             .append_pair("extensions", &extensions.to_string());
 
@@ -86,6 +129,8 @@ impl AirbnbGraphQLClient {
         debug!(url = %url, "GraphQL GET request");
 
         let response = self
+            .settings
+            .load()
             .http
             .get(url.as_str())
             .header("X-Airbnb-Api-Key", &api_key)
@@ -144,13 +189,20 @@ impl AirbnbGraphQLClient {
         });
 
         let endpoint = format!("{}/api/v3/{operation_name}/{hash}/", self.base_url);
+        let mut url = Url::parse(&endpoint)?;
+        url.query_pairs_mut()
+            .append_pair("operationName", operation_name)
+            .append_pair("locale", &self.locale)
+            .append_pair("currency", &self.currency);
 
         self.rate_limiter.wait().await;
-        debug!(endpoint, "GraphQL POST request");
+        debug!(url = %url, "GraphQL POST request");
 
         let response = self
+            .settings
+            .load()
             .http
-            .post(&endpoint)
+            .post(url.as_str())
             .header("X-Airbnb-Api-Key", &api_key)
             .header("Accept", "application/json")
             .header("Content-Type", "application/json")
@@ -188,45 +240,46 @@ impl AirbnbGraphQLClient {
     }
 }
 
+impl Reloadable for AirbnbGraphQLClient {
+    fn prepare(&self, config: &Config) -> Result<Commit> {
+        let settings = Settings::new(
+            &config.scraper,
+            config.cache.clone(),
+            Some(&self.settings.load()),
+        )?;
+        let rate = config.scraper.rate_limit_per_second;
+        let (swappable, rate_limiter) =
+            (Arc::clone(&self.settings), Arc::clone(&self.rate_limiter));
+        Ok(Box::new(move || {
+            swappable.store(settings);
+            rate_limiter.set_rate(rate);
+        }))
+    }
+}
+
 #[async_trait]
 impl AirbnbClient for AirbnbGraphQLClient {
     async fn search_listings(&self, params: &SearchParams) -> Result<SearchResult> {
         params.validate()?;
 
-        let cache_key = format!("gql:search:{}", params.location.to_lowercase());
-        if let Some(cached) = self.cache.get(&cache_key)
-            && let Ok(result) = serde_json::from_str::<SearchResult>(&cached)
-        {
-            debug!("Cache hit for GraphQL search");
-            return Ok(result);
-        }
-
         let variables = parsers::search::build_search_variables(params);
-        let json = self
-            .graphql_post("StaysSearch", &self.hashes.stays_search, &variables)
-            .await?;
-        let result = parsers::search::parse_search_response(&json, &self.base_url)?;
-
-        if let Ok(serialized) = serde_json::to_string(&result) {
-            self.cache.set(
-                &cache_key,
-                &serialized,
-                Duration::from_secs(self.cache_config.search_ttl_secs),
-            );
-        }
-
-        Ok(result)
+        let this = self.clone();
+        self.cache
+            .get_or_fetch(
+                self.keys.search(params),
+                self.settings.load().cache_config.policy(CacheKind::Search),
+                move || async move {
+                    let settings = this.settings.load();
+                    let json = this
+                        .graphql_post("StaysSearch", &settings.hashes.stays_search, &variables)
+                        .await?;
+                    parsers::search::parse_search_response(&json, &this.base_url)
+                },
+            )
+            .await
     }
 
     async fn get_listing_detail(&self, id: &str) -> Result<ListingDetail> {
-        let cache_key = format!("gql:detail:{id}");
-        if let Some(cached) = self.cache.get(&cache_key)
-            && let Ok(detail) = serde_json::from_str::<ListingDetail>(&cached)
-        {
-            debug!(id, "Cache hit for GraphQL listing detail");
-            return Ok(detail);
-        }
-
         let b64 = base64::engine::general_purpose::STANDARD;
         let encoded_id = b64.encode(format!("StayListing:{id}"));
         let demand_id = b64.encode(format!("DemandStayListing:{id}"));
@@ -251,35 +304,28 @@ impl AirbnbClient for AirbnbGraphQLClient {
             }
         });
 
-        let json = self
-            .graphql_get(
-                "StaysPdpSections",
-                &self.hashes.stays_pdp_sections,
-                &variables,
+        let this = self.clone();
+        let id = id.to_string();
+        self.cache
+            .get_or_fetch(
+                self.keys.detail(&id),
+                self.settings.load().cache_config.policy(CacheKind::Detail),
+                move || async move {
+                    let settings = this.settings.load();
+                    let json = this
+                        .graphql_get(
+                            "StaysPdpSections",
+                            &settings.hashes.stays_pdp_sections,
+                            &variables,
+                        )
+                        .await?;
+                    parsers::detail::parse_detail_response(&json, &id, &this.base_url)
+                },
             )
-            .await?;
-        let detail = parsers::detail::parse_detail_response(&json, id, &self.base_url)?;
-
-        if let Ok(serialized) = serde_json::to_string(&detail) {
-            self.cache.set(
-                &cache_key,
-                &serialized,
-                Duration::from_secs(self.cache_config.detail_ttl_secs),
-            );
-        }
-
-        Ok(detail)
+            .await
     }
 
     async fn get_reviews(&self, id: &str, cursor: Option<&str>) -> Result<ReviewsPage> {
-        let cache_key = format!("gql:reviews:{id}:{}", cursor.unwrap_or("first"));
-        if let Some(cached) = self.cache.get(&cache_key)
-            && let Ok(page) = serde_json::from_str::<ReviewsPage>(&cached)
-        {
-            debug!(id, "Cache hit for GraphQL reviews");
-            return Ok(page);
-        }
-
         let offset: u64 = cursor.and_then(|c| c.parse().ok()).unwrap_or(0);
         let variables = serde_json::json!({
             "id": id,
@@ -299,35 +345,28 @@ impl AirbnbClient for AirbnbGraphQLClient {
             }
         });
 
-        let json = self
-            .graphql_get(
-                "StaysPdpReviewsQuery",
-                &self.hashes.stays_pdp_reviews,
-                &variables,
+        let this = self.clone();
+        let id = id.to_string();
+        self.cache
+            .get_or_fetch(
+                self.keys.reviews(&id, cursor),
+                self.settings.load().cache_config.policy(CacheKind::Reviews),
+                move || async move {
+                    let settings = this.settings.load();
+                    let json = this
+                        .graphql_get(
+                            "StaysPdpReviewsQuery",
+                            &settings.hashes.stays_pdp_reviews,
+                            &variables,
+                        )
+                        .await?;
+                    parsers::review::parse_reviews_response(&json, &id)
+                },
             )
-            .await?;
-        let page = parsers::review::parse_reviews_response(&json, id)?;
-
-        if let Ok(serialized) = serde_json::to_string(&page) {
-            self.cache.set(
-                &cache_key,
-                &serialized,
-                Duration::from_secs(self.cache_config.reviews_ttl_secs),
-            );
-        }
-
-        Ok(page)
+            .await
     }
 
     async fn get_price_calendar(&self, id: &str, months: u32) -> Result<PriceCalendar> {
-        let cache_key = format!("gql:calendar:{id}:m={months}");
-        if let Some(cached) = self.cache.get(&cache_key)
-            && let Ok(calendar) = serde_json::from_str::<PriceCalendar>(&cached)
-        {
-            debug!(id, "Cache hit for GraphQL calendar");
-            return Ok(calendar);
-        }
-
         let now = chrono::Utc::now();
         let variables = serde_json::json!({
             "request": {
@@ -338,39 +377,34 @@ impl AirbnbClient for AirbnbGraphQLClient {
             }
         });
 
-        let json = self
-            .graphql_get(
-                "PdpAvailabilityCalendar",
-                &self.hashes.pdp_availability_calendar,
-                &variables,
+        let this = self.clone();
+        let id = id.to_string();
+        self.cache
+            .get_or_fetch(
+                self.keys.calendar(&id, months),
+                self.settings
+                    .load()
+                    .cache_config
+                    .policy(CacheKind::Calendar),
+                move || async move {
+                    let settings = this.settings.load();
+                    let json = this
+                        .graphql_get(
+                            "PdpAvailabilityCalendar",
+                            &settings.hashes.pdp_availability_calendar,
+                            &variables,
+                        )
+                        .await?;
+
+                    // Reuse the existing calendar parser which already handles GraphQL JSON
+                    let json_str = json.to_string();
+                    crate::adapters::scraper::calendar_parser::parse_price_calendar(&json_str, &id)
+                },
             )
-            .await?;
-
-        // Reuse the existing calendar parser which already handles GraphQL JSON
-        let json_str = json.to_string();
-        let calendar =
-            crate::adapters::scraper::calendar_parser::parse_price_calendar(&json_str, id)?;
-
-        if let Ok(serialized) = serde_json::to_string(&calendar) {
-            self.cache.set(
-                &cache_key,
-                &serialized,
-                Duration::from_secs(self.cache_config.calendar_ttl_secs),
-            );
-        }
-
-        Ok(calendar)
+            .await
     }
 
     async fn get_host_profile(&self, listing_id: &str) -> Result<HostProfile> {
-        let cache_key = format!("gql:host:{listing_id}");
-        if let Some(cached) = self.cache.get(&cache_key)
-            && let Ok(profile) = serde_json::from_str::<HostProfile>(&cached)
-        {
-            debug!(listing_id, "Cache hit for GraphQL host profile");
-            return Ok(profile);
-        }
-
         // Use PDP sections instead of GetUserProfile (which requires a user_id, not listing_id)
         let b64 = base64::engine::general_purpose::STANDARD;
         let encoded_id = b64.encode(format!("StayListing:{listing_id}"));
@@ -388,24 +422,27 @@ impl AirbnbClient for AirbnbGraphQLClient {
             }
         });
 
-        let json = self
-            .graphql_get(
-                "StaysPdpSections",
-                &self.hashes.stays_pdp_sections,
-                &variables,
+        let this = self.clone();
+        self.cache
+            .get_or_fetch(
+                self.keys.host(listing_id),
+                self.settings
+                    .load()
+                    .cache_config
+                    .policy(CacheKind::HostProfile),
+                move || async move {
+                    let settings = this.settings.load();
+                    let json = this
+                        .graphql_get(
+                            "StaysPdpSections",
+                            &settings.hashes.stays_pdp_sections,
+                            &variables,
+                        )
+                        .await?;
+                    parsers::host::parse_host_response(&json)
+                },
             )
-            .await?;
-        let profile = parsers::host::parse_host_response(&json)?;
-
-        if let Ok(serialized) = serde_json::to_string(&profile) {
-            self.cache.set(
-                &cache_key,
-                &serialized,
-                Duration::from_secs(self.cache_config.host_profile_ttl_secs),
-            );
-        }
-
-        Ok(profile)
+            .await
     }
 
     async fn get_neighborhood_stats(&self, params: &SearchParams) -> Result<NeighborhoodStats> {
@@ -6,13 +6,16 @@ use reqwest::Client;
 use tracing::{debug, warn};
 use url::Url;
 
+use crate::adapters::cache::cache_aside::CacheAside;
+use crate::adapters::cache::keys::CacheKeys;
 use crate::adapters::scraper::calendar_parser;
 use crate::adapters::scraper::detail_parser;
 use crate::adapters::scraper::rate_limiter::RateLimiter;
 use crate::adapters::scraper::review_parser;
 use crate::adapters::scraper::search_parser;
-use crate::adapters::shared::ApiKeyManager;
-use crate::config::types::{CacheConfig, ScraperConfig};
+use crate::adapters::shared::{ApiKeyManager, Swappable};
+use crate::config::reload::{Commit, Reloadable};
+use crate::config::types::{CacheConfig, Config, ScraperConfig};
 use crate::domain::analytics::{self, HostProfile, NeighborhoodStats, OccupancyEstimate};
 use crate::domain::calendar::PriceCalendar;
 use crate::domain::listing::{ListingDetail, SearchResult};
@@ -20,16 +23,56 @@ use crate::domain::review::ReviewsPage;
 use crate::domain::search_params::SearchParams;
 use crate::error::{AirbnbError, Result};
 use crate::ports::airbnb_client::AirbnbClient;
-use crate::ports::cache::ListingCache;
+use crate::ports::cache::{CacheKind, ListingCache};
 
+/// Cheap to clone: clones share settings, rate limiter and cache, so a background
+/// cache refresh can own one.
+#[derive(Clone)]
 pub struct AirbnbScraper {
+    settings: Arc<Swappable<Settings>>,
+    rate_limiter: Arc<RateLimiter>,
+    cache: CacheAside,
+    keys: CacheKeys,
+    locale: String,
+    currency: String,
+    #[allow(dead_code)] // Kept for CompositeClient construction symmetry
+    api_key_manager: Arc<ApiKeyManager>,
+}
+
+/// The part of the scraper a config reload replaces.
+struct Settings {
     http: Client,
-    rate_limiter: RateLimiter,
-    cache: Arc<dyn ListingCache>,
     config: ScraperConfig,
     cache_config: CacheConfig,
-    #[allow(dead_code)] // Kept for CompositeClient construction symmetry
-    api_key_manager: Arc<ApiKeyManager>,
+}
+
+impl Settings {
+    /// Reuses `previous`'s HTTP client (and its cookies) unless the user agent or the
+    /// timeout changed.
+    fn new(
+        config: ScraperConfig,
+        cache_config: CacheConfig,
+        previous: Option<&Self>,
+    ) -> std::result::Result<Self, reqwest::Error> {
+        let http = match previous {
+            Some(previous)
+                if previous.config.user_agent == config.user_agent
+                    && previous.config.request_timeout_secs == config.request_timeout_secs =>
+            {
+                previous.http.clone()
+            }
+            _ => Client::builder()
+                .user_agent(&config.user_agent)
+                .timeout(Duration::from_secs(config.request_timeout_secs))
+                .cookie_store(true)
+                .build()?,
+        };
+        Ok(Self {
+            http,
+            config,
+            cache_config,
+        })
+    }
 }
 
 impl AirbnbScraper {
@@ -39,31 +82,38 @@ impl AirbnbScraper {
         cache: Arc<dyn ListingCache>,
         api_key_manager: Arc<ApiKeyManager>,
     ) -> std::result::Result<Self, reqwest::Error> {
-        let http = Client::builder()
-            .user_agent(&config.user_agent)
-            .timeout(Duration::from_secs(config.request_timeout_secs))
-            .cookie_store(true)
-            .build()?;
-
         let rate_limiter = RateLimiter::new(config.rate_limit_per_second);
+        let keys = CacheKeys::scraper(&config.locale, &config.currency);
+        let (locale, currency) = (config.locale.clone(), config.currency.clone());
+        let settings = Settings::new(config, cache_config, None)?;
 
         Ok(Self {
-            http,
-            rate_limiter,
-            cache,
-            config,
-            cache_config,
+            settings: Arc::new(Swappable::new(settings)),
+            rate_limiter: Arc::new(rate_limiter),
+            cache: CacheAside::new(cache, "scraper"),
+            keys,
+            locale,
+            currency,
             api_key_manager,
         })
     }
 
+    /// Fetch `url` with the configured locale and currency added to its query.
     async fn fetch_html(&self, url: &str) -> Result<String> {
+        let mut localized = Url::parse(url)?;
+        localized
+            .query_pairs_mut()
+            .append_pair("locale", &self.locale)
+            .append_pair("currency", &self.currency);
+        let url = localized.as_str();
+
+        let settings = self.settings.load();
         self.rate_limiter.wait().await;
 
         debug!(url, "Fetching page");
 
         let mut last_error = None;
-        for attempt in 0..=self.config.max_retries {
+        for attempt in 0..=settings.config.max_retries {
             if attempt > 0 {
                 let delay = Duration::from_secs(u64::from(attempt) * 2);
                 debug!(attempt, delay_secs = delay.as_secs(), "Retrying request");
@@ -71,7 +121,7 @@ impl AirbnbScraper {
                 self.rate_limiter.wait().await;
             }
 
-            match self.http.get(url).send().await {
+            match settings.http.get(url).send().await {
                 Ok(response) => {
                     let status = response.status();
                     if status.is_success() {
@@ -113,139 +163,122 @@ impl AirbnbScraper {
     }
 }
 
+impl Reloadable for AirbnbScraper {
+    fn prepare(&self, config: &Config) -> Result<Commit> {
+        let settings = Settings::new(
+            config.scraper.clone(),
+            config.cache.clone(),
+            Some(&self.settings.load()),
+        )?;
+        let rate = config.scraper.rate_limit_per_second;
+        let (swappable, rate_limiter) =
+            (Arc::clone(&self.settings), Arc::clone(&self.rate_limiter));
+        Ok(Box::new(move || {
+            swappable.store(settings);
+            rate_limiter.set_rate(rate);
+        }))
+    }
+}
+
 #[async_trait]
 impl AirbnbClient for AirbnbScraper {
     async fn search_listings(&self, params: &SearchParams) -> Result<SearchResult> {
         params.validate()?;
-
-        let cache_key = format!("search:{}", build_search_cache_key(params));
-        if let Some(cached) = self.cache.get(&cache_key)
-            && let Ok(result) = serde_json::from_str::<SearchResult>(&cached)
-        {
-            debug!("Cache hit for search");
-            return Ok(result);
-        }
-
-        let url = build_search_url(&self.config.base_url, params);
-        let html = self.fetch_html(&url).await?;
-        let result = search_parser::parse_search_results(&html, &self.config.base_url)?;
-
-        if let Ok(json) = serde_json::to_string(&result) {
-            self.cache.set(
-                &cache_key,
-                &json,
-                Duration::from_secs(self.cache_config.search_ttl_secs),
-            );
-        }
-
-        Ok(result)
+        let settings = self.settings.load();
+        let this = self.clone();
+        let params = params.clone();
+        self.cache
+            .get_or_fetch(
+                self.keys.search(&params),
+                settings.cache_config.policy(CacheKind::Search),
+                move || async move {
+                    let url = build_search_url(&settings.config.base_url, &params);
+                    let html = this.fetch_html(&url).await?;
+                    search_parser::parse_search_results(&html, &settings.config.base_url)
+                },
+            )
+            .await
     }
 
     async fn get_listing_detail(&self, id: &str) -> Result<ListingDetail> {
-        let cache_key = format!("detail:{id}");
-        if let Some(cached) = self.cache.get(&cache_key)
-            && let Ok(detail) = serde_json::from_str::<ListingDetail>(&cached)
-        {
-            debug!(id, "Cache hit for listing detail");
-            return Ok(detail);
-        }
-
-        let url = format!("{}/rooms/{id}", self.config.base_url);
-        let html = self.fetch_html(&url).await?;
-        let detail = detail_parser::parse_listing_detail(&html, id, &self.config.base_url)?;
-
-        if let Ok(json) = serde_json::to_string(&detail) {
-            self.cache.set(
-                &cache_key,
-                &json,
-                Duration::from_secs(self.cache_config.detail_ttl_secs),
-            );
-        }
-
-        Ok(detail)
+        let settings = self.settings.load();
+        let this = self.clone();
+        let id = id.to_string();
+        self.cache
+            .get_or_fetch(
+                self.keys.detail(&id),
+                settings.cache_config.policy(CacheKind::Detail),
+                move || async move {
+                    let url = format!("{}/rooms/{id}", settings.config.base_url);
+                    let html = this.fetch_html(&url).await?;
+                    detail_parser::parse_listing_detail(&html, &id, &settings.config.base_url)
+                },
+            )
+            .await
     }
 
     async fn get_reviews(&self, id: &str, cursor: Option<&str>) -> Result<ReviewsPage> {
-        let cache_key = format!("reviews:{id}:{}", cursor.unwrap_or("first"));
-        if let Some(cached) = self.cache.get(&cache_key)
-            && let Ok(page) = serde_json::from_str::<ReviewsPage>(&cached)
-        {
-            debug!(id, "Cache hit for reviews");
-            return Ok(page);
-        }
-
-        let base = format!("{}/rooms/{id}", self.config.base_url);
-        let url = if let Some(c) = cursor {
-            let mut parsed = Url::parse(&base)?;
-            parsed.query_pairs_mut().append_pair("review_cursor", c);
-            parsed.to_string()
-        } else {
-            base
-        };
-        let html = self.fetch_html(&url).await?;
-        let page = review_parser::parse_reviews(&html, id)?;
-
-        if let Ok(json) = serde_json::to_string(&page) {
-            self.cache.set(
-                &cache_key,
-                &json,
-                Duration::from_secs(self.cache_config.reviews_ttl_secs),
-            );
-        }
-
-        Ok(page)
+        let settings = self.settings.load();
+        let this = self.clone();
+        let id = id.to_string();
+        let cursor = cursor.map(String::from);
+        self.cache
+            .get_or_fetch(
+                self.keys.reviews(&id, cursor.as_deref()),
+                settings.cache_config.policy(CacheKind::Reviews),
+                move || async move {
+                    let base = format!("{}/rooms/{id}", settings.config.base_url);
+                    let url = if let Some(c) = cursor {
+                        let mut parsed = Url::parse(&base)?;
+                        parsed.query_pairs_mut().append_pair("review_cursor", &c);
+                        parsed.to_string()
+                    } else {
+                        base
+                    };
+                    let html = this.fetch_html(&url).await?;
+                    review_parser::parse_reviews(&html, &id)
+                },
+            )
+            .await
     }
 
     async fn get_price_calendar(&self, id: &str, months: u32) -> Result<PriceCalendar> {
-        let cache_key = format!("calendar:{id}:m={months}");
-        if let Some(cached) = self.cache.get(&cache_key)
-            && let Ok(calendar) = serde_json::from_str::<PriceCalendar>(&cached)
-        {
-            debug!(id, "Cache hit for calendar");
-            return Ok(calendar);
-        }
-
-        let mut parsed = Url::parse(&format!("{}/rooms/{id}", self.config.base_url))?;
-        parsed
-            .query_pairs_mut()
-            .append_pair("calendar_months", &months.to_string());
-        let url = parsed.to_string();
-        let html = self.fetch_html(&url).await?;
-        let calendar = calendar_parser::parse_price_calendar(&html, id)?;
-
-        if let Ok(json) = serde_json::to_string(&calendar) {
-            self.cache.set(
-                &cache_key,
-                &json,
-                Duration::from_secs(self.cache_config.calendar_ttl_secs),
-            );
-        }
-
-        Ok(calendar)
+        let settings = self.settings.load();
+        let this = self.clone();
+        let id = id.to_string();
+        self.cache
+            .get_or_fetch(
+                self.keys.calendar(&id, months),
+                settings.cache_config.policy(CacheKind::Calendar),
+                move || async move {
+                    let mut parsed =
+                        Url::parse(&format!("{}/rooms/{id}", settings.config.base_url))?;
+                    parsed
+                        .query_pairs_mut()
+                        .append_pair("calendar_months", &months.to_string());
+                    let url = parsed.to_string();
+                    let html = this.fetch_html(&url).await?;
+                    calendar_parser::parse_price_calendar(&html, &id)
+                },
+            )
+            .await
     }
 
     async fn get_host_profile(&self, listing_id: &str) -> Result<HostProfile> {
-        let cache_key = format!("host:{listing_id}");
-        if let Some(cached) = self.cache.get(&cache_key)
-            && let Ok(profile) = serde_json::from_str::<HostProfile>(&cached)
-        {
-            debug!(listing_id, "Cache hit for host profile");
-            return Ok(profile);
-        }
-
-        let url = format!("{}/rooms/{listing_id}", self.config.base_url);
-        let html = self.fetch_html(&url).await?;
-        let profile = detail_parser::parse_host_profile(&html)?;
-
-        if let Ok(json) = serde_json::to_string(&profile) {
-            self.cache.set(
-                &cache_key,
-                &json,
-                Duration::from_secs(self.cache_config.host_profile_ttl_secs),
-            );
-        }
-
-        Ok(profile)
+        let settings = self.settings.load();
+        let this = self.clone();
+        let listing_id = listing_id.to_string();
+        self.cache
+            .get_or_fetch(
+                self.keys.host(&listing_id),
+                settings.cache_config.policy(CacheKind::HostProfile),
+                move || async move {
+                    let url = format!("{}/rooms/{listing_id}", settings.config.base_url);
+                    let html = this.fetch_html(&url).await?;
+                    detail_parser::parse_host_profile(&html)
+                },
+            )
+            .await
     }
 
     async fn get_neighborhood_stats(&self, params: &SearchParams) -> Result<NeighborhoodStats> {
@@ -289,41 +322,6 @@ fn build_search_url(base_url: &str, params: &SearchParams) -> String {
     }
 }
 
-fn build_search_cache_key(params: &SearchParams) -> String {
-    let mut key = params.location.to_lowercase();
-    if let Some(ref checkin) = params.checkin {
-        key.push_str(&format!(":ci={checkin}"));
-    }
-    if let Some(ref checkout) = params.checkout {
-        key.push_str(&format!(":co={checkout}"));
-    }
-    if let Some(adults) = params.adults {
-        key.push_str(&format!(":a={adults}"));
-    }
-    if let Some(children) = params.children {
-        key.push_str(&format!(":ch={children}"));
-    }
-    if let Some(infants) = params.infants {
-        key.push_str(&format!(":inf={infants}"));
-    }
-    if let Some(pets) = params.pets {
-        key.push_str(&format!(":p={pets}"));
-    }
-    if let Some(min_price) = params.min_price {
-        key.push_str(&format!(":min={min_price}"));
-    }
-    if let Some(max_price) = params.max_price {
-        key.push_str(&format!(":max={max_price}"));
-    }
-    if let Some(ref property_type) = params.property_type {
-        key.push_str(&format!(":pt={}", property_type.to_lowercase()));
-    }
-    if let Some(ref cursor) = params.cursor {
-        key.push_str(&format!(":cur={cursor}"));
-    }
-    key
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -384,31 +382,6 @@ mod tests {
         }
     }
 
-    #[test]
-    fn build_search_cache_key_location_only() {
-        let params = base_params();
-        let key = build_search_cache_key(&params);
-        assert_eq!(key, "paris");
-    }
-
-    #[test]
-    fn build_search_cache_key_with_dates() {
-        let mut params = base_params();
-        params.checkin = Some("2025-06-01".into());
-        params.checkout = Some("2025-06-05".into());
-        let key = build_search_cache_key(&params);
-        assert!(key.contains(":ci=2025-06-01"));
-        assert!(key.contains(":co=2025-06-05"));
-    }
-
-    #[test]
-    fn build_search_cache_key_with_cursor() {
-        let mut params = base_params();
-        params.cursor = Some("page2".into());
-        let key = build_search_cache_key(&params);
-        assert!(key.contains(":cur=page2"));
-    }
-
     #[test]
     fn build_search_url_with_price_filters() {
         let mut params = base_params();
@@ -419,24 +392,6 @@ mod tests {
         assert!(url.contains("price_max=200"));
     }
 
-    #[test]
-    fn cache_key_includes_all_params() {
-        let mut params = base_params();
-        params.children = Some(1);
-        params.infants = Some(1);
-        params.pets = Some(1);
-        params.min_price = Some(50);
-        params.max_price = Some(200);
-        params.property_type = Some("Entire home".into());
-        let key = build_search_cache_key(&params);
-        assert!(key.contains(":ch=1"));
-        assert!(key.contains(":inf=1"));
-        assert!(key.contains(":p=1"));
-        assert!(key.contains(":min=50"));
-        assert!(key.contains(":max=200"));
-        assert!(key.contains(":pt=entire home"));
-    }
-
     #[test]
     fn build_search_url_with_property_type() {
         let mut params = base_params();
@@ -2,33 +2,31 @@ use std::sync::Mutex;
 use std::time::{Duration, Instant};
 
 pub struct RateLimiter {
-    min_interval: Duration,
+    min_interval: Mutex<Duration>,
     last_request: Mutex<Option<Instant>>,
 }
 
 impl RateLimiter {
     pub fn new(requests_per_second: f64) -> Self {
-        let min_interval = if requests_per_second > 0.0 {
-            Duration::from_secs_f64(1.0 / requests_per_second)
-        } else {
-            tracing::warn!(
-                "Rate limiter initialized with non-positive rate ({requests_per_second} req/s), no rate limiting applied"
-            );
-            Duration::ZERO
-        };
         Self {
-            min_interval,
+            min_interval: Mutex::new(min_interval(requests_per_second)),
             last_request: Mutex::new(None),
         }
     }
 
+    /// Change the rate on a config reload. The next `wait` already uses the new interval.
+    pub fn set_rate(&self, requests_per_second: f64) {
+        *self.min_interval.lock().unwrap() = min_interval(requests_per_second);
+    }
+
     pub async fn wait(&self) {
+        let min_interval = *self.min_interval.lock().unwrap();
         let wait_duration = {
             let last = self.last_request.lock().unwrap();
             if let Some(last_time) = *last {
                 let elapsed = last_time.elapsed();
-                if elapsed < self.min_interval {
-                    Some(self.min_interval.checked_sub(elapsed).unwrap())
+                if elapsed < min_interval {
+                    Some(min_interval.checked_sub(elapsed).unwrap())
                 } else {
                     None
                 }
@@ -46,6 +44,17 @@ impl RateLimiter {
     }
 }
 
+fn min_interval(requests_per_second: f64) -> Duration {
+    if requests_per_second > 0.0 {
+        Duration::from_secs_f64(1.0 / requests_per_second)
+    } else {
+        tracing::warn!(
+            "Rate limiter initialized with non-positive rate ({requests_per_second} req/s), no rate limiting applied"
+        );
+        Duration::ZERO
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -90,4 +99,14 @@ mod tests {
         limiter.wait().await;
         assert!(start.elapsed() >= Duration::from_millis(300));
     }
+
+    #[tokio::test]
+    async fn rate_limiter_applies_new_rate() {
+        let limiter = RateLimiter::new(1.0);
+        limiter.wait().await;
+        limiter.set_rate(0.0);
+        let start = Instant::now();
+        limiter.wait().await;
+        assert!(start.elapsed() < Duration::from_millis(50));
+    }
 }
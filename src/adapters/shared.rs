@@ -1,7 +1,11 @@
+use std::any::Any;
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::{Arc, Mutex, PoisonError};
 use std::time::{Duration, Instant};
 
 use reqwest::Client;
-use tokio::sync::RwLock;
+use tokio::sync::{OnceCell, RwLock};
 use tracing::debug;
 
 use crate::error::{AirbnbError, Result};
@@ -64,6 +68,97 @@ impl ApiKeyManager {
     }
 }
 
+/// Settings that a config reload can replace while requests are in flight.
+///
+/// A request takes a snapshot with [`Swappable::load`] and keeps using it until it
+/// finishes; [`Swappable::store`] only affects requests that start afterwards, so no
+/// request ever sees half of an old configuration and half of a new one.
+pub struct Swappable<T>(std::sync::RwLock<Arc<T>>);
+
+impl<T> Swappable<T> {
+    pub fn new(value: T) -> Self {
+        Self(std::sync::RwLock::new(Arc::new(value)))
+    }
+
+    pub fn load(&self) -> Arc<T> {
+        Arc::clone(&self.0.read().unwrap_or_else(PoisonError::into_inner))
+    }
+
+    pub fn store(&self, value: T) {
+        *self.0.write().unwrap_or_else(PoisonError::into_inner) = Arc::new(value);
+    }
+}
+
+type Flight = Arc<OnceCell<std::result::Result<Arc<dyn Any + Send + Sync>, Arc<AirbnbError>>>>;
+
+/// Deduplicates concurrent fetches of the same cache key.
+///
+/// The first caller for a key runs its fetch; callers arriving while it is in flight
+/// wait for it and get a clone of its value or error instead of sending their own
+/// request. If the running caller is cancelled, a waiting one takes over the fetch.
+/// Once it finishes the key is released, so later callers go through the cache again.
+#[derive(Default)]
+pub struct SingleFlight {
+    in_flight: Mutex<HashMap<String, Flight>>,
+}
+
+impl SingleFlight {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Run `fetch` for `key`, or join the fetch already running for it.
+    pub async fn run<T, F, Fut>(&self, key: &str, fetch: F) -> Result<T>
+    where
+        T: Clone + Send + Sync + 'static,
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<T>>,
+    {
+        let flight = {
+            let mut in_flight = self
+                .in_flight
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner);
+            Arc::clone(in_flight.entry(key.to_string()).or_default())
+        };
+        if Arc::strong_count(&flight) > 2 {
+            debug!(key, "Joining in-flight request");
+        }
+
+        let outcome = flight
+            .get_or_init(|| async {
+                fetch()
+                    .await
+                    .map(|value| Arc::new(value) as Arc<dyn Any + Send + Sync>)
+                    .map_err(Arc::new)
+            })
+            .await
+            .clone();
+
+        {
+            let mut in_flight = self
+                .in_flight
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner);
+            if in_flight.get(key).is_some_and(|f| Arc::ptr_eq(f, &flight)) {
+                in_flight.remove(key);
+            }
+        }
+        // A caller nobody joined owns the outcome and gets it back unwrapped
+        drop(flight);
+
+        match outcome {
+            Ok(value) => {
+                let value = value.downcast::<T>().map_err(|_| AirbnbError::Parse {
+                    reason: format!("in-flight request for {key} returned another type"),
+                })?;
+                Ok(Arc::try_unwrap(value).unwrap_or_else(|shared| (*shared).clone()))
+            }
+            Err(err) => Err(Arc::try_unwrap(err).unwrap_or_else(AirbnbError::Coalesced)),
+        }
+    }
+}
+
 /// Extract the Airbnb API key from the homepage HTML.
 /// The key is embedded in `"api_config":{"key":"<KEY>"`.
 pub fn extract_api_key(html: &str) -> Option<String> {
@@ -139,6 +234,77 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn single_flight_shares_one_fetch_between_concurrent_callers() {
+        let flights = SingleFlight::new();
+        let calls = std::sync::atomic::AtomicUsize::new(0);
+        let fetch = || async {
+            calls.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            Ok("detail".to_string())
+        };
+
+        let (a, b) = tokio::join!(
+            flights.run("detail:1", fetch),
+            flights.run("detail:1", fetch)
+        );
+        assert_eq!(a.unwrap(), "detail");
+        assert_eq!(b.unwrap(), "detail");
+        assert_eq!(calls.load(std::sync::atomic::Ordering::SeqCst), 1);
+
+        // The key is released once the fetch completes
+        flights.run("detail:1", fetch).await.unwrap();
+        assert_eq!(calls.load(std::sync::atomic::Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn single_flight_shares_errors_and_keeps_keys_apart() {
+        let flights = SingleFlight::new();
+        let failing = || async {
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            Err::<String, _>(AirbnbError::RateLimited)
+        };
+        let other = || async { Ok("other".to_string()) };
+
+        let (a, b, c) = tokio::join!(
+            flights.run("detail:1", failing),
+            flights.run("detail:1", failing),
+            flights.run("detail:2", other),
+        );
+        for err in [a.unwrap_err(), b.unwrap_err()] {
+            assert!(matches!(err, AirbnbError::Coalesced(_)));
+            assert!(err.to_string().contains("Rate limit"));
+        }
+        assert_eq!(c.unwrap(), "other");
+
+        // Alone, the caller gets its own error back
+        let alone = flights.run("detail:1", failing).await.unwrap_err();
+        assert!(matches!(alone, AirbnbError::RateLimited));
+    }
+
+    #[tokio::test]
+    async fn single_flight_waiter_takes_over_cancelled_fetch() {
+        let flights = SingleFlight::new();
+        let leader = tokio::time::timeout(
+            Duration::from_millis(30),
+            flights.run("detail:1", || async {
+                tokio::time::sleep(Duration::from_mins(1)).await;
+                Ok("never".to_string())
+            }),
+        );
+        // Joins while the leader's fetch is still running
+        let waiter = async {
+            tokio::time::sleep(Duration::from_millis(5)).await;
+            flights
+                .run("detail:1", || async { Ok("retried".to_string()) })
+                .await
+        };
+
+        let (leader, waiter) = tokio::join!(leader, waiter);
+        assert!(leader.is_err());
+        assert_eq!(waiter.unwrap(), "retried");
+    }
+
     #[test]
     fn extract_api_key_empty_value() {
         let html = r#"{"api_config":{"key":""}}"#;
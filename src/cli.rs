@@ -0,0 +1,362 @@
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use anyhow::Result;
+use clap::{Args, Parser, Subcommand};
+use rmcp::model::{CallToolResult, JsonObject};
+use serde_json::Value;
+
+use mcp_airbnb::config::types::Transport;
+use mcp_airbnb::mcp::local::{LocalSession, arguments_from_pairs};
+use mcp_airbnb::mcp::server::AirbnbMcpServer;
+
+/// MCP server for searching and browsing Airbnb listings.
+///
+/// Without a subcommand it serves MCP (same as `serve`). The other subcommands call one
+/// tool directly and print its result, which is handy for scripts and quick checks.
+#[derive(Debug, Parser)]
+#[command(name = "mcp-airbnb", version, about)]
+pub struct Cli {
+    #[command(flatten)]
+    pub config: ConfigArgs,
+    #[command(flatten)]
+    pub serve: ServeArgs,
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+/// Where configuration comes from; accepted before or after any subcommand.
+/// Precedence, lowest first: defaults, the YAML file, `MCP_AIRBNB_*` variables, `--set`.
+#[derive(Debug, Clone, Default, Args)]
+pub struct ConfigArgs {
+    /// YAML config file (default: `$MCP_AIRBNB_CONFIG`, else `./config.yaml` or next to the binary)
+    #[arg(long, global = true, value_name = "PATH")]
+    pub config: Option<PathBuf>,
+    /// Override one config value, e.g. `--set cache.max_entries=2000` (repeatable)
+    #[arg(long = "set", global = true, value_name = "KEY=VALUE")]
+    pub overrides: Vec<String>,
+    /// Validate the merged configuration, report every problem and exit (non-zero if invalid)
+    #[arg(long, global = true)]
+    pub check_config: bool,
+}
+
+#[derive(Debug, Clone, Default, Args)]
+pub struct ServeArgs {
+    /// Transport to serve MCP over: `stdio` or `http` (overrides `server.transport`)
+    #[arg(long)]
+    pub transport: Option<Transport>,
+    /// Address to listen on in HTTP mode, e.g. `0.0.0.0:8080` (overrides `server.bind_address`)
+    #[arg(long)]
+    pub bind: Option<String>,
+}
+
+#[derive(Debug, Args)]
+pub struct OutputArgs {
+    /// Print the structured result as JSON instead of text
+    #[arg(long)]
+    pub json: bool,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// Serve MCP over stdio or HTTP (the default)
+    Serve(ServeArgs),
+    /// Search listings in a location (`airbnb_search`)
+    Search {
+        /// Location to search, e.g. "Paris, France"
+        location: String,
+        /// Check-in date (YYYY-MM-DD)
+        #[arg(long)]
+        checkin: Option<String>,
+        /// Check-out date (YYYY-MM-DD)
+        #[arg(long)]
+        checkout: Option<String>,
+        #[arg(long)]
+        adults: Option<u32>,
+        #[arg(long)]
+        children: Option<u32>,
+        #[arg(long)]
+        infants: Option<u32>,
+        #[arg(long)]
+        pets: Option<u32>,
+        /// Minimum price per night
+        #[arg(long)]
+        min_price: Option<u32>,
+        /// Maximum price per night
+        #[arg(long)]
+        max_price: Option<u32>,
+        /// Property type, e.g. "Entire home"
+        #[arg(long)]
+        property_type: Option<String>,
+        /// Cursor from a previous page
+        #[arg(long)]
+        cursor: Option<String>,
+        #[command(flatten)]
+        output: OutputArgs,
+    },
+    /// Show a listing's details (`airbnb_listing_details`)
+    Detail {
+        id: String,
+        #[command(flatten)]
+        output: OutputArgs,
+    },
+    /// Show a page of a listing's reviews (`airbnb_reviews`)
+    Reviews {
+        id: String,
+        /// Cursor from a previous page
+        #[arg(long)]
+        cursor: Option<String>,
+        #[command(flatten)]
+        output: OutputArgs,
+    },
+    /// Show a listing's price and availability calendar (`airbnb_price_calendar`)
+    Calendar {
+        id: String,
+        /// Number of months (1-12)
+        #[arg(long)]
+        months: Option<u32>,
+        #[command(flatten)]
+        output: OutputArgs,
+    },
+    /// Show the host profile of a listing (`airbnb_host_profile`)
+    Host {
+        id: String,
+        #[command(flatten)]
+        output: OutputArgs,
+    },
+    /// Inspect the configuration
+    Config {
+        #[command(subcommand)]
+        action: ConfigAction,
+    },
+    /// Run any other tool, e.g. `analyze price-trends id=42 months=6`
+    Analyze {
+        /// Tool name, with or without the `airbnb_` prefix (`price-trends`, `airbnb_gap_finder`)
+        tool: String,
+        /// Tool arguments as key=value; lists are comma-separated (`ids=1,2,3`)
+        #[arg(value_name = "KEY=VALUE")]
+        args: Vec<String>,
+        #[command(flatten)]
+        output: OutputArgs,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+pub enum ConfigAction {
+    /// Print the effective configuration after merging every layer (secrets masked)
+    Show,
+}
+
+impl Cli {
+    /// The subcommand to run; `serve` when none was given. Serve flags given before
+    /// `serve` (`mcp-airbnb --transport http serve`) apply to it as well.
+    pub fn into_command(self) -> Command {
+        match self.command {
+            None => Command::Serve(self.serve),
+            Some(Command::Serve(args)) => Command::Serve(ServeArgs {
+                transport: args.transport.or(self.serve.transport),
+                bind: args.bind.or(self.serve.bind),
+            }),
+            Some(command) => command,
+        }
+    }
+}
+
+impl Command {
+    /// The tool a one-shot subcommand calls; `None` for `serve` and `config`.
+    fn tool_name(&self) -> Option<String> {
+        let name = match self {
+            Self::Serve(_) | Self::Config { .. } => return None,
+            Self::Search { .. } => "airbnb_search",
+            Self::Detail { .. } => "airbnb_listing_details",
+            Self::Reviews { .. } => "airbnb_reviews",
+            Self::Calendar { .. } => "airbnb_price_calendar",
+            Self::Host { .. } => "airbnb_host_profile",
+            Self::Analyze { tool, .. } => {
+                let tool = tool.replace('-', "_");
+                return Some(if tool.starts_with("airbnb_") {
+                    tool
+                } else {
+                    format!("airbnb_{tool}")
+                });
+            }
+        };
+        Some(name.into())
+    }
+
+    fn json_output(&self) -> bool {
+        match self {
+            Self::Serve(_) | Self::Config { .. } => false,
+            Self::Search { output, .. }
+            | Self::Detail { output, .. }
+            | Self::Reviews { output, .. }
+            | Self::Calendar { output, .. }
+            | Self::Host { output, .. }
+            | Self::Analyze { output, .. } => output.json,
+        }
+    }
+
+    /// Arguments of the typed subcommands; `analyze` builds its own from the tool schema.
+    fn typed_arguments(&self) -> JsonObject {
+        let value = match self {
+            Self::Serve(_) | Self::Config { .. } | Self::Analyze { .. } => {
+                return JsonObject::new();
+            }
+            Self::Search {
+                location,
+                checkin,
+                checkout,
+                adults,
+                children,
+                infants,
+                pets,
+                min_price,
+                max_price,
+                property_type,
+                cursor,
+                ..
+            } => serde_json::json!({
+                "location": location,
+                "checkin": checkin,
+                "checkout": checkout,
+                "adults": adults,
+                "children": children,
+                "infants": infants,
+                "pets": pets,
+                "min_price": min_price,
+                "max_price": max_price,
+                "property_type": property_type,
+                "cursor": cursor,
+            }),
+            Self::Detail { id, .. } | Self::Host { id, .. } => serde_json::json!({ "id": id }),
+            Self::Reviews { id, cursor, .. } => serde_json::json!({ "id": id, "cursor": cursor }),
+            Self::Calendar { id, months, .. } => serde_json::json!({ "id": id, "months": months }),
+        };
+        let Value::Object(mut arguments) = value else {
+            unreachable!("tool arguments are built as JSON objects");
+        };
+        arguments.retain(|_, v| !v.is_null());
+        arguments
+    }
+}
+
+/// Run a one-shot subcommand against `server` and print its result to stdout.
+/// Returns a failure exit code when the tool reports an error.
+pub async fn run_tool(command: &Command, server: AirbnbMcpServer) -> Result<ExitCode> {
+    let Some(name) = command.tool_name() else {
+        return Ok(ExitCode::SUCCESS);
+    };
+    let session = LocalSession::start(server).await?;
+    let outcome = call(&session, command, &name).await;
+    session.close().await;
+    let result = outcome?;
+
+    if result.is_error == Some(true) {
+        eprintln!("{}", text_of(&result));
+        return Ok(ExitCode::FAILURE);
+    }
+    if command.json_output() {
+        let json = match result.structured_content {
+            Some(ref data) => serde_json::to_string_pretty(data)?,
+            None => serde_json::to_string_pretty(&result)?,
+        };
+        println!("{json}");
+    } else {
+        println!("{}", text_of(&result));
+    }
+    Ok(ExitCode::SUCCESS)
+}
+
+async fn call(session: &LocalSession, command: &Command, name: &str) -> Result<CallToolResult> {
+    let tool = session.tool(name).await?.ok_or_else(|| {
+        anyhow::anyhow!("unknown tool '{name}' (or disabled by `tools.allow` / `tools.deny`)")
+    })?;
+    let arguments = match command {
+        Command::Analyze { args, .. } => arguments_from_pairs(&tool, args)?,
+        _ => command.typed_arguments(),
+    };
+    Ok(session.call(name, arguments).await?)
+}
+
+fn text_of(result: &CallToolResult) -> String {
+    result
+        .content
+        .iter()
+        .filter_map(|c| c.as_text().map(|t| t.text.as_str()))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn no_subcommand_serves() {
+        let cli = Cli::parse_from(["mcp-airbnb", "--transport", "http"]);
+        let Command::Serve(args) = cli.into_command() else {
+            panic!("expected serve");
+        };
+        assert_eq!(args.transport, Some(Transport::Http));
+
+        let cli = Cli::parse_from(["mcp-airbnb", "--bind", "0.0.0.0:9000", "serve"]);
+        let Command::Serve(args) = cli.into_command() else {
+            panic!("expected serve");
+        };
+        assert_eq!(args.bind.as_deref(), Some("0.0.0.0:9000"));
+    }
+
+    #[test]
+    fn config_flags_are_global() {
+        let cli = Cli::parse_from([
+            "mcp-airbnb",
+            "detail",
+            "42",
+            "--config",
+            "/etc/mcp-airbnb.yaml",
+            "--set",
+            "cache.max_entries=2000",
+            "--set",
+            "scraper.max_retries=4",
+        ]);
+        assert_eq!(
+            cli.config.config.as_deref(),
+            Some(std::path::Path::new("/etc/mcp-airbnb.yaml"))
+        );
+        assert_eq!(
+            cli.config.overrides,
+            ["cache.max_entries=2000", "scraper.max_retries=4"]
+        );
+
+        let cli = Cli::parse_from(["mcp-airbnb", "serve", "--check-config"]);
+        assert!(cli.config.check_config);
+
+        let cli = Cli::parse_from(["mcp-airbnb", "--config", "a.yaml", "config", "show"]);
+        assert!(cli.config.config.is_some());
+        assert!(!cli.config.check_config);
+        assert!(matches!(cli.into_command(), Command::Config { .. }));
+    }
+
+    #[test]
+    fn analyze_accepts_short_tool_names() {
+        let cli = Cli::parse_from(["mcp-airbnb", "analyze", "price-trends", "id=42", "--json"]);
+        let command = cli.command.unwrap();
+        assert_eq!(command.tool_name().as_deref(), Some("airbnb_price_trends"));
+        assert!(command.json_output());
+        let Command::Analyze { args, .. } = command else {
+            panic!("expected analyze");
+        };
+        assert_eq!(args, ["id=42"]);
+    }
+
+    #[test]
+    fn typed_subcommands_skip_unset_arguments() {
+        let cli = Cli::parse_from(["mcp-airbnb", "search", "Paris, France", "--adults", "2"]);
+        let command = cli.command.unwrap();
+        assert_eq!(command.tool_name().as_deref(), Some("airbnb_search"));
+        let arguments = command.typed_arguments();
+        assert_eq!(arguments.len(), 2);
+        assert_eq!(arguments["location"], "Paris, France");
+        assert_eq!(arguments["adults"], 2);
+    }
+}
@@ -1,17 +1,130 @@
+pub mod reload;
 pub mod types;
+pub mod validate;
 
-use std::path::Path;
+use std::path::{Path, PathBuf};
+
+use serde_yml::{Mapping, Value};
 
 use crate::error::{AirbnbError, Result};
 use types::Config;
 
+/// Environment variable naming the config file (`--config` takes precedence).
+pub const CONFIG_PATH_ENV: &str = "MCP_AIRBNB_CONFIG";
+
+/// Prefix of field-level overrides: `MCP_AIRBNB_SCRAPER__RATE_LIMIT_PER_SECOND=2` sets
+/// `scraper.rate_limit_per_second` (`__` separates nesting levels).
+pub const ENV_PREFIX: &str = "MCP_AIRBNB_";
+
+/// Where the configuration comes from. Layers apply in this order, each overriding the
+/// previous one: built-in defaults, the YAML file, `MCP_AIRBNB_*` environment variables,
+/// then `--set key=value` flags.
+#[derive(Debug, Clone, Default)]
+pub struct ConfigSources {
+    /// YAML file to read. A missing file means defaults, unless `required` is set.
+    pub file: PathBuf,
+    /// The file was named explicitly (`--config` or `MCP_AIRBNB_CONFIG`) and must exist.
+    pub required: bool,
+    /// `MCP_AIRBNB_*` variables as `(name, value)`.
+    pub env: Vec<(String, String)>,
+    /// `key=value` overrides from `--set`, e.g. `cache.max_entries=2000`.
+    pub overrides: Vec<String>,
+}
+
+impl ConfigSources {
+    /// Sources of this process: the file from `--config`, else `MCP_AIRBNB_CONFIG`, else
+    /// `./config.yaml` or `config.yaml` next to the binary; overrides from the environment.
+    pub fn from_process(config_flag: Option<PathBuf>, overrides: Vec<String>) -> Self {
+        let explicit = config_flag.or_else(|| std::env::var_os(CONFIG_PATH_ENV).map(PathBuf::from));
+        let env = std::env::vars()
+            .filter(|(name, _)| name.starts_with(ENV_PREFIX) && name != CONFIG_PATH_ENV)
+            .collect();
+        Self {
+            required: explicit.is_some(),
+            file: explicit.unwrap_or_else(default_config_path),
+            env,
+            overrides,
+        }
+    }
+
+    /// Merge all layers into the effective configuration and validate it
+    /// (see [`Config::validate`]).
+    pub fn load(&self) -> Result<Config> {
+        let config = self.merge()?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Merge all layers without checking the result.
+    pub fn merge(&self) -> Result<Config> {
+        let mut tree = read_yaml(&self.file, self.required)?;
+        let defaults = serde_yml::to_value(Config::default())?;
+
+        for (name, raw) in &self.env {
+            let key = env_key(name);
+            if let Err(e) = set_key(&mut tree, &defaults, &key, raw) {
+                // Unrelated variables may share the prefix; don't refuse to start over them
+                tracing::warn!("Ignoring {name}: {e}");
+            }
+        }
+        for pair in &self.overrides {
+            let (key, raw) = pair.split_once('=').ok_or_else(|| {
+                AirbnbError::Config(format!("--set expects key=value, got '{pair}'"))
+            })?;
+            set_key(&mut tree, &defaults, key.trim(), raw)
+                .map_err(|e| AirbnbError::Config(format!("--set {pair}: {e}")))?;
+        }
+        Ok(serde_yml::from_value(tree)?)
+    }
+}
+
 pub fn load_config(path: &Path) -> Result<Config> {
+    ConfigSources {
+        file: path.to_path_buf(),
+        ..ConfigSources::default()
+    }
+    .load()
+}
+
+/// The effective configuration as YAML, with token secrets masked.
+pub fn show_config(config: &Config) -> Result<String> {
+    let mut config = config.clone();
+    for token in &mut config.server.auth.tokens {
+        token.token = "<redacted>".into();
+    }
+    Ok(serde_yml::to_string(&config)?)
+}
+
+fn default_config_path() -> PathBuf {
+    // Check common locations for config file: the working directory, then the
+    // directory where the binary is
+    let candidates = [
+        PathBuf::from("config.yaml"),
+        std::env::current_exe()
+            .ok()
+            .and_then(|p| p.parent().map(|dir| dir.join("config.yaml")))
+            .unwrap_or_else(|| PathBuf::from("config.yaml")),
+    ];
+    candidates
+        .iter()
+        .find(|path| path.exists())
+        .unwrap_or(&candidates[0])
+        .clone()
+}
+
+fn read_yaml(path: &Path, required: bool) -> Result<Value> {
     if !path.exists() {
+        if required {
+            return Err(AirbnbError::Config(format!(
+                "config file {} does not exist",
+                path.display()
+            )));
+        }
         tracing::info!(
             "Config file not found at {}, using defaults",
             path.display()
         );
-        return Ok(Config::default());
+        return Ok(Value::Mapping(Mapping::new()));
     }
 
     let content = std::fs::read_to_string(path).map_err(|e| {
@@ -20,8 +133,74 @@ pub fn load_config(path: &Path) -> Result<Config> {
             path.display()
         ))
     })?;
-    let config: Config = serde_yml::from_str(&content)?;
-    Ok(config)
+    match serde_yml::from_str(&content)? {
+        Value::Null => Ok(Value::Mapping(Mapping::new())),
+        tree => Ok(tree),
+    }
+}
+
+/// `MCP_AIRBNB_SERVER__RESOURCES__MAX_ENTRIES` → `server.resources.max_entries`.
+fn env_key(name: &str) -> String {
+    name.trim_start_matches(ENV_PREFIX)
+        .to_lowercase()
+        .replace("__", ".")
+}
+
+/// Set the dotted `key` in `tree` to `raw`. The key must exist in `defaults`, which also
+/// decides how `raw` is read: as-is for string fields, as a YAML value otherwise (numbers,
+/// booleans, `[a, b]` lists). A `null` default (an unset optional field) says nothing
+/// about the type, so there `raw` stays text if the config accepts it as such, e.g.
+/// `token_file=1234`, and is read as YAML only when it doesn't, e.g. `max_bytes=4096`.
+fn set_key(
+    tree: &mut Value,
+    defaults: &Value,
+    key: &str,
+    raw: &str,
+) -> std::result::Result<(), String> {
+    let unknown = || format!("unknown config key '{key}'");
+    let default = key
+        .split('.')
+        .try_fold(defaults, |node, segment| node.get(segment))
+        .ok_or_else(unknown)?;
+    let text = Value::String(raw.to_string());
+    if default.is_string() {
+        return insert_key(tree, key, text);
+    }
+    if default.is_null() {
+        let mut candidate = tree.clone();
+        insert_key(&mut candidate, key, text)?;
+        if serde_yml::from_value::<Config>(candidate.clone()).is_ok() {
+            *tree = candidate;
+            return Ok(());
+        }
+    }
+    let value = serde_yml::from_str(raw).map_err(|e| format!("invalid value '{raw}': {e}"))?;
+    insert_key(tree, key, value)
+}
+
+/// Insert `value` at the dotted `key`, creating missing sections on the way.
+fn insert_key(tree: &mut Value, key: &str, value: Value) -> std::result::Result<(), String> {
+    let segments: Vec<&str> = key.split('.').collect();
+    let Some((last, parents)) = segments.split_last() else {
+        return Err("empty key".into());
+    };
+    let mut node = tree;
+    for segment in parents {
+        let Value::Mapping(map) = node else {
+            return Err(format!("'{key}' is inside a value that is not a section"));
+        };
+        node = map
+            .entry(Value::String((*segment).to_string()))
+            .or_insert_with(|| Value::Mapping(Mapping::new()));
+        if node.is_null() {
+            *node = Value::Mapping(Mapping::new());
+        }
+    }
+    let Value::Mapping(map) = node else {
+        return Err(format!("'{key}' is inside a value that is not a section"));
+    };
+    map.insert(Value::String((*last).to_string()), value);
+    Ok(())
 }
 
 #[cfg(test)]
@@ -77,17 +256,137 @@ mod tests {
         let mut tmp = tempfile::NamedTempFile::new().unwrap();
         writeln!(
             tmp,
-            "scraper:\n  graphql_hashes:\n    stays_search: \"custom_hash_abc\""
+            "scraper:\n  graphql_hashes:\n    stays_search: \"0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f\""
         )
         .unwrap();
         let config = load_config(tmp.path()).unwrap();
         assert_eq!(
             config.scraper.graphql_hashes.stays_search,
-            "custom_hash_abc"
+            "0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f"
         );
         assert!(!config.scraper.graphql_hashes.stays_pdp_sections.is_empty());
     }
 
+    fn layered(
+        yaml: &str,
+        env: &[(&str, &str)],
+        overrides: &[&str],
+    ) -> (tempfile::NamedTempFile, ConfigSources) {
+        let mut tmp = tempfile::NamedTempFile::new().unwrap();
+        writeln!(tmp, "{yaml}").unwrap();
+        let sources = ConfigSources {
+            file: tmp.path().to_path_buf(),
+            required: true,
+            env: env
+                .iter()
+                .map(|(k, v)| ((*k).to_string(), (*v).to_string()))
+                .collect(),
+            overrides: overrides.iter().map(|s| (*s).to_string()).collect(),
+        };
+        (tmp, sources)
+    }
+
+    #[test]
+    fn layers_apply_in_precedence_order() {
+        let (_tmp, sources) = layered(
+            "scraper:\n  max_retries: 5\n  rate_limit_per_second: 1.0\ncache:\n  max_entries: 100",
+            &[
+                ("MCP_AIRBNB_SCRAPER__RATE_LIMIT_PER_SECOND", "2.5"),
+                ("MCP_AIRBNB_CACHE__MAX_ENTRIES", "300"),
+                ("MCP_AIRBNB_SERVER__RESOURCES__PAGE_SIZE", "10"),
+            ],
+            &["cache.max_entries=2000"],
+        );
+        let config = sources.load().unwrap();
+        // file
+        assert_eq!(config.scraper.max_retries, 5);
+        // env over file
+        assert!((config.scraper.rate_limit_per_second - 2.5).abs() < f64::EPSILON);
+        assert_eq!(config.server.resources.page_size, 10);
+        // --set over env
+        assert_eq!(config.cache.max_entries, 2000);
+    }
+
+    #[test]
+    fn string_fields_keep_raw_values() {
+        let (_tmp, sources) = layered(
+            "",
+            &[],
+            &[
+                "scraper.graphql_hashes.stays_search=12345",
+                "server.transport=http",
+            ],
+        );
+        // merge() only: "12345" is kept as a string but is not a valid hash
+        let config = sources.merge().unwrap();
+        assert_eq!(config.scraper.graphql_hashes.stays_search, "12345");
+        assert_eq!(config.server.transport, types::Transport::Http);
+    }
+
+    #[test]
+    fn optional_path_fields_keep_raw_values() {
+        let (_tmp, sources) = layered(
+            "",
+            &[("MCP_AIRBNB_CACHE__DISK__PATH", "2024")],
+            &["server.auth.token_file=1234", "cache.max_bytes=4096"],
+        );
+        let config = sources.merge().unwrap();
+        assert_eq!(config.server.auth.token_file, Some(PathBuf::from("1234")));
+        assert_eq!(config.cache.disk.path, Some(PathBuf::from("2024")));
+        assert_eq!(config.cache.max_bytes, Some(4096));
+    }
+
+    #[test]
+    fn unknown_keys_fail_for_set_but_not_env() {
+        let (_tmp, sources) = layered("", &[("MCP_AIRBNB_TOKEN", "x")], &[]);
+        assert!(sources.load().is_ok());
+
+        let (_tmp, sources) = layered("", &[], &["cache.max_entrys=1"]);
+        let err = sources.load().unwrap_err().to_string();
+        assert!(
+            err.contains("unknown config key 'cache.max_entrys'"),
+            "{err}"
+        );
+
+        let (_tmp, sources) = layered("", &[], &["cache.max_entries"]);
+        assert!(sources.load().is_err());
+    }
+
+    #[test]
+    fn explicit_config_file_must_exist() {
+        let sources = ConfigSources {
+            file: PathBuf::from("/tmp/nonexistent_mcp_config_12345.yaml"),
+            required: true,
+            ..ConfigSources::default()
+        };
+        assert!(sources.load().is_err());
+    }
+
+    #[test]
+    fn env_names_map_to_dotted_keys() {
+        assert_eq!(
+            env_key("MCP_AIRBNB_SCRAPER__RATE_LIMIT_PER_SECOND"),
+            "scraper.rate_limit_per_second"
+        );
+        assert_eq!(
+            env_key("MCP_AIRBNB_SERVER__RESOURCES__MAX_ENTRIES"),
+            "server.resources.max_entries"
+        );
+    }
+
+    #[test]
+    fn show_config_masks_tokens() {
+        let (_tmp, sources) = layered(
+            "server:\n  auth:\n    tokens:\n      - name: ci\n        token: s3cret",
+            &[],
+            &[],
+        );
+        let shown = show_config(&sources.load().unwrap()).unwrap();
+        assert!(shown.contains("name: ci"));
+        assert!(!shown.contains("s3cret"));
+        assert!(shown.contains("rate_limit_per_second"));
+    }
+
     #[test]
     fn load_config_invalid_yaml() {
         let mut tmp = tempfile::NamedTempFile::new().unwrap();
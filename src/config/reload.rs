@@ -0,0 +1,377 @@
+use std::path::Path;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+
+use serde_json::Value;
+use tokio::task::JoinHandle;
+
+use crate::error::Result;
+
+use super::ConfigSources;
+use super::types::Config;
+
+/// How often the config file's modification time is checked.
+const WATCH_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Settings a running server takes without a restart. Everything else (transport, bind
+/// address, auth, cache and resource capacities, `base_url`, ...) is wired in at startup,
+/// so a change to it is reported and left for the next restart.
+const LIVE_SETTINGS: &[&str] = &[
+    "scraper.user_agent",
+    "scraper.rate_limit_per_second",
+    "scraper.request_timeout_secs",
+    "scraper.max_retries",
+    "scraper.graphql_hashes",
+    "cache.search_ttl_secs",
+    "cache.detail_ttl_secs",
+    "cache.reviews_ttl_secs",
+    "cache.calendar_ttl_secs",
+    "cache.host_profile_ttl_secs",
+    "cache.search_grace_secs",
+    "cache.detail_grace_secs",
+    "cache.reviews_grace_secs",
+    "cache.calendar_grace_secs",
+    "cache.host_profile_grace_secs",
+];
+
+/// Switches a [`Reloadable`] to the settings it prepared. Cannot fail.
+pub type Commit = Box<dyn FnOnce() + Send>;
+
+/// A component that can take new live settings while it keeps serving requests.
+pub trait Reloadable: Send + Sync {
+    /// Build the live settings of `config` without using them yet, and return the step
+    /// that switches to them. Building may fail (say, a new HTTP client) and switching
+    /// can't, so a reload reaches every component or none. Requests already running
+    /// finish with the settings they started with.
+    fn prepare(&self, config: &Config) -> Result<Commit>;
+}
+
+/// What a reload changed, as dotted config paths.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct ReloadOutcome {
+    pub applied: Vec<String>,
+    /// Changed settings that only take effect after a restart; not applied.
+    pub needs_restart: Vec<String>,
+}
+
+/// Re-reads the configuration when its file changes or on SIGHUP and hands the live
+/// settings to every registered [`Reloadable`].
+pub struct ConfigReloader {
+    sources: ConfigSources,
+    running: Config,
+    targets: Vec<Arc<dyn Reloadable>>,
+}
+
+impl ConfigReloader {
+    /// `running` is the configuration the server was started with, loaded from `sources`.
+    pub fn new(sources: ConfigSources, running: Config) -> Self {
+        Self {
+            sources,
+            running,
+            targets: Vec::new(),
+        }
+    }
+
+    #[must_use]
+    pub fn with_target(mut self, target: Arc<dyn Reloadable>) -> Self {
+        self.targets.push(target);
+        self
+    }
+
+    /// Load the sources again and apply what changed. An invalid configuration, or
+    /// settings a target fails to build, is an error and leaves every target and the
+    /// running configuration untouched.
+    pub fn reload(&mut self) -> Result<ReloadOutcome> {
+        let loaded = self.sources.load()?;
+        let (applied, needs_restart): (Vec<String>, Vec<String>) =
+            changed_settings(&self.running, &loaded)?
+                .into_iter()
+                .partition(|path| is_live(path));
+        if !applied.is_empty() {
+            let next = with_live_settings(&self.running, &loaded)?;
+            let commits = self
+                .targets
+                .iter()
+                .map(|target| target.prepare(&next))
+                .collect::<Result<Vec<_>>>()?;
+            for commit in commits {
+                commit();
+            }
+            self.running = next;
+        }
+        Ok(ReloadOutcome {
+            applied,
+            needs_restart,
+        })
+    }
+
+    /// Watch the config file (polling its modification time) and SIGHUP in the background.
+    pub fn spawn(mut self) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut modified = modified_at(&self.sources.file);
+            let mut poll = tokio::time::interval(WATCH_INTERVAL);
+            poll.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+            let mut hangup = hangup_listener();
+            loop {
+                let trigger = tokio::select! {
+                    _ = poll.tick() => {
+                        let now = modified_at(&self.sources.file);
+                        if now == modified {
+                            continue;
+                        }
+                        modified = now;
+                        "file change"
+                    }
+                    () = next_hangup(&mut hangup) => "SIGHUP",
+                };
+                self.reload_and_report(trigger);
+            }
+        })
+    }
+
+    fn reload_and_report(&mut self, trigger: &str) {
+        match self.reload() {
+            Ok(outcome) => {
+                for path in &outcome.needs_restart {
+                    tracing::warn!(
+                        "{path} changed in {}; restart the server to apply it",
+                        self.sources.file.display()
+                    );
+                }
+                if outcome.applied.is_empty() {
+                    tracing::info!("Configuration reloaded ({trigger}), no live settings changed");
+                } else {
+                    tracing::info!(
+                        "Configuration reloaded ({trigger}), applied: {}",
+                        outcome.applied.join(", ")
+                    );
+                }
+            }
+            Err(e) => {
+                tracing::error!(
+                    "Configuration reload ({trigger}) failed, keeping the running configuration: {e}"
+                );
+            }
+        }
+    }
+}
+
+/// Dotted paths of every setting that differs between `old` and `new`. Lists (such as
+/// auth tokens) compare as a whole; values are never returned, so secrets stay out of logs.
+pub fn changed_settings(old: &Config, new: &Config) -> Result<Vec<String>> {
+    let (old, new) = (serde_json::to_value(old)?, serde_json::to_value(new)?);
+    let mut changed = Vec::new();
+    diff(&old, &new, String::new(), &mut changed);
+    Ok(changed)
+}
+
+fn diff(old: &Value, new: &Value, path: String, changed: &mut Vec<String>) {
+    match (old, new) {
+        (Value::Object(old), Value::Object(new)) => {
+            for (key, old_value) in old {
+                let child = if path.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{path}.{key}")
+                };
+                diff(
+                    old_value,
+                    new.get(key).unwrap_or(&Value::Null),
+                    child,
+                    changed,
+                );
+            }
+        }
+        _ if old != new => changed.push(path),
+        _ => {}
+    }
+}
+
+fn is_live(path: &str) -> bool {
+    LIVE_SETTINGS.iter().any(|live| {
+        path == *live
+            || path
+                .strip_prefix(live)
+                .is_some_and(|rest| rest.starts_with('.'))
+    })
+}
+
+/// `running` with its live settings taken from `loaded`.
+fn with_live_settings(running: &Config, loaded: &Config) -> Result<Config> {
+    let mut merged = serde_json::to_value(running)?;
+    let loaded = serde_json::to_value(loaded)?;
+    for live in LIVE_SETTINGS {
+        let pointer = format!("/{}", live.replace('.', "/"));
+        if let (Some(slot), Some(value)) = (merged.pointer_mut(&pointer), loaded.pointer(&pointer))
+        {
+            *slot = value.clone();
+        }
+    }
+    Ok(serde_json::from_value(merged)?)
+}
+
+fn modified_at(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+#[cfg(unix)]
+type Hangup = tokio::signal::unix::Signal;
+#[cfg(not(unix))]
+type Hangup = std::convert::Infallible;
+
+fn hangup_listener() -> Option<Hangup> {
+    #[cfg(unix)]
+    {
+        use tokio::signal::unix::{SignalKind, signal};
+        signal(SignalKind::hangup())
+            .inspect_err(|e| tracing::warn!("SIGHUP reload unavailable: {e}"))
+            .ok()
+    }
+    #[cfg(not(unix))]
+    {
+        None
+    }
+}
+
+async fn next_hangup(hangup: &mut Option<Hangup>) {
+    #[cfg(unix)]
+    if let Some(signal) = hangup
+        && signal.recv().await.is_some()
+    {
+        return;
+    }
+    #[cfg(not(unix))]
+    let _ = hangup;
+    std::future::pending::<()>().await;
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+    use std::sync::Mutex;
+
+    use super::*;
+    use crate::config::types::Transport;
+
+    #[derive(Default)]
+    struct Recorder(Arc<Mutex<Vec<Config>>>);
+
+    impl Reloadable for Recorder {
+        fn prepare(&self, config: &Config) -> Result<Commit> {
+            let (applied, config) = (Arc::clone(&self.0), config.clone());
+            Ok(Box::new(move || applied.lock().unwrap().push(config)))
+        }
+    }
+
+    /// A target whose new settings never build.
+    struct Failing;
+
+    impl Reloadable for Failing {
+        fn prepare(&self, _config: &Config) -> Result<Commit> {
+            Err(crate::error::AirbnbError::Config("cannot build".into()))
+        }
+    }
+
+    fn write(file: &tempfile::NamedTempFile, yaml: &str) {
+        let mut f = std::fs::File::create(file.path()).unwrap();
+        writeln!(f, "{yaml}").unwrap();
+    }
+
+    fn reloader(file: &tempfile::NamedTempFile) -> (ConfigReloader, Arc<Recorder>) {
+        let sources = ConfigSources {
+            file: file.path().to_path_buf(),
+            required: true,
+            ..ConfigSources::default()
+        };
+        let running = sources.load().unwrap();
+        let recorder = Arc::new(Recorder::default());
+        let reloader = ConfigReloader::new(sources, running)
+            .with_target(Arc::clone(&recorder) as Arc<dyn Reloadable>);
+        (reloader, recorder)
+    }
+
+    #[test]
+    fn live_settings_are_applied() {
+        let file = tempfile::NamedTempFile::new().unwrap();
+        write(&file, "scraper:\n  rate_limit_per_second: 0.5");
+        let (mut reloader, recorder) = reloader(&file);
+
+        let hash = "ab".repeat(32);
+        write(
+            &file,
+            &format!(
+                "scraper:\n  rate_limit_per_second: 2.0\n  graphql_hashes:\n    stays_search: {hash}\ncache:\n  search_ttl_secs: 60"
+            ),
+        );
+        let outcome = reloader.reload().unwrap();
+        assert_eq!(
+            outcome.applied,
+            [
+                "cache.search_ttl_secs",
+                "scraper.graphql_hashes.stays_search",
+                "scraper.rate_limit_per_second",
+            ]
+        );
+        assert!(outcome.needs_restart.is_empty());
+        let applied = recorder.0.lock().unwrap();
+        assert_eq!(applied.len(), 1);
+        assert_eq!(applied[0].scraper.graphql_hashes.stays_search, hash);
+        assert_eq!(applied[0].cache.search_ttl_secs, 60);
+    }
+
+    #[test]
+    fn restart_only_settings_are_reported_not_applied() {
+        let file = tempfile::NamedTempFile::new().unwrap();
+        write(&file, "");
+        let (mut reloader, recorder) = reloader(&file);
+
+        write(
+            &file,
+            "server:\n  transport: http\nscraper:\n  user_agent: test-agent\ncache:\n  detail_grace_secs: 120",
+        );
+        let outcome = reloader.reload().unwrap();
+        assert_eq!(
+            outcome.applied,
+            ["cache.detail_grace_secs", "scraper.user_agent"]
+        );
+        assert_eq!(outcome.needs_restart, ["server.transport"]);
+        let applied = recorder.0.lock().unwrap();
+        assert_eq!(applied[0].scraper.user_agent, "test-agent");
+        assert_eq!(applied[0].cache.detail_grace_secs, 120);
+        assert_eq!(applied[0].server.transport, Transport::default());
+    }
+
+    #[test]
+    fn invalid_reload_keeps_running_config() {
+        let file = tempfile::NamedTempFile::new().unwrap();
+        write(&file, "");
+        let (mut reloader, recorder) = reloader(&file);
+
+        write(&file, "scraper:\n  rate_limit_per_second: -1");
+        assert!(reloader.reload().is_err());
+        assert!(recorder.0.lock().unwrap().is_empty());
+
+        write(&file, "");
+        assert_eq!(reloader.reload().unwrap(), ReloadOutcome::default());
+    }
+
+    #[test]
+    fn failing_target_leaves_every_target_untouched() {
+        let file = tempfile::NamedTempFile::new().unwrap();
+        write(&file, "");
+        let (reloader, recorder) = reloader(&file);
+        let mut reloader = reloader.with_target(Arc::new(Failing));
+
+        write(&file, "scraper:\n  max_retries: 1");
+        assert!(reloader.reload().is_err());
+        assert!(recorder.0.lock().unwrap().is_empty());
+
+        // Still a change against the running configuration on the next reload
+        let mut reloader = ConfigReloader {
+            targets: vec![Arc::clone(&recorder) as Arc<dyn Reloadable>],
+            ..reloader
+        };
+        assert_eq!(reloader.reload().unwrap().applied, ["scraper.max_retries"]);
+        assert_eq!(recorder.0.lock().unwrap().len(), 1);
+    }
+}
@@ -1,11 +1,162 @@
+use std::fmt;
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::time::Duration;
+
 use serde::{Deserialize, Serialize};
 
+use crate::ports::cache::{CacheKind, CachePolicy};
+
 #[derive(Debug, Clone, Deserialize, Serialize, Default)]
 pub struct Config {
     #[serde(default)]
     pub scraper: ScraperConfig,
     #[serde(default)]
     pub cache: CacheConfig,
+    #[serde(default)]
+    pub server: ServerConfig,
+    #[serde(default)]
+    pub tools: ToolsConfig,
+}
+
+/// How the MCP server is exposed to clients.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum Transport {
+    /// One client per process over stdin/stdout.
+    #[default]
+    Stdio,
+    /// Many clients over MCP streamable HTTP (with SSE streaming).
+    Http,
+}
+
+impl FromStr for Transport {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "stdio" => Ok(Self::Stdio),
+            "http" => Ok(Self::Http),
+            other => Err(format!(
+                "unknown transport '{other}' (expected 'stdio' or 'http')"
+            )),
+        }
+    }
+}
+
+impl fmt::Display for Transport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Stdio => f.write_str("stdio"),
+            Self::Http => f.write_str("http"),
+        }
+    }
+}
+
+/// Whether MCP sessions served by one process share resources and search-derived prices.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum SessionMode {
+    /// Every session reads and writes the same `ResourceStore` and price cache.
+    Shared,
+    /// Listing data is shared; searches, analysis results and prices are private per session.
+    #[default]
+    Isolated,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ServerConfig {
+    #[serde(default)]
+    pub transport: Transport,
+    #[serde(default)]
+    pub session_mode: SessionMode,
+    #[serde(default = "default_bind_address")]
+    pub bind_address: String,
+    #[serde(default = "default_http_path")]
+    pub http_path: String,
+    #[serde(default = "default_sse_keep_alive_secs")]
+    pub sse_keep_alive_secs: u64,
+    #[serde(default)]
+    pub auth: AuthConfig,
+    #[serde(default)]
+    pub resources: ResourceConfig,
+}
+
+/// Limits on the MCP resources kept in memory per store (the shared store and each
+/// isolated session's private store). A TTL of 0 keeps that kind until it is evicted.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ResourceConfig {
+    /// Least recently used resources are evicted beyond this count.
+    #[serde(default = "default_resource_max_entries")]
+    pub max_entries: usize,
+    /// Resources returned per `resources/list` page.
+    #[serde(default = "default_resource_page_size")]
+    pub page_size: usize,
+    /// `airbnb://listing/{id}` plus its reviews, host and occupancy sub-resources.
+    #[serde(default = "default_detail_ttl")]
+    pub listing_ttl_secs: u64,
+    /// `airbnb://listing/{id}/calendar`.
+    #[serde(default = "default_calendar_ttl")]
+    pub calendar_ttl_secs: u64,
+    /// `airbnb://search/{location}`.
+    #[serde(default = "default_search_ttl")]
+    pub search_ttl_secs: u64,
+    /// `airbnb://neighborhood/{location}`.
+    #[serde(default = "default_detail_ttl")]
+    pub neighborhood_ttl_secs: u64,
+    /// `airbnb://analysis/...`.
+    #[serde(default = "default_calendar_ttl")]
+    pub analysis_ttl_secs: u64,
+}
+
+impl Default for ResourceConfig {
+    fn default() -> Self {
+        Self {
+            max_entries: default_resource_max_entries(),
+            page_size: default_resource_page_size(),
+            listing_ttl_secs: default_detail_ttl(),
+            calendar_ttl_secs: default_calendar_ttl(),
+            search_ttl_secs: default_search_ttl(),
+            neighborhood_ttl_secs: default_detail_ttl(),
+            analysis_ttl_secs: default_calendar_ttl(),
+        }
+    }
+}
+
+/// Static credentials accepted in HTTP mode. Authentication is required as soon as
+/// at least one token is configured (inline or through `token_file`).
+#[derive(Debug, Clone, Deserialize, Serialize, Default)]
+pub struct AuthConfig {
+    #[serde(default)]
+    pub tokens: Vec<TokenConfig>,
+    /// YAML file holding a list of additional `TokenConfig` entries.
+    #[serde(default)]
+    pub token_file: Option<PathBuf>,
+}
+
+/// A bearer token / API key and the tools it may call.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct TokenConfig {
+    /// Label used in logs and error messages (never the secret itself).
+    pub name: String,
+    pub token: String,
+    /// Tool names this token may list and call. `None` allows every tool.
+    #[serde(default)]
+    pub allowed_tools: Option<Vec<String>>,
+}
+
+impl Default for ServerConfig {
+    fn default() -> Self {
+        Self {
+            transport: Transport::default(),
+            session_mode: SessionMode::default(),
+            bind_address: default_bind_address(),
+            http_path: default_http_path(),
+            sse_keep_alive_secs: default_sse_keep_alive_secs(),
+            auth: AuthConfig::default(),
+            resources: ResourceConfig::default(),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -22,6 +173,12 @@ pub struct ScraperConfig {
     pub respect_robots_txt: bool,
     #[serde(default = "default_base_url")]
     pub base_url: String,
+    /// Language Airbnb answers in, e.g. `en` or `fr`.
+    #[serde(default = "default_locale")]
+    pub locale: String,
+    /// Currency prices are quoted in, e.g. `USD` or `EUR`.
+    #[serde(default = "default_currency")]
+    pub currency: String,
     #[serde(default = "default_api_key_cache_secs")]
     pub api_key_cache_secs: u64,
     #[serde(default = "default_true")]
@@ -60,6 +217,8 @@ impl Default for ScraperConfig {
             max_retries: default_retries(),
             respect_robots_txt: true,
             base_url: default_base_url(),
+            locale: default_locale(),
+            currency: default_currency(),
             api_key_cache_secs: default_api_key_cache_secs(),
             graphql_enabled: true,
             graphql_hashes: default_graphql_hashes(),
@@ -69,8 +228,18 @@ impl Default for ScraperConfig {
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct CacheConfig {
+    #[serde(default)]
+    pub backend: CacheBackend,
+    /// Capacity of the in-memory cache (also the memory tier in front of the disk cache).
     #[serde(default = "default_max_entries")]
     pub max_entries: usize,
+    /// Byte budget of the in-memory cache (keys plus stored values, after compression).
+    /// Least recently used entries are evicted to stay within it; unset means no limit.
+    #[serde(default)]
+    pub max_bytes: Option<u64>,
+    /// How the in-memory cache stores values.
+    #[serde(default)]
+    pub compression: CacheCompression,
     #[serde(default = "default_search_ttl")]
     pub search_ttl_secs: u64,
     #[serde(default = "default_detail_ttl")]
@@ -81,21 +250,179 @@ pub struct CacheConfig {
     pub calendar_ttl_secs: u64,
     #[serde(default = "default_host_profile_ttl")]
     pub host_profile_ttl_secs: u64,
+    /// After its TTL, how long an entry is still served (marked stale) while it is
+    /// refreshed in the background. `0` makes callers wait for a fresh fetch.
+    #[serde(default)]
+    pub search_grace_secs: u64,
+    #[serde(default)]
+    pub detail_grace_secs: u64,
+    #[serde(default)]
+    pub reviews_grace_secs: u64,
+    #[serde(default)]
+    pub calendar_grace_secs: u64,
+    #[serde(default)]
+    pub host_profile_grace_secs: u64,
+    /// Used when `backend` is `disk`.
+    #[serde(default)]
+    pub disk: DiskCacheConfig,
+}
+
+/// Where fetched Airbnb data is cached.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum CacheBackend {
+    /// In-process LRU; lost on restart.
+    #[default]
+    Memory,
+    /// Files in `cache.disk.path`, kept across restarts.
+    Disk,
+}
+
+/// How the in-memory cache stores values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum CacheCompression {
+    /// Plain JSON.
+    #[default]
+    None,
+    /// LZ4-compressed JSON, cheap to decompress. Values under 512 bytes stay plain.
+    Lz4,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct DiskCacheConfig {
+    /// Cache directory. Default: `$XDG_CACHE_HOME/mcp-airbnb`, else `~/.cache/mcp-airbnb`.
+    #[serde(default)]
+    pub path: Option<PathBuf>,
+    #[serde(default = "default_disk_max_entries")]
+    pub max_entries: usize,
+    /// Keep recently used entries in memory (`cache.max_entries`) in front of the disk.
+    #[serde(default = "default_true")]
+    pub memory_front: bool,
+}
+
+impl DiskCacheConfig {
+    /// `path`, or the per-user cache directory when unset.
+    pub fn resolved_path(&self) -> PathBuf {
+        if let Some(ref path) = self.path {
+            return path.clone();
+        }
+        let base = std::env::var_os("XDG_CACHE_HOME")
+            .map(PathBuf::from)
+            .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".cache")))
+            .unwrap_or_else(std::env::temp_dir);
+        base.join("mcp-airbnb")
+    }
+}
+
+impl Default for DiskCacheConfig {
+    fn default() -> Self {
+        Self {
+            path: None,
+            max_entries: default_disk_max_entries(),
+            memory_front: true,
+        }
+    }
 }
 
 impl Default for CacheConfig {
     fn default() -> Self {
         Self {
+            backend: CacheBackend::default(),
             max_entries: default_max_entries(),
+            max_bytes: None,
+            compression: CacheCompression::default(),
             search_ttl_secs: default_search_ttl(),
             detail_ttl_secs: default_detail_ttl(),
             reviews_ttl_secs: default_reviews_ttl(),
             calendar_ttl_secs: default_calendar_ttl(),
             host_profile_ttl_secs: default_host_profile_ttl(),
+            search_grace_secs: 0,
+            detail_grace_secs: 0,
+            reviews_grace_secs: 0,
+            calendar_grace_secs: 0,
+            host_profile_grace_secs: 0,
+            disk: DiskCacheConfig::default(),
+        }
+    }
+}
+
+impl CacheConfig {
+    /// TTL and grace period for `kind`.
+    pub fn policy(&self, kind: CacheKind) -> CachePolicy {
+        let (ttl, grace) = match kind {
+            CacheKind::Search => (self.search_ttl_secs, self.search_grace_secs),
+            CacheKind::Detail => (self.detail_ttl_secs, self.detail_grace_secs),
+            CacheKind::Reviews => (self.reviews_ttl_secs, self.reviews_grace_secs),
+            CacheKind::Calendar => (self.calendar_ttl_secs, self.calendar_grace_secs),
+            CacheKind::HostProfile => (self.host_profile_ttl_secs, self.host_profile_grace_secs),
+        };
+        CachePolicy {
+            ttl: Duration::from_secs(ttl),
+            grace: Duration::from_secs(grace),
+        }
+    }
+}
+
+/// Which tools the server registers, and the caps some of them apply to their arguments.
+#[derive(Debug, Clone, Deserialize, Serialize, Default)]
+pub struct ToolsConfig {
+    /// Only register these tools. `None` registers every tool.
+    #[serde(default)]
+    pub allow: Option<Vec<String>>,
+    /// Never register these tools, even when listed in `allow`.
+    #[serde(default)]
+    pub deny: Vec<String>,
+    #[serde(default)]
+    pub limits: ToolLimits,
+}
+
+impl ToolsConfig {
+    /// Whether the tool called `name` is registered.
+    pub fn enables(&self, name: &str) -> bool {
+        self.allow
+            .as_ref()
+            .is_none_or(|allow| allow.iter().any(|t| t == name))
+            && !self.deny.iter().any(|t| t == name)
+    }
+}
+
+/// Upper bounds for tool arguments; larger requested values are clamped to these.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ToolLimits {
+    /// `max_listings` of a location-based `airbnb_compare_listings`.
+    #[serde(default = "default_max_listings")]
+    pub max_listings: u32,
+    /// Listing IDs compared by `airbnb_compare_listings`; extra IDs are ignored.
+    #[serde(default = "default_max_compare_ids")]
+    pub max_compare_ids: usize,
+    /// `max_pages` of `airbnb_review_sentiment` and `airbnb_review_summary`.
+    #[serde(default = "default_max_review_pages")]
+    pub max_review_pages: u32,
+}
+
+impl Default for ToolLimits {
+    fn default() -> Self {
+        Self {
+            max_listings: default_max_listings(),
+            max_compare_ids: default_max_compare_ids(),
+            max_review_pages: default_max_review_pages(),
         }
     }
 }
 
+fn default_max_listings() -> u32 {
+    100
+}
+
+fn default_max_compare_ids() -> usize {
+    10
+}
+
+fn default_max_review_pages() -> u32 {
+    20
+}
+
 fn default_user_agent() -> String {
     "Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36".into()
 }
@@ -120,6 +447,14 @@ fn default_base_url() -> String {
     "https://www.airbnb.com".into()
 }
 
+fn default_locale() -> String {
+    "en".into()
+}
+
+fn default_currency() -> String {
+    "USD".into()
+}
+
 fn default_api_key_cache_secs() -> u64 {
     86400 // 24 hours
 }
@@ -158,6 +493,10 @@ fn default_max_entries() -> usize {
     500
 }
 
+fn default_disk_max_entries() -> usize {
+    20_000
+}
+
 fn default_search_ttl() -> u64 {
     900
 }
@@ -178,6 +517,26 @@ fn default_host_profile_ttl() -> u64 {
     3600
 }
 
+fn default_bind_address() -> String {
+    "127.0.0.1:8080".into()
+}
+
+fn default_http_path() -> String {
+    "/mcp".into()
+}
+
+fn default_sse_keep_alive_secs() -> u64 {
+    15
+}
+
+fn default_resource_max_entries() -> usize {
+    1000
+}
+
+fn default_resource_page_size() -> usize {
+    50
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -189,6 +548,8 @@ mod tests {
         assert_eq!(config.scraper.request_timeout_secs, 30);
         assert_eq!(config.scraper.max_retries, 2);
         assert_eq!(config.scraper.base_url, "https://www.airbnb.com");
+        assert_eq!(config.scraper.locale, "en");
+        assert_eq!(config.scraper.currency, "USD");
         assert!(config.scraper.respect_robots_txt);
     }
 
@@ -201,6 +562,103 @@ mod tests {
         assert_eq!(config.reviews_ttl_secs, 3600);
         assert_eq!(config.calendar_ttl_secs, 1800);
         assert_eq!(config.host_profile_ttl_secs, 3600);
+        assert_eq!(config.detail_grace_secs, 0);
+        assert_eq!(config.calendar_grace_secs, 0);
+        assert_eq!(config.search_grace_secs, 0);
+        assert_eq!(
+            config.policy(CacheKind::Calendar),
+            CachePolicy {
+                ttl: Duration::from_mins(30),
+                grace: Duration::ZERO,
+            }
+        );
+        assert_eq!(config.backend, CacheBackend::Memory);
+        assert_eq!(config.max_bytes, None);
+        assert_eq!(config.compression, CacheCompression::None);
+        assert_eq!(config.disk.max_entries, 20_000);
+        assert!(config.disk.memory_front);
+    }
+
+    #[test]
+    fn config_deserialize_disk_cache() {
+        let yaml = "cache:\n  backend: disk\n  disk:\n    path: /tmp/airbnb-cache\n    memory_front: false";
+        let config: Config = serde_yml::from_str(yaml).unwrap();
+        assert_eq!(config.cache.backend, CacheBackend::Disk);
+        assert_eq!(
+            config.cache.disk.resolved_path(),
+            PathBuf::from("/tmp/airbnb-cache")
+        );
+        assert!(!config.cache.disk.memory_front);
+        assert_eq!(config.cache.disk.max_entries, 20_000);
+    }
+
+    #[test]
+    fn config_deserialize_byte_budget_and_compression() {
+        let yaml = "cache:\n  max_bytes: 67108864\n  compression: lz4";
+        let config: Config = serde_yml::from_str(yaml).unwrap();
+        assert_eq!(config.cache.max_bytes, Some(64 * 1024 * 1024));
+        assert_eq!(config.cache.compression, CacheCompression::Lz4);
+    }
+
+    #[test]
+    fn server_config_defaults() {
+        let config = ServerConfig::default();
+        assert_eq!(config.transport, Transport::Stdio);
+        assert_eq!(config.session_mode, SessionMode::Isolated);
+        assert_eq!(config.bind_address, "127.0.0.1:8080");
+        assert_eq!(config.http_path, "/mcp");
+        assert_eq!(config.sse_keep_alive_secs, 15);
+        assert_eq!(config.resources.max_entries, 1000);
+        assert_eq!(config.resources.page_size, 50);
+        assert_eq!(config.resources.search_ttl_secs, 900);
+        assert_eq!(config.resources.analysis_ttl_secs, 1800);
+    }
+
+    #[test]
+    fn config_deserialize_resource_limits() {
+        let yaml = "server:\n  resources:\n    max_entries: 200\n    search_ttl_secs: 60";
+        let config: Config = serde_yml::from_str(yaml).unwrap();
+        assert_eq!(config.server.resources.max_entries, 200);
+        assert_eq!(config.server.resources.search_ttl_secs, 60);
+        assert_eq!(config.server.resources.listing_ttl_secs, 3600);
+    }
+
+    #[test]
+    fn transport_from_str() {
+        assert_eq!("stdio".parse::<Transport>(), Ok(Transport::Stdio));
+        assert_eq!("HTTP".parse::<Transport>(), Ok(Transport::Http));
+        assert!("websocket".parse::<Transport>().is_err());
+    }
+
+    #[test]
+    fn config_deserialize_server_section() {
+        let yaml = "server:\n  transport: http\n  bind_address: \"0.0.0.0:9000\"";
+        let config: Config = serde_yml::from_str(yaml).unwrap();
+        assert_eq!(config.server.transport, Transport::Http);
+        assert_eq!(config.server.bind_address, "0.0.0.0:9000");
+        assert_eq!(config.server.http_path, "/mcp");
+        assert_eq!(config.server.session_mode, SessionMode::Isolated);
+    }
+
+    #[test]
+    fn config_deserialize_auth_tokens() {
+        let yaml = "server:\n  auth:\n    tokens:\n      - name: ci\n        token: s3cret\n        allowed_tools: [airbnb_search]\n      - name: admin\n        token: r00t";
+        let config: Config = serde_yml::from_str(yaml).unwrap();
+        let tokens = &config.server.auth.tokens;
+        assert_eq!(tokens.len(), 2);
+        assert_eq!(
+            tokens[0].allowed_tools.as_deref(),
+            Some(&["airbnb_search".to_string()][..])
+        );
+        assert!(tokens[1].allowed_tools.is_none());
+        assert!(config.server.auth.token_file.is_none());
+    }
+
+    #[test]
+    fn config_deserialize_shared_session_mode() {
+        let yaml = "server:\n  session_mode: shared";
+        let config: Config = serde_yml::from_str(yaml).unwrap();
+        assert_eq!(config.server.session_mode, SessionMode::Shared);
     }
 
     #[test]
@@ -216,6 +674,18 @@ mod tests {
         );
     }
 
+    #[test]
+    fn tools_allow_then_deny() {
+        let yaml = "tools:\n  allow: [airbnb_search, airbnb_reviews]\n  deny: [airbnb_reviews]\n  limits:\n    max_compare_ids: 5";
+        let config: Config = serde_yml::from_str(yaml).unwrap();
+        assert!(config.tools.enables("airbnb_search"));
+        assert!(!config.tools.enables("airbnb_reviews"));
+        assert!(!config.tools.enables("airbnb_price_trends"));
+        assert_eq!(config.tools.limits.max_compare_ids, 5);
+        assert_eq!(config.tools.limits.max_listings, 100);
+        assert!(Config::default().tools.enables("airbnb_price_trends"));
+    }
+
     #[test]
     fn config_deserialize_with_overrides() {
         let yaml = "scraper:\n  max_retries: 5";
@@ -0,0 +1,369 @@
+use std::collections::HashSet;
+use std::fmt;
+
+use crate::error::{AirbnbError, Result};
+
+use super::types::{
+    CacheBackend, CacheCompression, Config, GraphQLHashes, ServerConfig, ToolsConfig,
+};
+
+/// A value that deserializes fine but that the server cannot work with.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigIssue {
+    /// Dotted path of the field, as used by `--set` (e.g. `scraper.base_url`).
+    pub path: String,
+    pub message: String,
+    pub suggestion: Option<String>,
+}
+
+impl fmt::Display for ConfigIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.path, self.message)?;
+        if let Some(ref suggestion) = self.suggestion {
+            write!(f, " (hint: {suggestion})")?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Default)]
+struct Issues(Vec<ConfigIssue>);
+
+impl Issues {
+    fn check(&mut self, ok: bool, path: &str, message: impl Into<String>, suggestion: &str) {
+        if !ok {
+            self.0.push(ConfigIssue {
+                path: path.into(),
+                message: message.into(),
+                suggestion: (!suggestion.is_empty()).then(|| suggestion.into()),
+            });
+        }
+    }
+}
+
+impl Config {
+    /// Check every field serde accepted for values the server cannot use, returning all
+    /// problems at once rather than failing on the first request that hits one.
+    pub fn issues(&self) -> Vec<ConfigIssue> {
+        let mut issues = Issues::default();
+        let scraper = &self.scraper;
+
+        let rate = scraper.rate_limit_per_second;
+        issues.check(
+            rate.is_finite() && rate > 0.0,
+            "scraper.rate_limit_per_second",
+            format!("must be a positive number of requests per second, got {rate}"),
+            "0.5 sends one request every 2 seconds",
+        );
+        issues.check(
+            scraper.request_timeout_secs > 0,
+            "scraper.request_timeout_secs",
+            "must be at least 1 second",
+            "the default is 30",
+        );
+        issues.check(
+            !scraper.user_agent.trim().is_empty(),
+            "scraper.user_agent",
+            "must not be empty",
+            "remove the field to use the built-in browser user agent",
+        );
+        let base_url_ok = url::Url::parse(&scraper.base_url)
+            .is_ok_and(|url| matches!(url.scheme(), "http" | "https") && url.has_host());
+        issues.check(
+            base_url_ok,
+            "scraper.base_url",
+            format!("'{}' is not an http(s) URL", scraper.base_url),
+            "use https://www.airbnb.com",
+        );
+        issues.check(
+            !scraper.locale.trim().is_empty(),
+            "scraper.locale",
+            "must not be empty",
+            "the default is en",
+        );
+        issues.check(
+            !scraper.currency.trim().is_empty(),
+            "scraper.currency",
+            "must not be empty",
+            "use an ISO 4217 code such as USD or EUR",
+        );
+        check_hashes(&mut issues, &scraper.graphql_hashes);
+
+        issues.check(
+            self.cache.max_entries > 0,
+            "cache.max_entries",
+            "must be at least 1",
+            "the default is 500",
+        );
+        issues.check(
+            self.cache.max_bytes.is_none_or(|max| max > 0),
+            "cache.max_bytes",
+            "must be at least 1",
+            "remove it for no byte limit, or use e.g. 67108864 (64 MiB)",
+        );
+        if self.cache.backend == CacheBackend::Disk {
+            issues.check(
+                self.cache.disk.max_entries > 0,
+                "cache.disk.max_entries",
+                "must be at least 1",
+                "the default is 20000",
+            );
+            let path = self.cache.disk.resolved_path();
+            issues.check(
+                !path.exists() || path.is_dir(),
+                "cache.disk.path",
+                format!("{} exists and is not a directory", path.display()),
+                "point it at a directory; it is created if missing",
+            );
+            // Both only shape the memory tier, which `memory_front: false` leaves out
+            let memory_front = self.cache.disk.memory_front;
+            issues.check(
+                memory_front || self.cache.max_bytes.is_none(),
+                "cache.max_bytes",
+                "has no effect with cache.disk.memory_front: false",
+                "remove it, or set cache.disk.memory_front: true",
+            );
+            issues.check(
+                memory_front || self.cache.compression == CacheCompression::None,
+                "cache.compression",
+                "has no effect with cache.disk.memory_front: false",
+                "remove it, or set cache.disk.memory_front: true",
+            );
+        }
+
+        check_server(&mut issues, &self.server);
+        check_tools(&mut issues, &self.tools);
+
+        issues.0
+    }
+
+    /// [`Config::issues`] as an error listing every problem, one per line.
+    pub fn validate(&self) -> Result<()> {
+        let issues = self.issues();
+        if issues.is_empty() {
+            return Ok(());
+        }
+        let list: Vec<String> = issues.iter().map(|issue| format!("  - {issue}")).collect();
+        Err(AirbnbError::Config(format!(
+            "{} invalid setting(s):\n{}",
+            issues.len(),
+            list.join("\n")
+        )))
+    }
+}
+
+fn check_server(issues: &mut Issues, server: &ServerConfig) {
+    issues.check(
+        is_host_port(&server.bind_address),
+        "server.bind_address",
+        format!("'{}' is not a host:port address", server.bind_address),
+        "e.g. 127.0.0.1:8080 or 0.0.0.0:8080",
+    );
+    issues.check(
+        server.http_path.starts_with('/'),
+        "server.http_path",
+        format!("'{}' must start with '/'", server.http_path),
+        "the default is /mcp",
+    );
+    issues.check(
+        server.resources.max_entries > 0,
+        "server.resources.max_entries",
+        "must be at least 1",
+        "the default is 1000",
+    );
+    issues.check(
+        server.resources.page_size > 0,
+        "server.resources.page_size",
+        "must be at least 1",
+        "the default is 50",
+    );
+
+    let mut names = HashSet::new();
+    for (i, token) in server.auth.tokens.iter().enumerate() {
+        let path = format!("server.auth.tokens[{i}]");
+        issues.check(
+            !token.token.is_empty(),
+            &format!("{path}.token"),
+            format!("token '{}' has an empty secret", token.name),
+            "",
+        );
+        issues.check(
+            names.insert(token.name.as_str()),
+            &format!("{path}.name"),
+            format!("token name '{}' is used more than once", token.name),
+            "names identify tokens in logs, so keep them unique",
+        );
+    }
+    if let Some(ref file) = server.auth.token_file {
+        issues.check(
+            file.is_file(),
+            "server.auth.token_file",
+            format!("{} does not exist", file.display()),
+            "",
+        );
+    }
+}
+
+fn check_tools(issues: &mut Issues, tools: &ToolsConfig) {
+    let limits = &tools.limits;
+    issues.check(
+        limits.max_listings >= 2,
+        "tools.limits.max_listings",
+        "must be at least 2 (a comparison needs two listings)",
+        "the default is 100",
+    );
+    issues.check(
+        limits.max_compare_ids >= 2,
+        "tools.limits.max_compare_ids",
+        "must be at least 2 (a comparison needs two listings)",
+        "the default is 10",
+    );
+    issues.check(
+        limits.max_review_pages > 0,
+        "tools.limits.max_review_pages",
+        "must be at least 1",
+        "the default is 20",
+    );
+    if let Some(ref allow) = tools.allow {
+        issues.check(
+            allow.iter().any(|name| !tools.deny.contains(name)),
+            "tools.allow",
+            "every allowed tool is also denied, so no tool would be registered",
+            "remove `allow` to register every tool not in `deny`",
+        );
+    }
+}
+
+fn check_hashes(issues: &mut Issues, hashes: &GraphQLHashes) {
+    let fields = [
+        ("stays_search", &hashes.stays_search),
+        ("stays_pdp_sections", &hashes.stays_pdp_sections),
+        ("stays_pdp_reviews", &hashes.stays_pdp_reviews),
+        (
+            "pdp_availability_calendar",
+            &hashes.pdp_availability_calendar,
+        ),
+        ("get_user_profile", &hashes.get_user_profile),
+    ];
+    for (name, hash) in fields {
+        issues.check(
+            hash.len() == 64 && hash.chars().all(|c| c.is_ascii_hexdigit()),
+            &format!("scraper.graphql_hashes.{name}"),
+            format!("'{hash}' is not a 64-character hex SHA-256 hash"),
+            "copy the sha256Hash of the persisted query, or remove the field to use the built-in one",
+        );
+    }
+}
+
+/// `host:port` or `[v6]:port` with a valid port; the host is resolved when binding.
+fn is_host_port(address: &str) -> bool {
+    address
+        .rsplit_once(':')
+        .is_some_and(|(host, port)| !host.is_empty() && port.parse::<u16>().is_ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::types::TokenConfig;
+
+    fn paths(config: &Config) -> Vec<String> {
+        config.issues().into_iter().map(|i| i.path).collect()
+    }
+
+    #[test]
+    fn defaults_are_valid() {
+        assert!(Config::default().validate().is_ok());
+    }
+
+    #[test]
+    fn reports_every_problem_at_once() {
+        let mut config = Config::default();
+        config.scraper.rate_limit_per_second = -1.0;
+        config.scraper.base_url = "www.airbnb.com".into();
+        config.scraper.graphql_hashes.stays_search = "not-a-hash".into();
+        config.cache.max_entries = 0;
+        config.cache.max_bytes = Some(0);
+        config.server.http_path = "mcp".into();
+
+        assert_eq!(
+            paths(&config),
+            [
+                "scraper.rate_limit_per_second",
+                "scraper.base_url",
+                "scraper.graphql_hashes.stays_search",
+                "cache.max_entries",
+                "cache.max_bytes",
+                "server.http_path",
+            ]
+        );
+        let err = config.validate().unwrap_err().to_string();
+        assert!(err.contains("6 invalid setting(s)"), "{err}");
+        assert!(err.contains("hint: the default is 500"), "{err}");
+    }
+
+    #[test]
+    fn checks_addresses_and_tokens() {
+        let mut config = Config::default();
+        config.server.bind_address = "8080".into();
+        let token = |name: &str, secret: &str| TokenConfig {
+            name: name.into(),
+            token: secret.into(),
+            allowed_tools: None,
+        };
+        config.server.auth.tokens = vec![token("ci", "a"), token("ci", "")];
+        assert_eq!(
+            paths(&config),
+            [
+                "server.bind_address",
+                "server.auth.tokens[1].token",
+                "server.auth.tokens[1].name",
+            ]
+        );
+
+        config.server.bind_address = "[::1]:8080".into();
+        config.server.auth.tokens.clear();
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn checks_tool_limits_and_lists() {
+        let mut config = Config::default();
+        config.tools.allow = Some(vec!["airbnb_search".into()]);
+        config.tools.deny = vec!["airbnb_search".into()];
+        config.tools.limits.max_compare_ids = 1;
+        assert_eq!(
+            paths(&config),
+            ["tools.limits.max_compare_ids", "tools.allow"]
+        );
+    }
+
+    #[test]
+    fn checks_disk_cache_only_when_selected() {
+        let file = tempfile::NamedTempFile::new().unwrap();
+        let mut config = Config::default();
+        config.cache.disk.path = Some(file.path().to_path_buf());
+        config.cache.disk.max_entries = 0;
+        assert!(config.validate().is_ok());
+
+        config.cache.backend = CacheBackend::Disk;
+        assert_eq!(
+            paths(&config),
+            ["cache.disk.max_entries", "cache.disk.path"]
+        );
+    }
+
+    #[test]
+    fn memory_tier_settings_need_the_memory_front() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut config = Config::default();
+        config.cache.max_bytes = Some(1 << 20);
+        config.cache.compression = CacheCompression::Lz4;
+        config.cache.backend = CacheBackend::Disk;
+        config.cache.disk.path = Some(dir.path().to_path_buf());
+        assert!(config.validate().is_ok());
+
+        config.cache.disk.memory_front = false;
+        assert_eq!(paths(&config), ["cache.max_bytes", "cache.compression"]);
+    }
+}
@@ -1,6 +1,7 @@
 #![allow(clippy::cast_precision_loss)] // Counts are small enough for f64
 
 use std::collections::HashMap;
+use std::fmt::Write as _;
 
 use chrono::{Datelike, NaiveDate, Weekday};
 use serde::{Deserialize, Serialize};
@@ -13,7 +14,7 @@ use super::review::Review;
 // Types
 // ---------------------------------------------------------------------------
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
 pub struct HostProfile {
     pub host_id: Option<String>,
     pub name: String,
@@ -28,14 +29,14 @@ pub struct HostProfile {
     pub identity_verified: Option<bool>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
 pub struct PropertyTypeCount {
     pub property_type: String,
     pub count: u32,
     pub percentage: f64,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
 pub struct NeighborhoodStats {
     pub location: String,
     pub total_listings: u32,
@@ -47,7 +48,7 @@ pub struct NeighborhoodStats {
     pub superhost_percentage: Option<f64>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
 pub struct MonthlyOccupancy {
     pub month: String,
     pub total_days: u32,
@@ -57,7 +58,7 @@ pub struct MonthlyOccupancy {
     pub average_price: Option<f64>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
 pub struct OccupancyEstimate {
     pub listing_id: String,
     pub period_start: String,
@@ -76,7 +77,7 @@ pub struct OccupancyEstimate {
 // Compare Listings types
 // ---------------------------------------------------------------------------
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
 pub struct ListingComparison {
     pub id: String,
     pub name: String,
@@ -94,7 +95,7 @@ pub struct ListingComparison {
     pub rating_percentile: Option<f64>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
 pub struct ComparisonSummary {
     pub count: u32,
     pub avg_price: f64,
@@ -104,7 +105,7 @@ pub struct ComparisonSummary {
     pub superhost_count: u32,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
 pub struct CompareListingsResult {
     pub listings: Vec<ListingComparison>,
     pub summary: ComparisonSummary,
@@ -114,7 +115,7 @@ pub struct CompareListingsResult {
 // Price Trends types
 // ---------------------------------------------------------------------------
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
 pub struct MonthlyPriceSummary {
     pub month: String,
     pub avg_price: f64,
@@ -126,14 +127,14 @@ pub struct MonthlyPriceSummary {
     pub total_days: u32,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
 pub struct DayOfWeekPrice {
     pub day: String,
     pub avg_price: f64,
     pub sample_count: u32,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
 pub struct PriceTrends {
     pub listing_id: String,
     pub currency: String,
@@ -156,7 +157,7 @@ pub struct PriceTrends {
 // Gap Finder types
 // ---------------------------------------------------------------------------
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
 pub struct CalendarGap {
     pub start_date: String,
     pub end_date: String,
@@ -167,7 +168,7 @@ pub struct CalendarGap {
     pub gap_type: String,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
 pub struct GapFinderResult {
     pub listing_id: String,
     pub total_gaps: u32,
@@ -183,7 +184,7 @@ pub struct GapFinderResult {
 // Revenue Estimate types
 // ---------------------------------------------------------------------------
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
 pub struct MonthlyRevenue {
     pub month: String,
     pub projected_revenue: f64,
@@ -191,7 +192,7 @@ pub struct MonthlyRevenue {
     pub avg_nightly_rate: f64,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
 pub struct RevenueEstimate {
     pub listing_id: Option<String>,
     pub location: String,
@@ -208,14 +209,14 @@ pub struct RevenueEstimate {
 // Listing Score types
 // ---------------------------------------------------------------------------
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
 pub struct CategoryScore {
     pub category: String,
     pub score: f64,
     pub details: String,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
 pub struct ListingScore {
     pub listing_id: String,
     pub overall_score: f64,
@@ -227,14 +228,14 @@ pub struct ListingScore {
 // Amenity Analysis types
 // ---------------------------------------------------------------------------
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
 pub struct AmenityGap {
     pub amenity: String,
     pub neighborhood_frequency_pct: f64,
     pub is_present: bool,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
 pub struct AmenityAnalysis {
     pub listing_id: String,
     pub listing_amenity_count: u32,
@@ -248,7 +249,7 @@ pub struct AmenityAnalysis {
 // Market Comparison types
 // ---------------------------------------------------------------------------
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
 pub struct MarketSnapshot {
     pub location: String,
     pub total_listings: u32,
@@ -259,7 +260,7 @@ pub struct MarketSnapshot {
     pub top_property_type: Option<String>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
 pub struct MarketComparison {
     pub locations: Vec<MarketSnapshot>,
 }
@@ -268,7 +269,7 @@ pub struct MarketComparison {
 // Host Portfolio types
 // ---------------------------------------------------------------------------
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
 pub struct PortfolioProperty {
     pub id: String,
     pub name: String,
@@ -279,7 +280,7 @@ pub struct PortfolioProperty {
     pub property_type: Option<String>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
 pub struct HostPortfolio {
     pub host_name: String,
     pub host_id: Option<String>,
@@ -296,7 +297,7 @@ pub struct HostPortfolio {
 // Review Sentiment types
 // ---------------------------------------------------------------------------
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
 pub struct ReviewTheme {
     pub theme: String,
     pub mention_count: u32,
@@ -305,7 +306,7 @@ pub struct ReviewTheme {
     pub sample_quotes: Vec<String>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
 pub struct ReviewSentiment {
     pub listing_id: String,
     pub total_reviews_analyzed: u32,
@@ -317,11 +318,45 @@ pub struct ReviewSentiment {
     pub top_negative_keywords: Vec<(String, u32)>,
 }
 
+// ---------------------------------------------------------------------------
+// Review Summary types
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, schemars::JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum SummaryMethod {
+    /// Written by the MCP client's model through sampling.
+    Sampling,
+    /// Keyword sentiment analysis, used when sampling is unavailable.
+    KeywordSentiment,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct CitedReview {
+    /// Citation marker used in the summary, e.g. `R3`.
+    pub reference: String,
+    pub author: String,
+    pub date: String,
+    pub rating: Option<f64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct ReviewSummary {
+    pub listing_id: String,
+    pub total_reviews_analyzed: u32,
+    pub method: SummaryMethod,
+    pub model: Option<String>,
+    pub summary: Option<String>,
+    pub cited_reviews: Vec<CitedReview>,
+    pub sentiment: Option<ReviewSentiment>,
+    pub note: Option<String>,
+}
+
 // ---------------------------------------------------------------------------
 // Competitive Positioning types
 // ---------------------------------------------------------------------------
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
 pub struct CompetitiveAxis {
     pub axis: String,
     pub listing_value: f64,
@@ -330,7 +365,7 @@ pub struct CompetitiveAxis {
     pub assessment: String,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
 pub struct CompetitivePositioning {
     pub listing_id: String,
     pub axes: Vec<CompetitiveAxis>,
@@ -343,7 +378,7 @@ pub struct CompetitivePositioning {
 // Optimal Pricing types
 // ---------------------------------------------------------------------------
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
 pub struct PricingRecommendation {
     pub listing_id: String,
     pub current_price: f64,
@@ -860,6 +895,40 @@ impl std::fmt::Display for ReviewSentiment {
     }
 }
 
+impl std::fmt::Display for ReviewSummary {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "=== Review Summary: listing {} ===", self.listing_id)?;
+        writeln!(f, "Reviews Analyzed: {}", self.total_reviews_analyzed)?;
+        if let Some(ref note) = self.note {
+            writeln!(f, "Note: {note}")?;
+        }
+        if let Some(ref summary) = self.summary {
+            if let Some(ref model) = self.model {
+                writeln!(f, "Summarized by: {model}")?;
+            }
+            writeln!(f, "\n{}", summary.trim())?;
+        }
+        if !self.cited_reviews.is_empty() {
+            writeln!(f, "\n--- Cited Reviews ---")?;
+            for review in &self.cited_reviews {
+                let rating = review
+                    .rating
+                    .map(|r| format!(", {r:.1}/5"))
+                    .unwrap_or_default();
+                writeln!(
+                    f,
+                    "[{}] {} ({}{rating})",
+                    review.reference, review.author, review.date
+                )?;
+            }
+        }
+        if let Some(ref sentiment) = self.sentiment {
+            write!(f, "\n{sentiment}")?;
+        }
+        Ok(())
+    }
+}
+
 impl std::fmt::Display for CompetitivePositioning {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         writeln!(
@@ -1002,7 +1071,7 @@ pub fn compute_neighborhood_stats(location: &str, listings: &[Listing]) -> Neigh
             }
         })
         .collect();
-    property_type_distribution.sort_by(|a, b| b.count.cmp(&a.count));
+    property_type_distribution.sort_by_key(|p| std::cmp::Reverse(p.count));
 
     // Superhost percentage
     let superhost_count = listings
@@ -2008,6 +2077,88 @@ pub fn compute_host_portfolio(
     }
 }
 
+// ---------------------------------------------------------------------------
+// Review Summary computation
+// ---------------------------------------------------------------------------
+
+/// Reviews numbered `[R1]`, `[R2]`, ... for a model to cite, each comment cut to
+/// `max_comment_chars`.
+pub fn format_reviews_for_citation(reviews: &[Review], max_comment_chars: usize) -> String {
+    let mut out = String::new();
+    for (i, review) in reviews.iter().enumerate() {
+        let rating = review
+            .rating
+            .map(|r| format!(", {r:.1}/5"))
+            .unwrap_or_default();
+        let comment: String = review
+            .comment
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ")
+            .chars()
+            .take(max_comment_chars)
+            .collect();
+        let _ = writeln!(
+            out,
+            "[R{}] {} ({}{rating}): {comment}",
+            i + 1,
+            review.author,
+            review.date
+        );
+    }
+    out
+}
+
+#[allow(clippy::cast_possible_truncation)]
+pub fn sampled_review_summary(
+    listing_id: &str,
+    reviews: &[Review],
+    model: String,
+    summary: String,
+) -> ReviewSummary {
+    // Only list the reviews the model actually cited
+    let cited_reviews = reviews
+        .iter()
+        .enumerate()
+        .map(|(i, review)| (format!("R{}", i + 1), review))
+        .filter(|(reference, _)| summary.contains(&format!("[{reference}]")))
+        .map(|(reference, review)| CitedReview {
+            reference,
+            author: review.author.clone(),
+            date: review.date.clone(),
+            rating: review.rating,
+        })
+        .collect();
+    ReviewSummary {
+        listing_id: listing_id.to_string(),
+        total_reviews_analyzed: reviews.len() as u32,
+        method: SummaryMethod::Sampling,
+        model: Some(model),
+        summary: Some(summary),
+        cited_reviews,
+        sentiment: None,
+        note: None,
+    }
+}
+
+pub fn fallback_review_summary(
+    listing_id: &str,
+    reviews: &[Review],
+    note: String,
+) -> ReviewSummary {
+    let sentiment = compute_review_sentiment(listing_id, reviews);
+    ReviewSummary {
+        listing_id: listing_id.to_string(),
+        total_reviews_analyzed: sentiment.total_reviews_analyzed,
+        method: SummaryMethod::KeywordSentiment,
+        model: None,
+        summary: None,
+        cited_reviews: Vec::new(),
+        sentiment: Some(sentiment),
+        note: Some(note),
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Review Sentiment computation
 // ---------------------------------------------------------------------------
@@ -2195,14 +2346,14 @@ pub fn compute_review_sentiment(listing_id: &str, reviews: &[Review]) -> ReviewS
             },
         )
         .collect();
-    themes.sort_by(|a, b| b.mention_count.cmp(&a.mention_count));
+    themes.sort_by_key(|t| std::cmp::Reverse(t.mention_count));
 
     let mut top_positive: Vec<(String, u32)> = pos_keyword_counts.into_iter().collect();
-    top_positive.sort_by(|a, b| b.1.cmp(&a.1));
+    top_positive.sort_by_key(|kw| std::cmp::Reverse(kw.1));
     top_positive.truncate(10);
 
     let mut top_negative: Vec<(String, u32)> = neg_keyword_counts.into_iter().collect();
-    top_negative.sort_by(|a, b| b.1.cmp(&a.1));
+    top_negative.sort_by_key(|kw| std::cmp::Reverse(kw.1));
     top_negative.truncate(10);
 
     ReviewSentiment {
@@ -3354,6 +3505,58 @@ mod tests {
         assert!(sentiment.top_negative_keywords.is_empty());
     }
 
+    // -----------------------------------------------------------------------
+    // Review Summary tests
+    // -----------------------------------------------------------------------
+
+    #[test]
+    fn test_format_reviews_for_citation_numbers_and_truncates() {
+        let reviews = vec![
+            make_review("Great   view\nand quiet"),
+            make_review(&"x".repeat(50)),
+        ];
+        let text = format_reviews_for_citation(&reviews, 10);
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines[0], "[R1] TestUser (2025-01-01, 4.0/5): Great view");
+        assert!(lines[1].starts_with("[R2] "));
+        assert!(lines[1].ends_with(&"x".repeat(10)));
+    }
+
+    #[test]
+    fn test_sampled_review_summary_keeps_only_cited_reviews() {
+        let reviews = vec![
+            make_review("Lovely"),
+            make_review("Noisy"),
+            make_review("Clean"),
+        ];
+        let summary = sampled_review_summary(
+            "42",
+            &reviews,
+            "test-model".into(),
+            "Pros: spotless [R3]. Cons: street noise [R2].".into(),
+        );
+        assert_eq!(summary.method, SummaryMethod::Sampling);
+        assert_eq!(summary.total_reviews_analyzed, 3);
+        let refs: Vec<_> = summary
+            .cited_reviews
+            .iter()
+            .map(|r| r.reference.as_str())
+            .collect();
+        assert_eq!(refs, ["R2", "R3"]);
+        let text = summary.to_string();
+        assert!(text.contains("Summarized by: test-model"));
+        assert!(text.contains("[R3] TestUser"));
+    }
+
+    #[test]
+    fn test_fallback_review_summary_uses_sentiment() {
+        let reviews = vec![make_review("Amazing place, super clean!")];
+        let summary = fallback_review_summary("42", &reviews, "no sampling".into());
+        assert_eq!(summary.method, SummaryMethod::KeywordSentiment);
+        assert!(summary.summary.is_none());
+        assert_eq!(summary.sentiment.unwrap().total_reviews_analyzed, 1);
+    }
+
     // -----------------------------------------------------------------------
     // Competitive Positioning tests
     // -----------------------------------------------------------------------
@@ -29,7 +29,7 @@ impl std::fmt::Display for UnavailabilityReason {
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
 pub struct CalendarDay {
     pub date: String,
     pub price: Option<f64>,
@@ -45,7 +45,7 @@ pub struct CalendarDay {
     pub unavailability_reason: Option<UnavailabilityReason>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
 pub struct PriceCalendar {
     pub listing_id: String,
     pub currency: String,
@@ -1,6 +1,6 @@
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
 pub struct Listing {
     pub id: String,
     pub name: String,
@@ -31,7 +31,7 @@ pub struct Listing {
     pub longitude: Option<f64>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
 pub struct ListingDetail {
     pub id: String,
     pub name: String,
@@ -81,7 +81,7 @@ pub struct ListingDetail {
     pub neighborhood: Option<String>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
 pub struct SearchResult {
     pub listings: Vec<Listing>,
     pub total_count: Option<u32>,
@@ -1,6 +1,6 @@
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
 pub struct Review {
     pub author: String,
     pub date: String,
@@ -15,7 +15,7 @@ pub struct Review {
     pub is_translated: Option<bool>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
 pub struct ReviewsSummary {
     pub overall_rating: f64,
     pub total_reviews: u32,
@@ -27,7 +27,7 @@ pub struct ReviewsSummary {
     pub value: Option<f64>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
 pub struct ReviewsPage {
     pub listing_id: String,
     pub summary: Option<ReviewsSummary>,
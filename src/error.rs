@@ -1,3 +1,5 @@
+use std::sync::Arc;
+
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -31,6 +33,10 @@ pub enum AirbnbError {
 
     #[error("URL parse error: {0}")]
     Url(#[from] url::ParseError),
+
+    /// The error of a fetch shared by several concurrent callers (see `SingleFlight`).
+    #[error(transparent)]
+    Coalesced(Arc<AirbnbError>),
 }
 
 pub type Result<T> = std::result::Result<T, AirbnbError>;
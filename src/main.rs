@@ -1,64 +1,214 @@
-use std::path::{Path, PathBuf};
+mod cli;
+
+use std::process::ExitCode;
 use std::sync::Arc;
 
 use anyhow::Result;
+use clap::Parser;
 use rmcp::ServiceExt;
 use rmcp::transport::stdio;
-use tracing_subscriber::EnvFilter;
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::{EnvFilter, Layer};
 
+use mcp_airbnb::adapters::cache::disk_cache::DiskCache;
 use mcp_airbnb::adapters::cache::memory_cache::MemoryCache;
+use mcp_airbnb::adapters::cache::tiered_cache::TieredCache;
 use mcp_airbnb::adapters::composite::CompositeClient;
 use mcp_airbnb::adapters::graphql::client::AirbnbGraphQLClient;
 use mcp_airbnb::adapters::scraper::client::AirbnbScraper;
 use mcp_airbnb::adapters::shared::ApiKeyManager;
-use mcp_airbnb::config::load_config;
+use mcp_airbnb::config::reload::{ConfigReloader, Reloadable};
+use mcp_airbnb::config::types::{CacheBackend, CacheConfig, Config, Transport};
+use mcp_airbnb::config::{ConfigSources, show_config};
+use mcp_airbnb::mcp::http::serve_http;
+use mcp_airbnb::mcp::logging::LogForwarder;
 use mcp_airbnb::mcp::server::AirbnbMcpServer;
+use mcp_airbnb::ports::airbnb_client::AirbnbClient;
+use mcp_airbnb::ports::cache::ListingCache;
 
-fn find_config_path() -> PathBuf {
-    // Check common locations for config file
-    let candidates = [
-        PathBuf::from("config.yaml"),
-        dirs_next().join("config.yaml"),
-    ];
-
-    for path in &candidates {
-        if path.exists() {
-            return path.clone();
-        }
-    }
+use crate::cli::{Cli, Command, ConfigAction};
 
-    candidates[0].clone()
-}
+#[tokio::main]
+async fn main() -> Result<ExitCode> {
+    let cli = Cli::parse();
+    let config_args = cli.config.clone();
+    let command = cli.into_command();
 
-fn dirs_next() -> PathBuf {
-    // Look in the directory where the binary is
-    std::env::current_exe()
+    // Initialize logging to stderr (stdout is reserved for MCP JSON-RPC, or for the result
+    // of a one-shot subcommand), and to MCP clients as notifications/message at the level
+    // each client asks for. One-shot subcommands only log warnings unless RUST_LOG says otherwise.
+    let default_level = if matches!(command, Command::Serve(_)) {
+        "info"
+    } else {
+        "warn"
+    };
+    let directives = std::env::var(EnvFilter::DEFAULT_ENV)
         .ok()
-        .and_then(|p| p.parent().map(Path::to_path_buf))
-        .unwrap_or_else(|| PathBuf::from("."))
-}
-
-#[tokio::main]
-async fn main() -> Result<()> {
-    // Initialize logging to stderr (stdout is reserved for MCP JSON-RPC)
-    tracing_subscriber::fmt()
-        .with_env_filter(
-            EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info")),
+        .filter(|directives| EnvFilter::try_new(directives).is_ok())
+        .unwrap_or_else(|| default_level.to_string());
+    // Globally, other crates only log what RUST_LOG asks for, while this crate's debug
+    // events stay available to clients that lower their level (unless RUST_LOG names it)
+    let global_directives = if directives.contains("mcp_airbnb") {
+        directives.clone()
+    } else {
+        format!("{directives},mcp_airbnb=debug")
+    };
+    let log_forwarder = LogForwarder::default();
+    tracing_subscriber::registry()
+        .with(EnvFilter::new(global_directives))
+        .with(
+            tracing_subscriber::fmt::layer()
+                .with_writer(std::io::stderr)
+                .with_ansi(false)
+                .with_filter(EnvFilter::new(directives)),
         )
-        .with_writer(std::io::stderr)
-        .with_ansi(false)
+        .with(log_forwarder.layer())
         .init();
 
+    // Load configuration: defaults < YAML file < MCP_AIRBNB_* env vars < --set flags
+    // < --transport / --bind (kept as the last overrides so reloads see them too)
+    let mut sources = ConfigSources::from_process(config_args.config, config_args.overrides);
+    if let Command::Serve(ref serve) = command {
+        if let Some(transport) = serve.transport {
+            sources
+                .overrides
+                .push(format!("server.transport={transport}"));
+        }
+        if let Some(ref bind) = serve.bind {
+            sources
+                .overrides
+                .push(format!("server.bind_address={bind}"));
+        }
+    }
+    if config_args.check_config {
+        return Ok(check_config(&sources));
+    }
+    let config = sources.load()?;
+
+    match command {
+        Command::Serve(_) => {}
+        Command::Config {
+            action: ConfigAction::Show,
+        } => {
+            print!("{}", show_config(&config)?);
+            return Ok(ExitCode::SUCCESS);
+        }
+        command => {
+            let resources = config.server.resources.clone();
+            let tools = config.tools.clone();
+            let cache = build_cache(&config.cache)?;
+            let (client, _) = build_client(config, Arc::clone(&cache))?;
+            let server = AirbnbMcpServer::new(client)
+                .with_cache(cache)
+                .with_resource_config(&resources)
+                .with_tools(&tools)?;
+            return cli::run_tool(&command, server).await;
+        }
+    }
+
     tracing::info!("Starting mcp-airbnb server");
+    let server_config = config.server.clone();
+    let tools = config.tools.clone();
 
-    // Load configuration
-    let config_path = find_config_path();
-    let config = load_config(&config_path)?;
+    // Watch the config file and SIGHUP; live settings are swapped into the clients
+    let mut reloader = ConfigReloader::new(sources, config.clone());
+    let cache = build_cache(&config.cache)?;
+    let (client, reloadable) = build_client(config, Arc::clone(&cache))?;
+    for target in reloadable {
+        reloader = reloader.with_target(target);
+    }
+    let _watcher = reloader.spawn();
 
-    // Build dependencies
-    let cache: Arc<dyn mcp_airbnb::ports::cache::ListingCache> =
-        Arc::new(MemoryCache::new(config.cache.max_entries));
+    let server = AirbnbMcpServer::new(client)
+        .with_cache(cache)
+        .with_session_mode(server_config.session_mode)
+        .with_resource_config(&server_config.resources)
+        .with_tools(&tools)?
+        .with_log_forwarder(log_forwarder);
 
+    match server_config.transport {
+        Transport::Stdio => {
+            // Start MCP server over stdio
+            let service = server.serve(stdio()).await?;
+            service.waiting().await?;
+        }
+        Transport::Http => {
+            let listener = tokio::net::TcpListener::bind(&server_config.bind_address)
+                .await
+                .map_err(|e| {
+                    anyhow::anyhow!("failed to bind {}: {e}", server_config.bind_address)
+                })?;
+            serve_http(server, listener, &server_config, async {
+                let _ = tokio::signal::ctrl_c().await;
+                tracing::info!("Shutdown signal received, closing MCP sessions");
+            })
+            .await?;
+        }
+    }
+
+    Ok(ExitCode::SUCCESS)
+}
+
+/// `--check-config`: report every problem with the merged configuration on stderr.
+fn check_config(sources: &ConfigSources) -> ExitCode {
+    let issues = match sources.merge() {
+        Ok(config) => {
+            let mut issues = config.issues();
+            issues.extend(AirbnbMcpServer::tool_issues(&config.tools));
+            issues
+        }
+        Err(e) => {
+            eprintln!("{}: {e}", sources.file.display());
+            return ExitCode::FAILURE;
+        }
+    };
+    if issues.is_empty() {
+        eprintln!("{}: configuration is valid", sources.file.display());
+        return ExitCode::SUCCESS;
+    }
+    eprintln!(
+        "{}: {} invalid setting(s)",
+        sources.file.display(),
+        issues.len()
+    );
+    for issue in &issues {
+        eprintln!("  - {issue}");
+    }
+    ExitCode::FAILURE
+}
+
+/// The `ListingCache` selected by `cache.backend`.
+fn build_cache(config: &CacheConfig) -> Result<Arc<dyn ListingCache>> {
+    let memory = MemoryCache::new(config.max_entries)
+        .with_max_bytes(config.max_bytes)
+        .with_compression(config.compression);
+    match config.backend {
+        CacheBackend::Memory => Ok(Arc::new(memory)),
+        CacheBackend::Disk => {
+            let path = config.disk.resolved_path();
+            let disk = DiskCache::open(&path, config.disk.max_entries).map_err(|e| {
+                anyhow::anyhow!("failed to open disk cache at {}: {e}", path.display())
+            })?;
+            tracing::info!(path = %path.display(), entries = disk.len(), memory_front = config.disk.memory_front, "Using disk cache");
+            if config.disk.memory_front {
+                Ok(Arc::new(TieredCache::new(memory, disk)))
+            } else {
+                Ok(Arc::new(disk))
+            }
+        }
+    }
+}
+
+type ReloadTargets = Vec<Arc<dyn Reloadable>>;
+
+/// Build the Airbnb client stack (API key manager, GraphQL and/or HTML scraper on top
+/// of `cache`) shared by the MCP server and the one-shot subcommands, along with the
+/// parts of it that take reloaded settings.
+fn build_client(
+    config: Config,
+    cache: Arc<dyn ListingCache>,
+) -> Result<(Arc<dyn AirbnbClient>, ReloadTargets)> {
     // Shared API key manager (used by both scraper and GraphQL client)
     let http_for_key = reqwest::Client::builder()
         .user_agent(&config.scraper.user_agent)
@@ -73,39 +223,37 @@ async fn main() -> Result<()> {
         config.scraper.api_key_cache_secs,
     ));
 
-    let client: Arc<dyn mcp_airbnb::ports::airbnb_client::AirbnbClient> = if config
-        .scraper
-        .graphql_enabled
-    {
+    if config.scraper.graphql_enabled {
         tracing::info!("GraphQL mode enabled — using composite client (GraphQL + HTML fallback)");
-        let graphql = AirbnbGraphQLClient::new(
-            &config.scraper,
-            config.cache.clone(),
-            Arc::clone(&cache),
-            Arc::clone(&api_key_manager),
-        )
-        .map_err(|e| anyhow::anyhow!("failed to create GraphQL client: {e}"))?;
-        let scraper = AirbnbScraper::new(
-            config.scraper,
-            config.cache,
-            Arc::clone(&cache),
-            Arc::clone(&api_key_manager),
-        )
-        .map_err(|e| anyhow::anyhow!("failed to create scraper client: {e}"))?;
-        Arc::new(CompositeClient::new(Box::new(graphql), Box::new(scraper)))
+        let graphql = Arc::new(
+            AirbnbGraphQLClient::new(
+                &config.scraper,
+                config.cache.clone(),
+                Arc::clone(&cache),
+                Arc::clone(&api_key_manager),
+            )
+            .map_err(|e| anyhow::anyhow!("failed to create GraphQL client: {e}"))?,
+        );
+        let scraper = Arc::new(
+            AirbnbScraper::new(
+                config.scraper,
+                config.cache,
+                Arc::clone(&cache),
+                Arc::clone(&api_key_manager),
+            )
+            .map_err(|e| anyhow::anyhow!("failed to create scraper client: {e}"))?,
+        );
+        let client = CompositeClient::new(
+            Box::new(Arc::clone(&graphql)),
+            Box::new(Arc::clone(&scraper)),
+        );
+        Ok((Arc::new(client), vec![graphql, scraper]))
     } else {
         tracing::info!("GraphQL disabled — using HTML scraper only");
-        Arc::new(
+        let scraper = Arc::new(
             AirbnbScraper::new(config.scraper, config.cache, cache, api_key_manager)
                 .map_err(|e| anyhow::anyhow!("failed to create scraper client: {e}"))?,
-        )
-    };
-
-    let server = AirbnbMcpServer::new(client);
-
-    // Start MCP server over stdio
-    let service = server.serve(stdio()).await?;
-    service.waiting().await?;
-
-    Ok(())
+        );
+        Ok((Arc::clone(&scraper) as Arc<dyn AirbnbClient>, vec![scraper]))
+    }
 }
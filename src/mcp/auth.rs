@@ -0,0 +1,277 @@
+use std::path::Path;
+use std::sync::Arc;
+
+use axum::extract::{Request, State};
+use axum::http::{HeaderMap, HeaderValue, StatusCode, header};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use rmcp::RoleServer;
+use rmcp::service::RequestContext;
+
+use crate::config::types::{AuthConfig, TokenConfig};
+use crate::error::{AirbnbError, Result};
+
+/// Alternative header for clients that cannot send `Authorization: Bearer`.
+pub const API_KEY_HEADER: &str = "x-api-key";
+
+/// JSON-RPC error code returned for missing or invalid credentials.
+const UNAUTHORIZED_CODE: i32 = -32001;
+
+/// The caller behind an authenticated HTTP request.
+///
+/// Inserted into the request extensions by [`require_token`] and read back in the
+/// MCP handler through [`identity`].
+#[derive(Debug, Clone)]
+pub struct AuthIdentity {
+    pub name: String,
+    allowed_tools: Option<Vec<String>>,
+}
+
+impl AuthIdentity {
+    pub fn allows_tool(&self, tool: &str) -> bool {
+        self.allowed_tools
+            .as_ref()
+            .is_none_or(|tools| tools.iter().any(|t| t == tool))
+    }
+
+    /// Whether `allowed_tools` lists `tool` by name, rather than allowing every tool.
+    pub fn names_tool(&self, tool: &str) -> bool {
+        self.allowed_tools
+            .as_ref()
+            .is_some_and(|tools| tools.iter().any(|t| t == tool))
+    }
+}
+
+/// The set of static tokens accepted by the HTTP transport.
+#[derive(Debug, Default)]
+pub struct TokenStore {
+    tokens: Vec<TokenConfig>,
+}
+
+impl TokenStore {
+    /// Collect inline tokens and those from `token_file`.
+    pub fn from_config(config: &AuthConfig) -> Result<Self> {
+        let mut tokens = config.tokens.clone();
+        if let Some(ref path) = config.token_file {
+            tokens.extend(load_token_file(path)?);
+        }
+        if let Some(empty) = tokens.iter().find(|t| t.token.is_empty()) {
+            return Err(AirbnbError::Config(format!(
+                "auth token '{}' has an empty secret",
+                empty.name
+            )));
+        }
+        Ok(Self { tokens })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tokens.is_empty()
+    }
+
+    /// Find the identity for a presented secret, comparing in constant time.
+    pub fn authenticate(&self, presented: &str) -> Option<AuthIdentity> {
+        // Check every token so the response time does not reveal which one matched
+        let mut found = None;
+        for token in &self.tokens {
+            if constant_time_eq(token.token.as_bytes(), presented.as_bytes()) && found.is_none() {
+                found = Some(AuthIdentity {
+                    name: token.name.clone(),
+                    allowed_tools: token.allowed_tools.clone(),
+                });
+            }
+        }
+        found
+    }
+}
+
+fn load_token_file(path: &Path) -> Result<Vec<TokenConfig>> {
+    let content = std::fs::read_to_string(path).map_err(|e| {
+        AirbnbError::Config(format!("failed to read token file {}: {e}", path.display()))
+    })?;
+    serde_yml::from_str(&content).map_err(|e| {
+        AirbnbError::Config(format!(
+            "failed to parse token file {}: {e}",
+            path.display()
+        ))
+    })
+}
+
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+/// Extract the presented secret from `Authorization: Bearer <token>` or `X-API-Key`.
+/// Other `Authorization` schemes (say, `Basic` added by a proxy) fall through to
+/// `X-API-Key`.
+fn presented_token(headers: &HeaderMap) -> Option<&str> {
+    let bearer = headers
+        .get(header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|value| value.split_once(' '))
+        .filter(|(scheme, _)| scheme.eq_ignore_ascii_case("bearer"));
+    if let Some((_, token)) = bearer {
+        return Some(token.trim()).filter(|t| !t.is_empty());
+    }
+    headers
+        .get(API_KEY_HEADER)
+        .and_then(|v| v.to_str().ok())
+        .map(str::trim)
+        .filter(|t| !t.is_empty())
+}
+
+/// Axum middleware rejecting requests without a valid token with `401 Unauthorized`.
+///
+/// The body is a JSON-RPC error so MCP clients can surface it like any other protocol error.
+pub async fn require_token(
+    State(store): State<Arc<TokenStore>>,
+    mut request: Request,
+    next: Next,
+) -> Response {
+    let identity = presented_token(request.headers()).and_then(|t| store.authenticate(t));
+    let Some(identity) = identity else {
+        tracing::warn!("Rejected unauthenticated MCP request");
+        return unauthorized_response();
+    };
+    tracing::debug!(token = %identity.name, "Authenticated MCP request");
+    request.extensions_mut().insert(identity);
+    next.run(request).await
+}
+
+fn unauthorized_response() -> Response {
+    let body = serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": null,
+        "error": {
+            "code": UNAUTHORIZED_CODE,
+            "message": "Unauthorized: provide a valid token via 'Authorization: Bearer <token>' or 'X-API-Key'",
+        }
+    });
+    let mut response = (StatusCode::UNAUTHORIZED, axum::Json(body)).into_response();
+    response.headers_mut().insert(
+        header::WWW_AUTHENTICATE,
+        HeaderValue::from_static("Bearer realm=\"mcp-airbnb\""),
+    );
+    response
+}
+
+/// The authenticated caller of an MCP request, if it arrived over authenticated HTTP.
+pub fn identity(context: &RequestContext<RoleServer>) -> Option<&AuthIdentity> {
+    context
+        .extensions
+        .get::<axum::http::request::Parts>()?
+        .extensions
+        .get::<AuthIdentity>()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write as _;
+
+    fn token(name: &str, secret: &str, tools: Option<&[&str]>) -> TokenConfig {
+        TokenConfig {
+            name: name.into(),
+            token: secret.into(),
+            allowed_tools: tools.map(|t| t.iter().map(ToString::to_string).collect()),
+        }
+    }
+
+    fn store(tokens: Vec<TokenConfig>) -> TokenStore {
+        TokenStore::from_config(&AuthConfig {
+            tokens,
+            token_file: None,
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn authenticate_matches_configured_token() {
+        let store = store(vec![token("ci", "abc", None), token("ops", "xyz", None)]);
+        assert_eq!(store.authenticate("xyz").unwrap().name, "ops");
+        assert!(store.authenticate("nope").is_none());
+        assert!(store.authenticate("").is_none());
+    }
+
+    #[test]
+    fn allowlist_restricts_tools() {
+        let store = store(vec![token("ci", "abc", Some(&["airbnb_search"]))]);
+        let identity = store.authenticate("abc").unwrap();
+        assert!(identity.allows_tool("airbnb_search"));
+        assert!(!identity.allows_tool("airbnb_compare_listings"));
+    }
+
+    #[test]
+    fn missing_allowlist_allows_everything() {
+        let store = store(vec![token("admin", "abc", None)]);
+        assert!(
+            store
+                .authenticate("abc")
+                .unwrap()
+                .allows_tool("airbnb_compare_listings")
+        );
+    }
+
+    #[test]
+    fn presented_token_prefers_bearer_and_falls_back_to_api_key() {
+        let headers = |pairs: &[(&'static str, &'static str)]| {
+            let mut map = HeaderMap::new();
+            for (name, value) in pairs {
+                map.insert(*name, value.parse().unwrap());
+            }
+            map
+        };
+        let both = headers(&[("authorization", "Bearer abc"), (API_KEY_HEADER, "xyz")]);
+        assert_eq!(presented_token(&both), Some("abc"));
+        let basic = headers(&[
+            ("authorization", "Basic dXNlcjpwdw=="),
+            (API_KEY_HEADER, "xyz"),
+        ]);
+        assert_eq!(presented_token(&basic), Some("xyz"));
+        assert_eq!(
+            presented_token(&headers(&[("authorization", "Basic x")])),
+            None
+        );
+        assert_eq!(
+            presented_token(&headers(&[("authorization", "Bearer ")])),
+            None
+        );
+    }
+
+    #[test]
+    fn empty_secret_is_rejected() {
+        let result = TokenStore::from_config(&AuthConfig {
+            tokens: vec![token("broken", "", None)],
+            token_file: None,
+        });
+        assert!(result.unwrap_err().to_string().contains("broken"));
+    }
+
+    #[test]
+    fn token_file_is_merged() {
+        let mut tmp = tempfile::NamedTempFile::new().unwrap();
+        writeln!(
+            tmp,
+            "- name: from-file\n  token: filesecret\n  allowed_tools: [airbnb_reviews]"
+        )
+        .unwrap();
+        let store = TokenStore::from_config(&AuthConfig {
+            tokens: vec![token("inline", "abc", None)],
+            token_file: Some(tmp.path().to_path_buf()),
+        })
+        .unwrap();
+        assert_eq!(store.authenticate("filesecret").unwrap().name, "from-file");
+        assert_eq!(store.authenticate("abc").unwrap().name, "inline");
+    }
+
+    #[test]
+    fn missing_token_file_is_config_error() {
+        let result = TokenStore::from_config(&AuthConfig {
+            tokens: vec![],
+            token_file: Some("/tmp/nonexistent_mcp_tokens_12345.yaml".into()),
+        });
+        assert!(matches!(result, Err(AirbnbError::Config(_))));
+    }
+}
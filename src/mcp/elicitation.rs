@@ -0,0 +1,404 @@
+use std::collections::BTreeSet;
+use std::time::Duration;
+
+use chrono::NaiveDate;
+
+use rmcp::{
+    ErrorData as McpError, Peer, RoleServer,
+    handler::server::common::{AsRequestContext, FromContextPart},
+    model::{
+        CreateElicitationRequestParams, ElicitationAction, ElicitationSchema, EnumSchema,
+        IntegerSchema, PrimitiveSchema, StringSchema,
+    },
+    service::ElicitationMode,
+};
+use serde_json::Value;
+use tokio::time::Instant;
+
+use crate::domain::search_params::SearchParams;
+
+/// How long the questions of one tool call may wait for the user, together, before the
+/// search goes ahead without the answers.
+const ANSWER_TIMEOUT: Duration = Duration::from_mins(5);
+
+/// Times the trip form is shown while the dates entered are invalid.
+const DATE_ATTEMPTS: usize = 2;
+
+/// Place names that commonly refer to several destinations. Locations seen earlier in the
+/// session (e.g. "Paris, Texas") are added to these when asking the user to pick one.
+const AMBIGUOUS_PLACES: &[(&str, &[&str])] = &[
+    ("paris", &["Paris, France", "Paris, Texas, United States"]),
+    (
+        "portland",
+        &[
+            "Portland, Oregon, United States",
+            "Portland, Maine, United States",
+        ],
+    ),
+    (
+        "cambridge",
+        &[
+            "Cambridge, United Kingdom",
+            "Cambridge, Massachusetts, United States",
+        ],
+    ),
+    (
+        "london",
+        &["London, United Kingdom", "London, Ontario, Canada"],
+    ),
+    (
+        "san jose",
+        &[
+            "San Jose, California, United States",
+            "San José, Costa Rica",
+        ],
+    ),
+    ("valencia", &["Valencia, Spain", "Valencia, Venezuela"]),
+    (
+        "victoria",
+        &["Victoria, British Columbia, Canada", "Victoria, Australia"],
+    ),
+    (
+        "springfield",
+        &[
+            "Springfield, Illinois, United States",
+            "Springfield, Missouri, United States",
+            "Springfield, Massachusetts, United States",
+        ],
+    ),
+];
+
+/// Asks the user for missing search details through MCP elicitation (`elicitation/create`).
+///
+/// Extracted from the request like `Parameters`. It only asks when the client declared
+/// form elicitation; otherwise, and whenever the user declines, cancels or does not
+/// answer, the search parameters are left as they are. All questions of one request
+/// share [`ANSWER_TIMEOUT`], counted from the extraction.
+#[derive(Clone)]
+pub struct Elicitor {
+    peer: Option<Peer<RoleServer>>,
+    deadline: Instant,
+}
+
+impl Elicitor {
+    /// An elicitor for clients without the `elicitation` capability.
+    pub fn unavailable() -> Self {
+        Self {
+            peer: None,
+            deadline: Instant::now(),
+        }
+    }
+
+    pub fn is_available(&self) -> bool {
+        self.peer.is_some()
+    }
+
+    /// Let the user pick a destination when `params.location` names several places.
+    pub async fn resolve_location(&self, params: &mut SearchParams, seen: &BTreeSet<String>) {
+        let choices = location_choices(&params.location, seen);
+        if choices.is_empty() {
+            return;
+        }
+        let message = format!(
+            "\"{}\" matches several places. Which one do you want to search?",
+            params.location
+        );
+        let mut values = choices;
+        values.push(params.location.clone());
+        let schema = ElicitationSchema::builder()
+            .required_enum_schema(
+                "location",
+                EnumSchema::builder(values)
+                    .title("Destination")
+                    .description("The last option searches the name as typed")
+                    .build(),
+            )
+            .build_unchecked();
+        if let Some(answer) = self.ask(message, schema).await
+            && let Some(location) = answer.get("location").and_then(Value::as_str)
+            && !location.trim().is_empty()
+        {
+            params.location = location.trim().to_string();
+        }
+    }
+
+    /// Ask for the stay dates and guest count when the search has none. Invalid dates
+    /// are asked for again, and left out if the second answer is invalid too.
+    pub async fn fill_trip_details(&self, params: &mut SearchParams) {
+        let mut message = if params.checkin.is_none() {
+            format!(
+                "When are you staying in {} and with how many guests? Prices depend on the dates.",
+                params.location
+            )
+        } else {
+            format!("How many guests are staying in {}?", params.location)
+        };
+        for _ in 0..DATE_ATTEMPTS {
+            let Some(schema) = trip_details_schema(params) else {
+                return;
+            };
+            let Some(answer) = self.ask(message, schema).await else {
+                return;
+            };
+            match merge_trip_details(params, &answer) {
+                Ok(()) => return,
+                Err(reason) => {
+                    tracing::debug!(reason, "Elicited dates rejected");
+                    message = format!(
+                        "{reason}. Please enter the dates of your stay in {} again (YYYY-MM-DD).",
+                        params.location
+                    );
+                }
+            }
+        }
+    }
+
+    /// Send one form and return its content if the user accepted it.
+    async fn ask(&self, message: String, schema: ElicitationSchema) -> Option<Value> {
+        let peer = self.peer.as_ref()?;
+        let remaining = self.deadline.saturating_duration_since(Instant::now());
+        if remaining.is_zero() {
+            tracing::debug!("No time left to ask the user, searching as requested");
+            return None;
+        }
+        let request = CreateElicitationRequestParams::FormElicitationParams {
+            meta: None,
+            message,
+            requested_schema: schema,
+        };
+        match peer
+            .create_elicitation_with_timeout(request, Some(remaining))
+            .await
+        {
+            Ok(result) if result.action == ElicitationAction::Accept => result.content,
+            Ok(result) => {
+                tracing::debug!(action = ?result.action, "Elicitation not accepted, searching as requested");
+                None
+            }
+            Err(e) => {
+                tracing::debug!(error = %e, "Elicitation failed, searching as requested");
+                None
+            }
+        }
+    }
+}
+
+impl<C: AsRequestContext> FromContextPart<C> for Elicitor {
+    fn from_context_part(context: &mut C) -> Result<Self, McpError> {
+        let peer = &context.as_request_context().peer;
+        let supported = peer
+            .supported_elicitation_modes()
+            .contains(&ElicitationMode::Form);
+        Ok(Self {
+            peer: supported.then(|| peer.clone()),
+            deadline: Instant::now() + ANSWER_TIMEOUT,
+        })
+    }
+}
+
+/// Destinations `location` may refer to, or nothing when it is already qualified
+/// (contains a comma) or matches fewer than two known places.
+pub fn location_choices(location: &str, seen: &BTreeSet<String>) -> Vec<String> {
+    let name = location.trim().to_lowercase();
+    if name.is_empty() || name.contains(',') {
+        return Vec::new();
+    }
+    let prefix = format!("{name},");
+    let mut choices: Vec<String> = AMBIGUOUS_PLACES
+        .iter()
+        .filter(|(place, _)| *place == name)
+        .flat_map(|(_, known)| known.iter().map(|s| (*s).to_string()))
+        .collect();
+    for candidate in seen {
+        let lower = candidate.to_lowercase();
+        if lower.starts_with(&prefix) && !choices.iter().any(|c| c.to_lowercase() == lower) {
+            choices.push(candidate.clone());
+        }
+    }
+    if choices.len() < 2 {
+        choices.clear();
+    }
+    choices
+}
+
+/// Form asking for whatever of dates and adults `params` lacks, or `None` if complete.
+fn trip_details_schema(params: &SearchParams) -> Option<ElicitationSchema> {
+    let wants_dates = params.checkin.is_none() || params.checkout.is_none();
+    let wants_guests = params.adults.is_none();
+    if !wants_dates && !wants_guests {
+        return None;
+    }
+    let mut builder = ElicitationSchema::builder();
+    if wants_dates {
+        builder = builder
+            .required_property(
+                "checkin",
+                PrimitiveSchema::String(StringSchema::date().title("Check-in (YYYY-MM-DD)")),
+            )
+            .required_property(
+                "checkout",
+                PrimitiveSchema::String(StringSchema::date().title("Check-out (YYYY-MM-DD)")),
+            );
+    }
+    if wants_guests {
+        builder = builder.property(
+            "adults",
+            PrimitiveSchema::Integer(IntegerSchema::new().range(1, 16).title("Adults")),
+        );
+        if params.children.is_none() {
+            builder = builder.property(
+                "children",
+                PrimitiveSchema::Integer(IntegerSchema::new().range(0, 15).title("Children")),
+            );
+        }
+    }
+    Some(builder.build_unchecked())
+}
+
+/// Copy accepted answers into the fields `params` does not set yet. Dates are only taken
+/// as a valid pair, so a half-filled form cannot make the search invalid; an invalid
+/// pair is reported so the user can be asked again.
+fn merge_trip_details(params: &mut SearchParams, answer: &Value) -> Result<(), String> {
+    let text = |key: &str| {
+        answer
+            .get(key)
+            .and_then(Value::as_str)
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+            .map(String::from)
+    };
+    let count = |key: &str| {
+        answer
+            .get(key)
+            .and_then(Value::as_u64)
+            .and_then(|n| u32::try_from(n).ok())
+    };
+    if params.adults.is_none() {
+        params.adults = count("adults");
+    }
+    if params.children.is_none() {
+        params.children = count("children").filter(|&n| n > 0);
+    }
+    if params.checkin.is_none()
+        && params.checkout.is_none()
+        && let (Some(checkin), Some(checkout)) = (text("checkin"), text("checkout"))
+    {
+        check_stay(&checkin, &checkout)?;
+        params.checkin = Some(checkin);
+        params.checkout = Some(checkout);
+    }
+    Ok(())
+}
+
+/// Why `checkin` to `checkout` is not a stay, if it isn't.
+fn check_stay(checkin: &str, checkout: &str) -> Result<(), String> {
+    let date = |value: &str| {
+        NaiveDate::parse_from_str(value, "%Y-%m-%d")
+            .map_err(|_| format!("'{value}' is not a date in the form YYYY-MM-DD"))
+    };
+    if date(checkout)? <= date(checkin)? {
+        return Err(format!(
+            "Check-out {checkout} is not after check-in {checkin}"
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn params(location: &str) -> SearchParams {
+        SearchParams {
+            location: location.into(),
+            ..SearchParams::default()
+        }
+    }
+
+    #[test]
+    fn qualified_or_unknown_locations_are_not_ambiguous() {
+        let seen = BTreeSet::new();
+        assert!(location_choices("Paris, France", &seen).is_empty());
+        assert!(location_choices("Tokyo", &seen).is_empty());
+        assert_eq!(
+            location_choices(" paris ", &seen),
+            ["Paris, France", "Paris, Texas, United States"]
+        );
+    }
+
+    #[test]
+    fn session_history_adds_location_choices() {
+        let seen: BTreeSet<String> = ["Lyon, France", "Nice, France", "Nice, Colorado"]
+            .into_iter()
+            .map(String::from)
+            .collect();
+        assert_eq!(
+            location_choices("Nice", &seen),
+            ["Nice, Colorado", "Nice, France"]
+        );
+        assert!(location_choices("Lyon", &seen).is_empty());
+    }
+
+    #[test]
+    fn trip_form_only_asks_for_missing_fields() {
+        let mut complete = params("Tokyo");
+        complete.checkin = Some("2026-06-01".into());
+        complete.checkout = Some("2026-06-05".into());
+        complete.adults = Some(2);
+        assert!(trip_details_schema(&complete).is_none());
+
+        complete.adults = None;
+        let schema = trip_details_schema(&complete).unwrap();
+        assert!(schema.properties.contains_key("adults"));
+        assert!(!schema.properties.contains_key("checkin"));
+        assert!(schema.required.is_none());
+    }
+
+    #[test]
+    fn answers_merge_without_overriding_the_caller() {
+        let mut search = params("Tokyo");
+        search.children = Some(1);
+        merge_trip_details(
+            &mut search,
+            &serde_json::json!({
+                "checkin": "2026-06-01",
+                "checkout": "2026-06-05",
+                "adults": 2,
+                "children": 3
+            }),
+        )
+        .unwrap();
+        assert_eq!(search.checkin.as_deref(), Some("2026-06-01"));
+        assert_eq!(search.checkout.as_deref(), Some("2026-06-05"));
+        assert_eq!(search.adults, Some(2));
+        assert_eq!(search.children, Some(1));
+        assert!(search.validate().is_ok());
+
+        let mut half = params("Tokyo");
+        merge_trip_details(&mut half, &serde_json::json!({ "checkin": "2026-06-01" })).unwrap();
+        assert!(half.checkin.is_none());
+    }
+
+    #[test]
+    fn invalid_dates_are_rejected_but_guests_kept() {
+        let mut search = params("Tokyo");
+        let reason = merge_trip_details(
+            &mut search,
+            &serde_json::json!({ "checkin": "2026-06-05", "checkout": "2026-06-01", "adults": 2 }),
+        )
+        .unwrap_err();
+        assert!(reason.contains("not after check-in"));
+        assert!(search.checkin.is_none() && search.checkout.is_none());
+        assert_eq!(search.adults, Some(2));
+
+        let reason = merge_trip_details(
+            &mut search,
+            &serde_json::json!({ "checkin": "June 1st", "checkout": "2026-06-05" }),
+        )
+        .unwrap_err();
+        assert!(reason.contains("'June 1st'"));
+        // Only the dates are asked for again
+        let schema = trip_details_schema(&search).unwrap();
+        assert!(!schema.properties.contains_key("adults"));
+    }
+}
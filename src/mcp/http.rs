@@ -0,0 +1,91 @@
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Duration;
+
+use axum::Router;
+use rmcp::transport::streamable_http_server::session::local::LocalSessionManager;
+use rmcp::transport::{StreamableHttpServerConfig, StreamableHttpService};
+use tokio::net::TcpListener;
+use tokio_util::sync::CancellationToken;
+
+use crate::config::types::ServerConfig;
+use crate::error::Result;
+use crate::mcp::auth::{TokenStore, require_token};
+use crate::mcp::server::AirbnbMcpServer;
+
+/// Build the HTTP router that serves `server` over MCP streamable HTTP at `config.http_path`.
+///
+/// Every MCP session gets its own instance from [`AirbnbMcpServer::for_session`]. All
+/// sessions share the same `AirbnbClient` (and therefore the same warm cache and API key
+/// manager); whether they also share resources depends on the server's `SessionMode`.
+///
+/// When `config.auth` holds any tokens, every request must carry one of them
+/// (see [`require_token`]). Returns the router and the cancellation token that
+/// terminates all open sessions.
+pub fn build_router(
+    server: AirbnbMcpServer,
+    config: &ServerConfig,
+) -> Result<(Router, CancellationToken)> {
+    let tokens = TokenStore::from_config(&config.auth)?;
+
+    let http_config = StreamableHttpServerConfig {
+        sse_keep_alive: (config.sse_keep_alive_secs > 0)
+            .then(|| Duration::from_secs(config.sse_keep_alive_secs)),
+        ..StreamableHttpServerConfig::default()
+    };
+    let cancellation_token = http_config.cancellation_token.clone();
+
+    let service = StreamableHttpService::new(
+        move || Ok(server.for_session()),
+        Arc::new(LocalSessionManager::default()),
+        http_config,
+    );
+
+    let path = config.http_path.trim_end_matches('/');
+    let mut router = if path.is_empty() {
+        Router::new().fallback_service(service)
+    } else {
+        Router::new().nest_service(path, service)
+    };
+
+    if tokens.is_empty() {
+        tracing::warn!(
+            "No auth tokens configured — HTTP transport accepts unauthenticated clients"
+        );
+    } else {
+        router = router.layer(axum::middleware::from_fn_with_state(
+            Arc::new(tokens),
+            require_token,
+        ));
+    }
+
+    Ok((router, cancellation_token))
+}
+
+/// Serve `server` over MCP streamable HTTP on `listener` until `shutdown` resolves.
+pub async fn serve_http<F>(
+    server: AirbnbMcpServer,
+    listener: TcpListener,
+    config: &ServerConfig,
+    shutdown: F,
+) -> Result<()>
+where
+    F: Future<Output = ()> + Send + 'static,
+{
+    let (router, cancellation_token) = build_router(server, config)?;
+
+    tracing::info!(
+        address = %listener.local_addr()?,
+        path = %config.http_path,
+        "Serving MCP over streamable HTTP"
+    );
+
+    axum::serve(listener, router)
+        .with_graceful_shutdown(async move {
+            shutdown.await;
+            cancellation_token.cancel();
+        })
+        .await?;
+
+    Ok(())
+}
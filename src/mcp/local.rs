@@ -0,0 +1,170 @@
+use rmcp::{
+    RoleClient, ServiceExt,
+    model::{CallToolRequestParams, CallToolResult, JsonObject, Tool},
+    service::RunningService,
+};
+use serde_json::Value;
+use tokio::task::JoinHandle;
+
+use crate::error::{AirbnbError, Result};
+use crate::mcp::server::AirbnbMcpServer;
+
+/// An in-process MCP session for one-shot tool calls without an MCP host.
+///
+/// The server runs on one end of a `tokio::io::duplex` pipe and a plain client on the
+/// other, so calls go through the same router, argument validation and output as they
+/// would from a real host. Used by the CLI subcommands (`mcp-airbnb search ...`).
+pub struct LocalSession {
+    client: RunningService<RoleClient, ()>,
+    server: JoinHandle<()>,
+}
+
+impl LocalSession {
+    pub async fn start(server: AirbnbMcpServer) -> Result<Self> {
+        let (server_transport, client_transport) = tokio::io::duplex(1 << 16);
+        let server = tokio::spawn(async move {
+            match server.serve(server_transport).await {
+                Ok(running) => {
+                    let _ = running.waiting().await;
+                }
+                Err(e) => tracing::error!(error = %e, "Local MCP session failed to start"),
+            }
+        });
+        let client = ().serve(client_transport).await.map_err(std::io::Error::other)?;
+        Ok(Self { client, server })
+    }
+
+    /// The tool called `name`, if the server has one.
+    pub async fn tool(&self, name: &str) -> Result<Option<Tool>> {
+        let tools = self
+            .client
+            .list_all_tools()
+            .await
+            .map_err(std::io::Error::other)?;
+        Ok(tools.into_iter().find(|tool| tool.name == name))
+    }
+
+    /// Call `name` with `arguments`. Tool-level failures come back as a result with
+    /// `is_error` set; rejected arguments and unknown tools are errors.
+    pub async fn call(&self, name: &str, arguments: JsonObject) -> Result<CallToolResult> {
+        self.client
+            .call_tool(CallToolRequestParams {
+                meta: None,
+                name: name.to_string().into(),
+                arguments: Some(arguments),
+                task: None,
+            })
+            .await
+            .map_err(|e| AirbnbError::InvalidParams {
+                reason: format!("{name}: {e}"),
+            })
+    }
+
+    pub async fn close(self) {
+        let _ = self.client.cancel().await;
+        let _ = self.server.await;
+    }
+}
+
+/// Build tool arguments from `key=value` pairs, typed after the tool's input schema:
+/// string properties take the value as-is, array properties split it on commas, and
+/// anything else is parsed as JSON (`months=6`, `fresh=true`).
+pub fn arguments_from_pairs(tool: &Tool, pairs: &[String]) -> Result<JsonObject> {
+    let properties = tool
+        .input_schema
+        .get("properties")
+        .and_then(Value::as_object);
+    let mut arguments = JsonObject::new();
+    for pair in pairs {
+        let Some((key, raw)) = pair.split_once('=') else {
+            return Err(invalid(format!("expected key=value, got '{pair}'")));
+        };
+        let Some(schema) = properties.and_then(|p| p.get(key)) else {
+            let known: Vec<&str> = properties
+                .map(|p| p.keys().map(String::as_str).collect())
+                .unwrap_or_default();
+            return Err(invalid(format!(
+                "unknown argument '{key}' for {}; expected one of: {}",
+                tool.name,
+                known.join(", ")
+            )));
+        };
+        arguments.insert(key.to_string(), typed_value(schema, raw));
+    }
+    Ok(arguments)
+}
+
+fn typed_value(schema: &Value, raw: &str) -> Value {
+    if has_type(schema, "array") {
+        let items = schema.get("items").unwrap_or(&Value::Null);
+        return Value::Array(
+            raw.split(',')
+                .map(str::trim)
+                .filter(|item| !item.is_empty())
+                .map(|item| typed_value(items, item))
+                .collect(),
+        );
+    }
+    if has_type(schema, "string") {
+        return Value::String(raw.to_string());
+    }
+    serde_json::from_str(raw).unwrap_or_else(|_| Value::String(raw.to_string()))
+}
+
+/// Whether a JSON schema allows `ty`, as `"type": "x"` or `"type": ["x", "null"]`.
+fn has_type(schema: &Value, ty: &str) -> bool {
+    match schema.get("type") {
+        Some(Value::String(t)) => t == ty,
+        Some(Value::Array(types)) => types.iter().any(|t| t == ty),
+        _ => false,
+    }
+}
+
+fn invalid(reason: String) -> AirbnbError {
+    AirbnbError::InvalidParams { reason }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+
+    fn tool(schema: Value) -> Tool {
+        let Value::Object(schema) = schema else {
+            panic!("schema must be an object");
+        };
+        Tool::new("airbnb_test", "test tool", Arc::new(schema))
+    }
+
+    #[test]
+    fn pairs_are_typed_after_the_schema() {
+        let tool = tool(serde_json::json!({
+            "type": "object",
+            "properties": {
+                "id": { "type": "string" },
+                "months": { "type": ["integer", "null"] },
+                "ids": { "type": ["array", "null"], "items": { "type": "string" } }
+            }
+        }));
+        let args = arguments_from_pairs(
+            &tool,
+            &["id=42".into(), "months=6".into(), "ids=1, 2,3".into()],
+        )
+        .unwrap();
+        assert_eq!(args["id"], "42");
+        assert_eq!(args["months"], 6);
+        assert_eq!(args["ids"], serde_json::json!(["1", "2", "3"]));
+    }
+
+    #[test]
+    fn unknown_or_malformed_pairs_are_rejected() {
+        let tool = tool(serde_json::json!({
+            "type": "object",
+            "properties": { "id": { "type": "string" } }
+        }));
+        let err = arguments_from_pairs(&tool, &["name=x".into()]).unwrap_err();
+        assert!(err.to_string().contains("expected one of: id"));
+        assert!(arguments_from_pairs(&tool, &["id".into()]).is_err());
+    }
+}
@@ -0,0 +1,225 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::future::Future;
+use std::sync::{Arc, RwLock};
+
+use rmcp::{
+    Peer, RoleServer,
+    model::{LoggingLevel, LoggingMessageNotificationParam},
+};
+use serde_json::{Map, Value};
+use tracing::field::{Field, Visit};
+use tracing::{Event, Level, Subscriber};
+use tracing_subscriber::layer::{Context, Layer};
+
+use crate::mcp::subscriptions::SessionId;
+
+/// Only this crate's events are forwarded. Besides keeping dependency noise out of the
+/// client, it prevents a feedback loop: rmcp logs every notification it sends.
+const FORWARDED_TARGET: &str = "mcp_airbnb";
+
+/// Level forwarded to a session until it sends `logging/setLevel`.
+const DEFAULT_LEVEL: LoggingLevel = LoggingLevel::Warning;
+
+tokio::task_local! {
+    static LOG_SESSION: SessionId;
+}
+
+/// Run `fut` with the events it logs attributed to `session`. The server wraps each
+/// request in it; events logged outside any session are never forwarded.
+pub async fn in_session<F: Future>(session: SessionId, fut: F) -> F::Output {
+    LOG_SESSION.scope(session, fut).await
+}
+
+/// Forwards `tracing` events to connected MCP clients as `notifications/message`.
+///
+/// Shared by every session of a server: each session registers its peer on
+/// initialization and can change its own minimum level with `logging/setLevel`.
+/// An event goes only to the session whose request logged it (see [`in_session`]),
+/// so one client never sees another's listing IDs, locations or token names.
+/// [`LogForwarder::layer`] is installed next to the stderr subscriber in `main`.
+#[derive(Clone, Default)]
+pub struct LogForwarder {
+    sessions: Arc<RwLock<HashMap<SessionId, LogSession>>>,
+}
+
+struct LogSession {
+    peer: Peer<RoleServer>,
+    level: LoggingLevel,
+}
+
+impl LogForwarder {
+    /// The `tracing_subscriber` layer that feeds this forwarder.
+    pub fn layer(&self) -> McpLogLayer {
+        McpLogLayer {
+            forwarder: self.clone(),
+        }
+    }
+
+    /// Start forwarding to `session` at the default level (`warning`).
+    pub fn register(&self, session: SessionId, peer: Peer<RoleServer>) {
+        if let Ok(mut sessions) = self.sessions.write() {
+            sessions.retain(|_, s| !s.peer.is_transport_closed());
+            sessions.entry(session).or_insert(LogSession {
+                peer,
+                level: DEFAULT_LEVEL,
+            });
+        }
+    }
+
+    /// Handle `logging/setLevel` from `session`.
+    pub fn set_level(&self, session: SessionId, peer: Peer<RoleServer>, level: LoggingLevel) {
+        if let Ok(mut sessions) = self.sessions.write() {
+            sessions.insert(session, LogSession { peer, level });
+        }
+    }
+
+    /// The peer of `session`, if it wants messages of `level`.
+    fn recipient(&self, session: SessionId, level: LoggingLevel) -> Option<Peer<RoleServer>> {
+        let sessions = self.sessions.read().ok()?;
+        sessions
+            .get(&session)
+            .filter(|s| severity(level) >= severity(s.level) && !s.peer.is_transport_closed())
+            .map(|s| s.peer.clone())
+    }
+}
+
+impl fmt::Debug for LogForwarder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LogForwarder").finish()
+    }
+}
+
+/// `tracing_subscriber` layer created by [`LogForwarder::layer`].
+pub struct McpLogLayer {
+    forwarder: LogForwarder,
+}
+
+impl<S: Subscriber> Layer<S> for McpLogLayer {
+    fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+        let metadata = event.metadata();
+        if !metadata.target().starts_with(FORWARDED_TARGET) {
+            return;
+        }
+        let Ok(session) = LOG_SESSION.try_with(|session| *session) else {
+            return;
+        };
+        let level = logging_level(*metadata.level());
+        let Some(peer) = self.forwarder.recipient(session, level) else {
+            return;
+        };
+        let Ok(runtime) = tokio::runtime::Handle::try_current() else {
+            return;
+        };
+
+        let mut fields = JsonFields::default();
+        event.record(&mut fields);
+        let param = LoggingMessageNotificationParam {
+            level,
+            logger: Some(metadata.target().to_string()),
+            data: Value::Object(fields.0),
+        };
+        // Sending is async and may be slow; never block the code that logged.
+        // Failures are dropped silently: logging them would re-enter this layer.
+        runtime.spawn(async move {
+            let _ = peer.notify_logging_message(param).await;
+        });
+    }
+}
+
+/// Collects an event's fields (including `message`) into a JSON object.
+#[derive(Default)]
+struct JsonFields(Map<String, Value>);
+
+impl Visit for JsonFields {
+    fn record_f64(&mut self, field: &Field, value: f64) {
+        self.0.insert(field.name().into(), value.into());
+    }
+
+    fn record_i64(&mut self, field: &Field, value: i64) {
+        self.0.insert(field.name().into(), value.into());
+    }
+
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        self.0.insert(field.name().into(), value.into());
+    }
+
+    fn record_bool(&mut self, field: &Field, value: bool) {
+        self.0.insert(field.name().into(), value.into());
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.0.insert(field.name().into(), value.into());
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        self.0
+            .insert(field.name().into(), format!("{value:?}").into());
+    }
+}
+
+fn logging_level(level: Level) -> LoggingLevel {
+    match level {
+        Level::ERROR => LoggingLevel::Error,
+        Level::WARN => LoggingLevel::Warning,
+        Level::INFO => LoggingLevel::Info,
+        Level::DEBUG | Level::TRACE => LoggingLevel::Debug,
+    }
+}
+
+fn severity(level: LoggingLevel) -> u8 {
+    match level {
+        LoggingLevel::Debug => 0,
+        LoggingLevel::Info => 1,
+        LoggingLevel::Notice => 2,
+        LoggingLevel::Warning => 3,
+        LoggingLevel::Error => 4,
+        LoggingLevel::Critical => 5,
+        LoggingLevel::Alert => 6,
+        LoggingLevel::Emergency => 7,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tracing_subscriber::layer::SubscriberExt;
+
+    #[test]
+    fn tracing_levels_map_to_mcp_levels() {
+        assert_eq!(logging_level(Level::WARN), LoggingLevel::Warning);
+        assert_eq!(logging_level(Level::TRACE), LoggingLevel::Debug);
+        assert!(severity(LoggingLevel::Error) > severity(LoggingLevel::Warning));
+        assert!(severity(LoggingLevel::Debug) < severity(LoggingLevel::Info));
+    }
+
+    #[test]
+    fn fields_are_collected_as_json() {
+        #[derive(Default, Clone)]
+        struct Capture(Arc<RwLock<Option<Value>>>);
+
+        impl<S: Subscriber> Layer<S> for Capture {
+            fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+                let mut fields = JsonFields::default();
+                event.record(&mut fields);
+                *self.0.write().unwrap() = Some(Value::Object(fields.0));
+            }
+        }
+
+        let capture = Capture::default();
+        let subscriber = tracing_subscriber::registry().with(capture.clone());
+        tracing::subscriber::with_default(subscriber, || {
+            tracing::warn!(id = "42", retries = 2u64, "GraphQL failed");
+        });
+        let data = capture.0.read().unwrap().clone().unwrap();
+        assert_eq!(data["message"], "GraphQL failed");
+        assert_eq!(data["id"], "42");
+        assert_eq!(data["retries"], 2);
+    }
+
+    #[test]
+    fn without_sessions_there_are_no_recipients() {
+        let forwarder = LogForwarder::default();
+        assert!(forwarder.recipient(0, LoggingLevel::Emergency).is_none());
+    }
+}
@@ -1 +1,11 @@
+pub mod auth;
+pub mod elicitation;
+pub mod http;
+pub mod local;
+pub mod logging;
+pub mod progress;
+pub mod prompts;
+pub mod resource_uri;
+pub mod sampling;
 pub mod server;
+pub mod subscriptions;
@@ -0,0 +1,97 @@
+use std::future::Future;
+
+use rmcp::{
+    ErrorData as McpError, Peer, RoleServer,
+    handler::server::common::{AsRequestContext, FromContextPart},
+    model::{ProgressNotificationParam, ProgressToken},
+};
+use tokio_util::sync::CancellationToken;
+
+/// Progress reporting and cancellation for one long-running tool call.
+///
+/// Extracted from the request like `Parameters`: it sends `notifications/progress` when
+/// the client attached a `progressToken` to the call, and [`ToolProgress::run`] stops
+/// waiting on a fetch as soon as the client sends `notifications/cancelled`.
+#[derive(Clone)]
+pub struct ToolProgress {
+    reporter: Option<(Peer<RoleServer>, ProgressToken)>,
+    ct: CancellationToken,
+}
+
+impl ToolProgress {
+    /// Progress that is reported nowhere and stops when `ct` is cancelled.
+    pub fn detached(ct: CancellationToken) -> Self {
+        Self { reporter: None, ct }
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.ct.is_cancelled()
+    }
+
+    /// Await `fetch`, or return `None` if the call is cancelled first.
+    pub async fn run<F: Future>(&self, fetch: F) -> Option<F::Output> {
+        tokio::select! {
+            biased;
+            () = self.ct.cancelled() => None,
+            output = fetch => Some(output),
+        }
+    }
+
+    /// Report that `done` of `total` steps have finished.
+    pub async fn report(&self, done: u32, total: u32, message: impl Into<String>) {
+        let Some((ref peer, ref token)) = self.reporter else {
+            return;
+        };
+        let param = ProgressNotificationParam {
+            progress_token: token.clone(),
+            progress: f64::from(done),
+            total: Some(f64::from(total)),
+            message: Some(message.into()),
+        };
+        if let Err(e) = peer.notify_progress(param).await {
+            tracing::debug!(error = %e, "Failed to send progress notification");
+        }
+    }
+}
+
+impl<C: AsRequestContext> FromContextPart<C> for ToolProgress {
+    fn from_context_part(context: &mut C) -> Result<Self, McpError> {
+        let context = context.as_request_context();
+        Ok(Self {
+            reporter: context
+                .meta
+                .get_progress_token()
+                .map(|token| (context.peer.clone(), token)),
+            ct: context.ct.clone(),
+        })
+    }
+}
+
+/// Note prepended to a tool's output when it was cancelled before finishing.
+pub fn partial_note(done: u32, total: u32, unit: &str) -> String {
+    format!(
+        "⚠️ Cancelled after {done} of {total} {unit}; the results below are partial and were not stored as a resource.\n\n"
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn run_completes_when_not_cancelled() {
+        let progress = ToolProgress::detached(CancellationToken::new());
+        assert_eq!(progress.run(async { 7 }).await, Some(7));
+        progress.report(1, 2, "ignored without a reporter").await;
+    }
+
+    #[tokio::test]
+    async fn run_stops_pending_fetch_on_cancel() {
+        let ct = CancellationToken::new();
+        let progress = ToolProgress::detached(ct.clone());
+        ct.cancel();
+        let out = progress.run(std::future::pending::<()>()).await;
+        assert!(out.is_none());
+        assert!(progress.is_cancelled());
+    }
+}
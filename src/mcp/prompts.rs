@@ -0,0 +1,391 @@
+use std::fmt::Write as _;
+
+use rmcp::{
+    ErrorData as McpError,
+    handler::server::wrapper::Parameters,
+    model::{GetPromptResult, PromptMessage, PromptMessageRole},
+    prompt, prompt_router, schemars,
+};
+
+use crate::mcp::server::AirbnbMcpServer;
+
+// ---------- Prompt params ----------
+//
+// MCP prompt arguments are always strings, so numeric inputs are parsed leniently.
+
+#[derive(Debug, serde::Deserialize, schemars::JsonSchema)]
+pub struct ListingAuditPromptParams {
+    /// Airbnb listing ID to audit
+    pub id: String,
+}
+
+#[derive(Debug, serde::Deserialize, schemars::JsonSchema)]
+pub struct InvestmentAnalysisPromptParams {
+    /// Market to evaluate (e.g. "Lisbon, Portugal")
+    pub location: String,
+    /// Total amount available to invest in the property, in local currency (e.g. "350000")
+    pub budget: String,
+}
+
+#[derive(Debug, serde::Deserialize, schemars::JsonSchema)]
+pub struct TripShortlistPromptParams {
+    /// Destination (e.g. "Kyoto, Japan")
+    pub location: String,
+    /// Stay dates as `YYYY-MM-DD/YYYY-MM-DD` (check-in/check-out)
+    pub dates: String,
+    /// Number of adult guests
+    pub guests: String,
+}
+
+// ---------- Workflow rendering ----------
+
+/// One tool call in a prompt workflow.
+struct Step {
+    tool: &'static str,
+    args: serde_json::Value,
+    purpose: &'static str,
+}
+
+/// Expand a workflow into the message sequence returned by `prompts/get`:
+/// the user's goal, the assistant's tool plan, and the user's go-ahead with the report format.
+fn workflow(description: String, goal: String, steps: &[Step], report: &str) -> GetPromptResult {
+    let mut plan = String::from("I'll chain these tools in order:\n");
+    for (i, step) in steps.iter().enumerate() {
+        let _ = writeln!(
+            plan,
+            "{}. `{}` with `{}` — {}",
+            i + 1,
+            step.tool,
+            step.args,
+            step.purpose
+        );
+    }
+
+    GetPromptResult {
+        description: Some(description),
+        messages: vec![
+            PromptMessage::new_text(PromptMessageRole::User, goal),
+            PromptMessage::new_text(PromptMessageRole::Assistant, plan),
+            PromptMessage::new_text(
+                PromptMessageRole::User,
+                format!(
+                    "Go ahead: call the tools in that order, using each result to inform the next. \
+                     If a step fails, say so and continue with the rest.\n\n{report}"
+                ),
+            ),
+        ],
+    }
+}
+
+/// Split `YYYY-MM-DD/YYYY-MM-DD` (also accepting ` to `, `..` or `,`) into check-in and check-out.
+fn parse_dates(dates: &str) -> Option<(String, String)> {
+    let dates = dates.trim();
+    let (checkin, checkout) = ["/", " to ", "..", ","]
+        .iter()
+        .find_map(|sep| dates.split_once(sep))?;
+    let checkin = checkin.trim();
+    let checkout = checkout.trim();
+    (!checkin.is_empty() && !checkout.is_empty())
+        .then(|| (checkin.to_string(), checkout.to_string()))
+}
+
+/// Parse a human-entered amount such as `"350,000"` or `"$350000"`.
+fn parse_amount(amount: &str) -> Option<f64> {
+    let digits: String = amount
+        .chars()
+        .filter(|c| c.is_ascii_digit() || *c == '.')
+        .collect();
+    digits.parse().ok().filter(|v: &f64| *v > 0.0)
+}
+
+#[prompt_router(vis = "pub(crate)")]
+impl AirbnbMcpServer {
+    /// Full quality audit of one listing.
+    #[prompt(
+        name = "listing_audit",
+        description = "Audit an Airbnb listing: quality score, amenity gaps vs the neighborhood and guest sentiment, merged into a prioritized improvement plan."
+    )]
+    async fn listing_audit(
+        &self,
+        Parameters(params): Parameters<ListingAuditPromptParams>,
+    ) -> Result<GetPromptResult, McpError> {
+        let id = params.id.trim();
+        if id.is_empty() {
+            return Err(McpError::invalid_params("`id` must not be empty", None));
+        }
+        let args = serde_json::json!({ "id": id });
+        Ok(workflow(
+            format!("Listing audit for listing {id}"),
+            format!(
+                "Audit Airbnb listing {id}. I want to know what holds it back and what to fix first."
+            ),
+            &[
+                Step {
+                    tool: "airbnb_listing_score",
+                    args: args.clone(),
+                    purpose: "overall 0-100 score and weakest categories",
+                },
+                Step {
+                    tool: "airbnb_amenity_analysis",
+                    args: args.clone(),
+                    purpose: "popular amenities the neighborhood offers that this listing lacks",
+                },
+                Step {
+                    tool: "airbnb_review_sentiment",
+                    args,
+                    purpose: "recurring guest complaints and praise",
+                },
+            ],
+            "Then write the audit with these sections: **Score** (overall and per category), \
+             **Strengths**, **Issues** (each backed by a number or a review theme) and a \
+             **Top 5 fixes** list ordered by expected impact.",
+        ))
+    }
+
+    /// Evaluate a market for buying a short-term rental.
+    #[prompt(
+        name = "investment_analysis",
+        description = "Evaluate a market for a short-term rental investment within a budget: market prices, revenue potential and payback period."
+    )]
+    async fn investment_analysis(
+        &self,
+        Parameters(params): Parameters<InvestmentAnalysisPromptParams>,
+    ) -> Result<GetPromptResult, McpError> {
+        let location = params.location.trim();
+        if location.is_empty() {
+            return Err(McpError::invalid_params(
+                "`location` must not be empty",
+                None,
+            ));
+        }
+        let budget = match parse_amount(&params.budget) {
+            Some(amount) => format!("{amount:.0}"),
+            None => params.budget.trim().to_string(),
+        };
+        let args = serde_json::json!({ "location": location });
+        Ok(workflow(
+            format!("Investment analysis for {location} with a budget of {budget}"),
+            format!(
+                "I'm considering buying a property in {location} to rent on Airbnb. \
+                 My total budget is {budget} (local currency). Is this market worth it?"
+            ),
+            &[
+                Step {
+                    tool: "airbnb_neighborhood_stats",
+                    args: args.clone(),
+                    purpose: "price levels, ratings and property mix",
+                },
+                Step {
+                    tool: "airbnb_compare_listings",
+                    args: serde_json::json!({ "location": location, "max_listings": 50 }),
+                    purpose: "spread of nightly rates and what top performers look like",
+                },
+                Step {
+                    tool: "airbnb_revenue_estimate",
+                    args,
+                    purpose: "ADR, occupancy and projected annual revenue",
+                },
+            ],
+            &format!(
+                "Then write the analysis with: **Market snapshot**, **Revenue outlook** \
+                 (annual revenue range), **Payback** (years to recover {budget}, before \
+                 costs), **Risks** (seasonality, competition) and a clear **Verdict**."
+            ),
+        ))
+    }
+
+    /// Build a shortlist of places to stay for a trip.
+    #[prompt(
+        name = "trip_shortlist",
+        description = "Shortlist Airbnb stays for a trip: search the destination for the dates and party size, compare the best options and check their reviews."
+    )]
+    async fn trip_shortlist(
+        &self,
+        Parameters(params): Parameters<TripShortlistPromptParams>,
+    ) -> Result<GetPromptResult, McpError> {
+        let location = params.location.trim();
+        if location.is_empty() {
+            return Err(McpError::invalid_params(
+                "`location` must not be empty",
+                None,
+            ));
+        }
+        let Some((checkin, checkout)) = parse_dates(&params.dates) else {
+            return Err(McpError::invalid_params(
+                format!(
+                    "`dates` must look like YYYY-MM-DD/YYYY-MM-DD, got '{}'",
+                    params.dates
+                ),
+                None,
+            ));
+        };
+        let guests: u32 = params.guests.trim().parse().map_err(|_| {
+            McpError::invalid_params(
+                format!("`guests` must be a number, got '{}'", params.guests),
+                None,
+            )
+        })?;
+
+        Ok(workflow(
+            format!("Trip shortlist for {location}, {checkin} to {checkout}, {guests} guests"),
+            format!(
+                "Find me the best places to stay in {location} from {checkin} to {checkout} \
+                 for {guests} guest(s)."
+            ),
+            &[
+                Step {
+                    tool: "airbnb_search",
+                    args: serde_json::json!({
+                        "location": location,
+                        "checkin": checkin,
+                        "checkout": checkout,
+                        "adults": guests,
+                    }),
+                    purpose: "available listings for these dates",
+                },
+                Step {
+                    tool: "airbnb_compare_listings",
+                    args: serde_json::json!({ "ids": ["<up to 5 promising IDs from the search>"] }),
+                    purpose: "price and rating percentiles of the candidates",
+                },
+                Step {
+                    tool: "airbnb_review_sentiment",
+                    args: serde_json::json!({ "id": "<each of the top 3 candidates>" }),
+                    purpose: "red flags in recent guest feedback",
+                },
+            ],
+            "Then present a shortlist of 3 stays as a table (name, price per night, rating, \
+             link) followed by one paragraph per stay on why it fits and any caveats from reviews.",
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::MockAirbnbClient;
+    use rmcp::model::PromptMessageContent;
+    use std::sync::Arc;
+
+    fn make_server() -> AirbnbMcpServer {
+        AirbnbMcpServer::new(Arc::new(MockAirbnbClient::new()))
+    }
+
+    fn message_text(message: &PromptMessage) -> &str {
+        match &message.content {
+            PromptMessageContent::Text { text } => text,
+            _ => panic!("expected text message"),
+        }
+    }
+
+    #[test]
+    fn router_lists_all_prompts_with_arguments() {
+        let prompts = AirbnbMcpServer::prompt_router().list_all();
+        let mut names: Vec<_> = prompts.iter().map(|p| p.name.as_str()).collect();
+        names.sort_unstable();
+        assert_eq!(
+            names,
+            ["investment_analysis", "listing_audit", "trip_shortlist"]
+        );
+
+        let trip = prompts.iter().find(|p| p.name == "trip_shortlist").unwrap();
+        let args: Vec<_> = trip
+            .arguments
+            .as_ref()
+            .unwrap()
+            .iter()
+            .map(|a| (a.name.as_str(), a.required))
+            .collect();
+        assert!(args.contains(&("location", Some(true))));
+        assert!(args.contains(&("dates", Some(true))));
+        assert!(args.contains(&("guests", Some(true))));
+    }
+
+    #[tokio::test]
+    async fn listing_audit_chains_score_amenities_and_sentiment() {
+        let result = make_server()
+            .listing_audit(Parameters(ListingAuditPromptParams { id: "42".into() }))
+            .await
+            .unwrap();
+
+        assert_eq!(result.messages.len(), 3);
+        assert_eq!(result.messages[1].role, PromptMessageRole::Assistant);
+        let plan = message_text(&result.messages[1]);
+        let score = plan.find("airbnb_listing_score").unwrap();
+        let amenities = plan.find("airbnb_amenity_analysis").unwrap();
+        let sentiment = plan.find("airbnb_review_sentiment").unwrap();
+        assert!(score < amenities && amenities < sentiment);
+        assert!(plan.contains(r#"{"id":"42"}"#));
+    }
+
+    #[tokio::test]
+    async fn investment_analysis_includes_budget_and_location() {
+        let result = make_server()
+            .investment_analysis(Parameters(InvestmentAnalysisPromptParams {
+                location: "Lisbon".into(),
+                budget: "$350,000".into(),
+            }))
+            .await
+            .unwrap();
+
+        assert!(message_text(&result.messages[0]).contains("350000"));
+        let plan = message_text(&result.messages[1]);
+        assert!(plan.contains("airbnb_revenue_estimate"));
+        assert!(plan.contains(r#""location":"Lisbon""#));
+    }
+
+    #[tokio::test]
+    async fn trip_shortlist_passes_dates_and_guests_to_search() {
+        let result = make_server()
+            .trip_shortlist(Parameters(TripShortlistPromptParams {
+                location: "Kyoto".into(),
+                dates: "2025-04-01/2025-04-07".into(),
+                guests: "3".into(),
+            }))
+            .await
+            .unwrap();
+
+        let plan = message_text(&result.messages[1]);
+        assert!(plan.contains(r#""checkin":"2025-04-01""#));
+        assert!(plan.contains(r#""checkout":"2025-04-07""#));
+        assert!(plan.contains(r#""adults":3"#));
+    }
+
+    #[tokio::test]
+    async fn trip_shortlist_rejects_malformed_arguments() {
+        let server = make_server();
+        let bad_dates = server
+            .trip_shortlist(Parameters(TripShortlistPromptParams {
+                location: "Kyoto".into(),
+                dates: "next week".into(),
+                guests: "2".into(),
+            }))
+            .await;
+        assert!(bad_dates.is_err());
+
+        let bad_guests = server
+            .trip_shortlist(Parameters(TripShortlistPromptParams {
+                location: "Kyoto".into(),
+                dates: "2025-04-01/2025-04-07".into(),
+                guests: "a few".into(),
+            }))
+            .await;
+        assert!(bad_guests.is_err());
+    }
+
+    #[test]
+    fn parse_dates_accepts_common_separators() {
+        let expected = Some(("2025-04-01".to_string(), "2025-04-07".to_string()));
+        assert_eq!(parse_dates("2025-04-01/2025-04-07"), expected);
+        assert_eq!(parse_dates("2025-04-01 to 2025-04-07"), expected);
+        assert_eq!(parse_dates("2025-04-01..2025-04-07"), expected);
+        assert_eq!(parse_dates("2025-04-01"), None);
+    }
+
+    #[test]
+    fn parse_amount_strips_formatting() {
+        assert_eq!(parse_amount("350,000"), Some(350_000.0));
+        assert_eq!(parse_amount("€ 1200.50"), Some(1200.5));
+        assert_eq!(parse_amount("lots"), None);
+    }
+}
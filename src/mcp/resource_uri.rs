@@ -0,0 +1,207 @@
+use std::fmt;
+
+use percent_encoding::{AsciiSet, NON_ALPHANUMERIC, percent_decode_str, utf8_percent_encode};
+
+const SCHEME: &str = "airbnb://";
+
+/// Characters kept as-is in a URI segment: RFC 3986 "unreserved" (`A-Z a-z 0-9 - . _ ~`).
+/// Everything else — spaces, commas, slashes, non-ASCII — is percent-encoded.
+const SEGMENT: &AsciiSet = &NON_ALPHANUMERIC
+    .remove(b'-')
+    .remove(b'.')
+    .remove(b'_')
+    .remove(b'~');
+
+/// A parsed `airbnb://` resource URI.
+///
+/// `Display` produces the canonical form with every segment percent-encoded, and
+/// [`ResourceUri::parse`] accepts any valid encoding of it, so
+/// `airbnb://search/Paris%2C%20France` and the canonical URI refer to the same resource.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ResourceUri {
+    Listing(String),
+    Calendar(String),
+    Reviews(String),
+    Host(String),
+    Occupancy(String),
+    Search(String),
+    Neighborhood(String),
+    /// Result of an analytical tool, e.g. `airbnb://analysis/price-trends/42`.
+    Analysis {
+        kind: String,
+        key: String,
+    },
+}
+
+impl ResourceUri {
+    pub fn analysis(kind: &str, key: impl Into<String>) -> Self {
+        Self::Analysis {
+            kind: kind.to_string(),
+            key: key.into(),
+        }
+    }
+
+    pub fn parse(uri: &str) -> Option<Self> {
+        let path = uri.strip_prefix(SCHEME)?;
+        let segments: Vec<String> = path
+            .split('/')
+            .map(|s| percent_decode_str(s).decode_utf8().ok().map(String::from))
+            .collect::<Option<_>>()?;
+        if segments.iter().any(String::is_empty) {
+            return None;
+        }
+        let parsed = match segments.as_slice() {
+            [root, id] if root == "listing" => Self::Listing(id.clone()),
+            [root, id, sub] if root == "listing" => match sub.as_str() {
+                "calendar" => Self::Calendar(id.clone()),
+                "reviews" => Self::Reviews(id.clone()),
+                "host" => Self::Host(id.clone()),
+                "occupancy" => Self::Occupancy(id.clone()),
+                _ => return None,
+            },
+            [root, location] if root == "search" => Self::Search(location.clone()),
+            [root, location] if root == "neighborhood" => Self::Neighborhood(location.clone()),
+            [root, kind, key] if root == "analysis" => Self::analysis(kind, key.clone()),
+            _ => return None,
+        };
+        Some(parsed)
+    }
+
+    /// The tool that can fetch this resource live when it is not stored yet.
+    ///
+    /// `None` for analysis results, which depend on tool parameters the URI does not carry.
+    pub fn source_tool(&self) -> Option<&'static str> {
+        match self {
+            Self::Listing(_) => Some("airbnb_listing_details"),
+            Self::Calendar(_) => Some("airbnb_price_calendar"),
+            Self::Reviews(_) => Some("airbnb_reviews"),
+            Self::Host(_) => Some("airbnb_host_profile"),
+            Self::Occupancy(_) => Some("airbnb_occupancy_estimate"),
+            Self::Search(_) => Some("airbnb_search"),
+            Self::Neighborhood(_) => Some("airbnb_neighborhood_stats"),
+            Self::Analysis { .. } => None,
+        }
+    }
+
+    /// One-line summary shown as the resource description in `resources/list`.
+    pub fn description(&self) -> String {
+        match self {
+            Self::Listing(id) => format!("Details of listing {id}"),
+            Self::Calendar(id) => format!("Price and availability calendar of listing {id}"),
+            Self::Reviews(id) => format!("Guest reviews of listing {id}"),
+            Self::Host(id) => format!("Host profile for listing {id}"),
+            Self::Occupancy(id) => format!("Occupancy estimate for listing {id}"),
+            Self::Search(location) => format!("Search results for {location}"),
+            Self::Neighborhood(location) => format!("Neighborhood statistics for {location}"),
+            Self::Analysis { kind, key } => format!("Result of the {kind} analysis for {key}"),
+        }
+    }
+}
+
+fn encode(segment: &str) -> impl fmt::Display + '_ {
+    utf8_percent_encode(segment, SEGMENT)
+}
+
+impl fmt::Display for ResourceUri {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Listing(id) => write!(f, "{SCHEME}listing/{}", encode(id)),
+            Self::Calendar(id) => write!(f, "{SCHEME}listing/{}/calendar", encode(id)),
+            Self::Reviews(id) => write!(f, "{SCHEME}listing/{}/reviews", encode(id)),
+            Self::Host(id) => write!(f, "{SCHEME}listing/{}/host", encode(id)),
+            Self::Occupancy(id) => write!(f, "{SCHEME}listing/{}/occupancy", encode(id)),
+            Self::Search(location) => write!(f, "{SCHEME}search/{}", encode(location)),
+            Self::Neighborhood(location) => {
+                write!(f, "{SCHEME}neighborhood/{}", encode(location))
+            }
+            Self::Analysis { kind, key } => {
+                write!(f, "{SCHEME}analysis/{}/{}", encode(kind), encode(key))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_listing_templates() {
+        assert_eq!(
+            ResourceUri::parse("airbnb://listing/42"),
+            Some(ResourceUri::Listing("42".into()))
+        );
+        assert_eq!(
+            ResourceUri::parse("airbnb://listing/42/calendar"),
+            Some(ResourceUri::Calendar("42".into()))
+        );
+        assert_eq!(
+            ResourceUri::parse("airbnb://listing/42/reviews"),
+            Some(ResourceUri::Reviews("42".into()))
+        );
+        assert_eq!(
+            ResourceUri::parse("airbnb://listing/42/host"),
+            Some(ResourceUri::Host("42".into()))
+        );
+        assert_eq!(
+            ResourceUri::parse("airbnb://listing/42/occupancy"),
+            Some(ResourceUri::Occupancy("42".into()))
+        );
+    }
+
+    #[test]
+    fn rejects_unknown_or_malformed_uris() {
+        assert_eq!(ResourceUri::parse("airbnb://listing/42/photos"), None);
+        assert_eq!(ResourceUri::parse("airbnb://listing/"), None);
+        assert_eq!(ResourceUri::parse("airbnb://search"), None);
+        assert_eq!(ResourceUri::parse("https://listing/42"), None);
+        assert_eq!(ResourceUri::parse("airbnb://search/%FF"), None);
+    }
+
+    #[test]
+    fn location_round_trips_through_percent_encoding() {
+        let uri = ResourceUri::Search("Porto-Vecchio, Corsica".into());
+        assert_eq!(
+            uri.to_string(),
+            "airbnb://search/Porto-Vecchio%2C%20Corsica"
+        );
+        assert_eq!(ResourceUri::parse(&uri.to_string()), Some(uri));
+    }
+
+    #[test]
+    fn encodes_slashes_and_unicode() {
+        let uri = ResourceUri::Neighborhood("São Paulo / Centro".into());
+        let text = uri.to_string();
+        assert_eq!(text.matches('/').count(), 3);
+        assert_eq!(ResourceUri::parse(&text), Some(uri));
+    }
+
+    #[test]
+    fn accepts_non_canonical_encoding() {
+        assert_eq!(
+            ResourceUri::parse("airbnb://search/New York"),
+            Some(ResourceUri::Search("New York".into()))
+        );
+        assert_eq!(
+            ResourceUri::parse("airbnb://search/new%2cyork"),
+            Some(ResourceUri::Search("new,york".into()))
+        );
+    }
+
+    #[test]
+    fn description_uses_decoded_segments() {
+        let uri = ResourceUri::parse("airbnb://search/Paris%2C%20France").unwrap();
+        assert_eq!(uri.description(), "Search results for Paris, France");
+    }
+
+    #[test]
+    fn analysis_has_no_source_tool() {
+        let uri = ResourceUri::parse("airbnb://analysis/price-trends/42").unwrap();
+        assert_eq!(uri, ResourceUri::analysis("price-trends", "42"));
+        assert_eq!(uri.source_tool(), None);
+        assert_eq!(
+            ResourceUri::Calendar("42".into()).source_tool(),
+            Some("airbnb_price_calendar")
+        );
+    }
+}
@@ -0,0 +1,120 @@
+use rmcp::{
+    ErrorData as McpError, Peer, RoleServer,
+    handler::server::common::{AsRequestContext, FromContextPart},
+    model::{
+        CreateMessageRequestParams, ModelPreferences, Role, SamplingMessage, SamplingMessageContent,
+    },
+};
+
+/// Access to the client's model through MCP sampling (`sampling/createMessage`).
+///
+/// Extracted from the request like `Parameters`. It is only usable when the client
+/// declared the `sampling` capability; tools check [`Sampler::is_available`] and fall back
+/// to a deterministic computation otherwise.
+#[derive(Clone)]
+pub struct Sampler {
+    peer: Option<Peer<RoleServer>>,
+}
+
+/// A model-written reply.
+#[derive(Debug, Clone)]
+pub struct Sampled {
+    pub model: String,
+    pub text: String,
+}
+
+impl Sampler {
+    /// A sampler for clients without the `sampling` capability.
+    pub fn unavailable() -> Self {
+        Self { peer: None }
+    }
+
+    pub fn is_available(&self) -> bool {
+        self.peer.is_some()
+    }
+
+    /// Ask the client's model to answer `prompt`, returning a reason on failure
+    /// (no sampling support, the user declined, or a non-text reply).
+    pub async fn complete(
+        &self,
+        system_prompt: &str,
+        prompt: String,
+        max_tokens: u32,
+    ) -> Result<Sampled, String> {
+        let Some(ref peer) = self.peer else {
+            return Err("the client does not support sampling".into());
+        };
+        let params = CreateMessageRequestParams {
+            meta: None,
+            task: None,
+            messages: vec![SamplingMessage::new(
+                Role::User,
+                SamplingMessageContent::text(prompt),
+            )],
+            model_preferences: Some(ModelPreferences {
+                hints: None,
+                cost_priority: None,
+                speed_priority: Some(0.3),
+                intelligence_priority: Some(0.7),
+            }),
+            system_prompt: Some(system_prompt.into()),
+            include_context: None,
+            temperature: Some(0.2),
+            max_tokens,
+            stop_sequences: None,
+            metadata: None,
+            tools: None,
+            tool_choice: None,
+        };
+        let result = peer
+            .create_message(params)
+            .await
+            .map_err(|e| format!("sampling failed: {e}"))?;
+        let text: String = result
+            .message
+            .content
+            .into_vec()
+            .into_iter()
+            .filter_map(|content| match content {
+                SamplingMessageContent::Text(text) => Some(text.text),
+                _ => None,
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        if text.trim().is_empty() {
+            return Err("the client's model returned no text".into());
+        }
+        Ok(Sampled {
+            model: result.model,
+            text,
+        })
+    }
+}
+
+impl<C: AsRequestContext> FromContextPart<C> for Sampler {
+    fn from_context_part(context: &mut C) -> Result<Self, McpError> {
+        let peer = &context.as_request_context().peer;
+        let supported = peer
+            .peer_info()
+            .is_some_and(|info| info.capabilities.sampling.is_some());
+        Ok(Self {
+            peer: supported.then(|| peer.clone()),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn unavailable_sampler_reports_why() {
+        let sampler = Sampler::unavailable();
+        assert!(!sampler.is_available());
+        let err = sampler
+            .complete("system", "prompt".into(), 100)
+            .await
+            .unwrap_err();
+        assert!(err.contains("does not support sampling"));
+    }
+}
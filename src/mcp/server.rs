@@ -1,62 +1,181 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 use std::fmt::Write as _;
+use std::num::NonZeroUsize;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 use tokio::sync::RwLock;
 
+use base64::Engine;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use chrono::{DateTime, Utc};
+use lru::LruCache;
+
 use rmcp::{
     ErrorData as McpError, RoleServer, ServerHandler,
-    handler::server::{router::tool::ToolRouter, wrapper::Parameters},
+    handler::server::{
+        router::{prompt::PromptRouter, tool::ToolRouter},
+        tool::ToolCallContext,
+        wrapper::Parameters,
+    },
     model::{
-        CallToolResult, Content, Implementation, ListResourceTemplatesResult, ListResourcesResult,
-        PaginatedRequestParams, ProtocolVersion, RawResource, RawResourceTemplate,
+        CallToolRequestParams, CallToolResult, CompleteRequestParams, CompleteResult,
+        CompletionInfo, Content, GetPromptRequestParams, GetPromptResult, Implementation,
+        JsonObject, ListPromptsResult, ListResourceTemplatesResult, ListResourcesResult,
+        ListToolsResult, PaginatedRequestParams, ProtocolVersion, RawResource, RawResourceTemplate,
         ReadResourceRequestParams, ReadResourceResult, Resource, ResourceContents,
-        ResourceTemplate, ServerCapabilities, ServerInfo,
+        ResourceTemplate, ServerCapabilities, ServerInfo, SetLevelRequestParams,
+        SubscribeRequestParams, Tool, UnsubscribeRequestParams,
     },
-    schemars,
-    service::RequestContext,
-    tool, tool_handler, tool_router,
+    prompt_handler, schemars,
+    service::{NotificationContext, RequestContext},
+    tool, tool_router,
 };
 
+use crate::config::types::{ResourceConfig, SessionMode, ToolLimits, ToolsConfig};
+use crate::config::validate::ConfigIssue;
 use crate::domain::analytics;
 use crate::domain::search_params::SearchParams;
+use crate::error::{AirbnbError, Result as AppResult};
+use crate::mcp::auth::{self, AuthIdentity};
+use crate::mcp::elicitation::Elicitor;
+use crate::mcp::logging::{self, LogForwarder};
+use crate::mcp::progress::{ToolProgress, partial_note};
+use crate::mcp::resource_uri::ResourceUri;
+use crate::mcp::sampling::Sampler;
+use crate::mcp::subscriptions::{ResourceChange, ResourceNotifier, SessionId};
 use crate::ports::airbnb_client::AirbnbClient;
+use crate::ports::cache::{
+    CacheRead, CacheStats, GRAPHQL_KEY_TAG, ListingCache, fetch_fresh, track_cache_reads,
+};
 
 // ---------- Resource Store ----------
 
 /// Thread-safe store of fetched Airbnb data exposed as MCP resources.
 /// Keys are URIs like `airbnb://listing/12345`, values are text content.
-#[derive(Clone, Default)]
+///
+/// Bounded by [`ResourceConfig`]: the least recently used entries are evicted beyond
+/// `max_entries`, and each entry expires after the TTL of its kind. Expired entries are
+/// treated as missing, so reading one again resolves it live.
+#[derive(Clone)]
 pub struct ResourceStore {
-    entries: Arc<RwLock<HashMap<String, ResourceEntry>>>,
+    entries: Arc<RwLock<LruCache<String, ResourceEntry>>>,
+    config: Arc<ResourceConfig>,
 }
 
 #[derive(Clone)]
 struct ResourceEntry {
     name: String,
     text: String,
+    expires_at: Option<Instant>,
+}
+
+impl ResourceEntry {
+    fn is_expired(&self, now: Instant) -> bool {
+        self.expires_at.is_some_and(|at| now >= at)
+    }
+}
+
+/// What `resources/list` shows about a stored resource.
+struct ResourceSummary {
+    uri: String,
+    name: String,
+    size: usize,
+}
+
+impl Default for ResourceStore {
+    fn default() -> Self {
+        Self::new(&ResourceConfig::default())
+    }
 }
 
 impl ResourceStore {
-    async fn insert(&self, uri: impl Into<String>, name: impl Into<String>, text: String) {
-        self.entries.write().await.insert(
-            uri.into(),
-            ResourceEntry {
-                name: name.into(),
-                text,
-            },
-        );
+    pub fn new(config: &ResourceConfig) -> Self {
+        let cap = NonZeroUsize::new(config.max_entries).unwrap_or_else(|| {
+            tracing::warn!("Resource max_entries was 0, defaulting to 1000");
+            NonZeroUsize::new(1000).unwrap()
+        });
+        Self {
+            entries: Arc::new(RwLock::new(LruCache::new(cap))),
+            config: Arc::new(config.clone()),
+        }
+    }
+
+    /// An empty store with the same limits.
+    fn empty_like(&self) -> Self {
+        Self::new(&self.config)
+    }
+
+    fn ttl(&self, uri: &str) -> Option<Duration> {
+        let secs = match ResourceUri::parse(uri) {
+            Some(
+                ResourceUri::Listing(_)
+                | ResourceUri::Reviews(_)
+                | ResourceUri::Host(_)
+                | ResourceUri::Occupancy(_),
+            ) => self.config.listing_ttl_secs,
+            Some(ResourceUri::Calendar(_)) => self.config.calendar_ttl_secs,
+            Some(ResourceUri::Search(_)) => self.config.search_ttl_secs,
+            Some(ResourceUri::Neighborhood(_)) => self.config.neighborhood_ttl_secs,
+            Some(ResourceUri::Analysis { .. }) | None => self.config.analysis_ttl_secs,
+        };
+        (secs > 0).then(|| Duration::from_secs(secs))
+    }
+
+    async fn insert(
+        &self,
+        uri: impl Into<String>,
+        name: impl Into<String>,
+        text: String,
+    ) -> ResourceChange {
+        let uri = uri.into();
+        let now = Instant::now();
+        let entry = ResourceEntry {
+            name: name.into(),
+            text,
+            expires_at: self.ttl(&uri).map(|ttl| now + ttl),
+        };
+        let displaced = self.entries.write().await.push(uri.clone(), entry);
+        match displaced {
+            // Replacing a live entry is an update; an expired one had already disappeared
+            Some((key, previous)) if key == uri && !previous.is_expired(now) => {
+                ResourceChange::Updated
+            }
+            Some((key, _)) if key != uri => {
+                tracing::debug!(evicted = %key, "Resource store full, evicted least recently used");
+                ResourceChange::Added
+            }
+            _ => ResourceChange::Added,
+        }
     }
 
     async fn get(&self, uri: &str) -> Option<ResourceEntry> {
-        self.entries.read().await.get(uri).cloned()
+        let mut entries = self.entries.write().await;
+        if entries.get(uri)?.is_expired(Instant::now()) {
+            entries.pop(uri);
+            return None;
+        }
+        entries.get(uri).cloned()
     }
 
-    async fn list(&self) -> Vec<(String, String)> {
-        self.entries
-            .read()
-            .await
+    /// Drop expired entries and summarize the rest, in no particular order.
+    async fn list(&self) -> Vec<ResourceSummary> {
+        let mut entries = self.entries.write().await;
+        let now = Instant::now();
+        let expired: Vec<String> = entries
             .iter()
-            .map(|(uri, entry)| (uri.clone(), entry.name.clone()))
+            .filter(|(_, entry)| entry.is_expired(now))
+            .map(|(uri, _)| uri.clone())
+            .collect();
+        for uri in expired {
+            entries.pop(&uri);
+        }
+        entries
+            .iter()
+            .map(|(uri, entry)| ResourceSummary {
+                uri: uri.clone(),
+                name: entry.name.clone(),
+                size: entry.text.len(),
+            })
             .collect()
     }
 }
@@ -67,6 +186,81 @@ impl std::fmt::Debug for ResourceStore {
     }
 }
 
+/// Resources visible to one MCP session: a shared layer plus an optional private layer.
+///
+/// Listing-scoped URIs (`airbnb://listing/...`) are public Airbnb data and always go to the
+/// shared layer. When a private layer is present (isolated sessions), everything else —
+/// searches, neighborhood stats and `airbnb://analysis/...` results — is written there,
+/// and reads check the private layer before falling through to the shared one.
+///
+/// Every write is reported to the [`ResourceNotifier`] so subscribed sessions hear about it.
+#[derive(Clone, Default)]
+struct SessionResources {
+    shared: ResourceStore,
+    private: Option<ResourceStore>,
+    notifier: ResourceNotifier,
+    session: SessionId,
+}
+
+impl SessionResources {
+    fn is_public(uri: &str) -> bool {
+        uri.starts_with("airbnb://listing/")
+    }
+
+    async fn insert(&self, uri: impl Into<String>, name: impl Into<String>, text: String) {
+        let uri = uri.into();
+        let (store, shared) = match self.private {
+            Some(ref private) if !Self::is_public(&uri) => (private, false),
+            _ => (&self.shared, true),
+        };
+        let change = store.insert(uri.clone(), name, text).await;
+        self.notifier
+            .notify(self.session, &uri, shared, change)
+            .await;
+    }
+
+    async fn get(&self, uri: &str) -> Option<ResourceEntry> {
+        if let Some(ref private) = self.private
+            && let Some(entry) = private.get(uri).await
+        {
+            return Some(entry);
+        }
+        self.shared.get(uri).await
+    }
+
+    /// Every visible resource sorted by URI, private entries shadowing shared ones.
+    async fn list(&self) -> Vec<ResourceSummary> {
+        let mut entries: BTreeMap<String, ResourceSummary> = BTreeMap::new();
+        let mut layers = vec![self.shared.list().await];
+        if let Some(ref private) = self.private {
+            layers.push(private.list().await);
+        }
+        for summary in layers.into_iter().flatten() {
+            entries.insert(summary.uri.clone(), summary);
+        }
+        entries.into_values().collect()
+    }
+
+    fn page_size(&self) -> usize {
+        self.shared.config.page_size.max(1)
+    }
+}
+
+/// `resources/list` cursors are the last URI of the previous page, base64url-encoded so
+/// clients treat them as opaque. Keying on the URI rather than an offset keeps pages
+/// stable while resources are added or evicted between calls.
+fn encode_cursor(last_uri: &str) -> String {
+    URL_SAFE_NO_PAD.encode(last_uri)
+}
+
+fn decode_cursor(cursor: &str) -> Result<String, McpError> {
+    URL_SAFE_NO_PAD
+        .decode(cursor)
+        .ok()
+        .and_then(|bytes| String::from_utf8(bytes).ok())
+        .ok_or_else(|| McpError::invalid_params(format!("Invalid cursor '{cursor}'"), None))
+}
+
 // ---------- Tool parameter types ----------
 
 #[derive(Debug, serde::Deserialize, schemars::JsonSchema)]
@@ -231,6 +425,16 @@ pub struct ReviewSentimentToolParams {
     pub max_pages: Option<u32>,
 }
 
+#[derive(Debug, serde::Deserialize, schemars::JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct ReviewSummaryToolParams {
+    /// Airbnb listing ID
+    pub id: String,
+    /// Maximum number of review pages to fetch (default: 3)
+    #[schemars(range(min = 1, max = 20))]
+    pub max_pages: Option<u32>,
+}
+
 #[derive(Debug, serde::Deserialize, schemars::JsonSchema)]
 #[serde(deny_unknown_fields)]
 pub struct CompetitivePositioningToolParams {
@@ -251,6 +455,187 @@ pub struct OptimalPricingToolParams {
     pub location: Option<String>,
 }
 
+// ---------- Cache administration ----------
+
+#[derive(Debug, serde::Deserialize, schemars::JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct CacheInvalidateToolParams {
+    /// Remove entries whose key starts with this, e.g. "detail:123:" or "calendar:".
+    /// Covers both adapters unless it starts with "gql:", which only matches GraphQL
+    /// entries. An empty string clears the whole cache.
+    pub prefix: String,
+}
+
+#[derive(Debug, serde::Serialize, schemars::JsonSchema)]
+pub struct CacheInvalidation {
+    pub prefix: String,
+    /// Number of entries removed.
+    pub removed: usize,
+}
+
+/// Tools that act on the cache every session shares. Hidden unless `tools.allow` or the
+/// caller's token names them, so clients of a shared server can't clear it for everyone
+/// or see what others fetch.
+const ADMIN_TOOLS: &[&str] = &["airbnb_cache_stats", "airbnb_cache_invalidate"];
+
+/// Name of the argument every data tool accepts to skip cached values.
+const FRESH_ARGUMENT: &str = "fresh";
+
+/// The canonical spelling of `uri`, so differently-encoded URIs match stored entries.
+fn canonical_uri(uri: &str) -> String {
+    ResourceUri::parse(uri).map_or_else(|| uri.to_string(), |parsed| parsed.to_string())
+}
+
+// ---------- Structured output ----------
+
+/// JSON schema of `T`, advertised as a tool's `outputSchema`.
+fn output_schema<T: schemars::JsonSchema + 'static>() -> Arc<JsonObject> {
+    rmcp::handler::server::tool::schema_for_output::<T>().unwrap_or_else(|e| {
+        panic!(
+            "invalid output schema for {}: {e}",
+            std::any::type_name::<T>()
+        )
+    })
+}
+
+/// Lower the advertised `maximum` of `property` in a tool's input schema to a configured limit.
+fn set_maximum(tool: &mut Tool, property: &str, maximum: u32) {
+    let schema = Arc::make_mut(&mut tool.input_schema);
+    if let Some(serde_json::Value::Object(property)) = schema
+        .get_mut("properties")
+        .and_then(|p| p.get_mut(property))
+    {
+        property.insert("maximum".into(), maximum.into());
+    }
+}
+
+/// Advertise the optional `fresh` flag, which `call_tool` takes out of the arguments
+/// before the tool parses them.
+fn add_fresh_option(tool: &mut Tool) {
+    let schema = Arc::make_mut(&mut tool.input_schema);
+    let properties = schema
+        .entry("properties")
+        .or_insert_with(|| serde_json::Value::Object(JsonObject::new()));
+    if let serde_json::Value::Object(properties) = properties {
+        properties.insert(
+            FRESH_ARGUMENT.into(),
+            serde_json::json!({
+                "type": "boolean",
+                "description": "Ignore cached Airbnb data and fetch it again (the new data is cached)",
+            }),
+        );
+    }
+}
+
+/// Successful tool result carrying both the markdown rendering and the typed data
+/// as `structuredContent`. Data that fails to serialize is an internal error, since a
+/// result without it would not match the tool's `outputSchema`.
+fn structured_success<T: serde::Serialize>(
+    text: String,
+    data: &T,
+) -> Result<CallToolResult, McpError> {
+    let structured = serde_json::to_value(data).map_err(|e| {
+        tracing::error!(error = %e, "Failed to serialize structured tool output");
+        McpError::internal_error(format!("failed to serialize tool output: {e}"), None)
+    })?;
+    let mut result = CallToolResult::success(vec![Content::text(text)]);
+    result.structured_content = Some(structured);
+    Ok(result)
+}
+
+/// `bytes` in the largest binary unit that keeps it at or above 1, e.g. `3.2 MiB`.
+#[allow(clippy::cast_precision_loss)]
+fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    format!("{value:.1} {}", UNITS[unit])
+}
+
+fn no_cache_error() -> CallToolResult {
+    CallToolResult::error(vec![Content::text(
+        "No cache is configured for this server.",
+    )])
+}
+
+/// Note appended to a tool's output when some of its data was served from the cache,
+/// saying when the oldest of it was fetched and which keys, if any, are being refreshed.
+fn cache_note(reads: &[CacheRead], now: DateTime<Utc>) -> Option<String> {
+    let oldest = reads.iter().map(|read| read.fetched_at).min()?;
+    let age = format_age((now - oldest).to_std().unwrap_or_default());
+    let stale = reads
+        .iter()
+        .filter(|read| read.stale)
+        .map(|read| format!("`{}`", read.key))
+        .collect::<BTreeSet<_>>();
+    if stale.is_empty() {
+        return Some(format!(
+            "ℹ️ Served from cache: fetched up to {age} ago (oldest at {} UTC).",
+            oldest.format("%Y-%m-%d %H:%M")
+        ));
+    }
+    let keys = stale.into_iter().collect::<Vec<_>>().join(", ");
+    Some(format!(
+        "ℹ️ Some data was served from cache and is up to {age} old ({keys}); a refresh is running in the background."
+    ))
+}
+
+/// `age` rounded down to its two largest units, e.g. `2h 5m` or `45s`.
+fn format_age(age: Duration) -> String {
+    let secs = age.as_secs();
+    let (days, hours, mins) = (secs / 86400, secs / 3600 % 24, secs / 60 % 60);
+    if days > 0 {
+        format!("{days}d {hours}h")
+    } else if hours > 0 {
+        format!("{hours}h {mins}m")
+    } else if mins > 0 {
+        format!("{mins}m")
+    } else {
+        format!("{secs}s")
+    }
+}
+
+// ---------- Completions ----------
+
+/// Completion values for `typed`: the candidates starting with it (ignoring case),
+/// sorted and capped at the MCP limit of 100.
+fn completion_info(candidates: BTreeSet<String>, typed: &str) -> CompletionInfo {
+    let typed = typed.to_lowercase();
+    let matches: Vec<String> = candidates
+        .into_iter()
+        .filter(|value| value.to_lowercase().starts_with(&typed))
+        .collect();
+    let total = u32::try_from(matches.len()).unwrap_or(u32::MAX);
+    let has_more = matches.len() > CompletionInfo::MAX_VALUES;
+    CompletionInfo {
+        values: matches
+            .into_iter()
+            .take(CompletionInfo::MAX_VALUES)
+            .collect(),
+        total: Some(total),
+        has_more: Some(has_more),
+    }
+}
+
+// ---------- Review summary ----------
+
+/// Number of reviews sent to the client's model; keeps the prompt bounded.
+const SUMMARY_MAX_REVIEWS: usize = 60;
+const SUMMARY_MAX_COMMENT_CHARS: usize = 500;
+const SUMMARY_MAX_TOKENS: u32 = 800;
+
+const SUMMARY_SYSTEM_PROMPT: &str = "You summarize Airbnb guest reviews for someone deciding whether to book. \
+Reply with a **Pros** and a **Cons** section of short bullet points. \
+End every bullet with the references of the reviews that support it, e.g. [R3][R7]. \
+Only state what the reviews say; do not invent details or cite reviews that are not listed.";
+
 // ---------- MCP Server ----------
 
 /// Lightweight cache of listing prices discovered from search results.
@@ -273,6 +658,10 @@ impl PriceCache {
     async fn get(&self, id: &str) -> Option<(f64, String)> {
         self.prices.read().await.get(id).cloned()
     }
+
+    async fn ids(&self) -> Vec<String> {
+        self.prices.read().await.keys().cloned().collect()
+    }
 }
 
 impl std::fmt::Debug for PriceCache {
@@ -285,8 +674,15 @@ impl std::fmt::Debug for PriceCache {
 pub struct AirbnbMcpServer {
     client: Arc<dyn AirbnbClient>,
     tool_router: ToolRouter<Self>,
-    resources: ResourceStore,
+    prompt_router: PromptRouter<Self>,
+    resources: SessionResources,
     price_cache: PriceCache,
+    session_mode: SessionMode,
+    logs: LogForwarder,
+    limits: ToolLimits,
+    cache: Option<Arc<dyn ListingCache>>,
+    /// [`ADMIN_TOOLS`] that `tools.allow` enables for every client.
+    admin_tools: Vec<String>,
 }
 
 #[tool_router]
@@ -294,10 +690,176 @@ impl AirbnbMcpServer {
     pub fn new(client: Arc<dyn AirbnbClient>) -> Self {
         Self {
             client,
-            tool_router: Self::tool_router(),
-            resources: ResourceStore::default(),
+            tool_router: Self::routes(),
+            prompt_router: Self::prompt_router(),
+            resources: SessionResources::default(),
             price_cache: PriceCache::default(),
+            session_mode: SessionMode::default(),
+            logs: LogForwarder::default(),
+            limits: ToolLimits::default(),
+            cache: None,
+            admin_tools: Vec::new(),
+        }
+    }
+
+    /// Every tool, with the `fresh` option on those that fetch from Airbnb.
+    fn routes() -> ToolRouter<Self> {
+        let mut router = ToolRouter::new();
+        for mut route in Self::tool_router() {
+            if route
+                .attr
+                .annotations
+                .as_ref()
+                .and_then(|a| a.open_world_hint)
+                == Some(true)
+            {
+                add_fresh_option(&mut route.attr);
+            }
+            router.add_route(route);
+        }
+        router
+    }
+
+    /// Register only the tools `config` enables and apply its argument limits. The cache
+    /// tools are only offered to every client when `tools.allow` names them.
+    /// Fails on tool names that don't exist, so a typo can't silently expose a tool.
+    pub fn with_tools(mut self, config: &ToolsConfig) -> AppResult<Self> {
+        let issues = Self::tool_issues(config);
+        if !issues.is_empty() {
+            let list: Vec<String> = issues.iter().map(ToString::to_string).collect();
+            return Err(AirbnbError::Config(list.join("; ")));
         }
+        let mut router = ToolRouter::new();
+        for mut route in Self::routes() {
+            if !config.enables(route.name()) {
+                continue;
+            }
+            if matches!(
+                route.name(),
+                "airbnb_review_sentiment" | "airbnb_review_summary"
+            ) {
+                set_maximum(&mut route.attr, "max_pages", config.limits.max_review_pages);
+            }
+            router.add_route(route);
+        }
+        self.tool_router = router;
+        self.limits = config.limits.clone();
+        self.admin_tools = ADMIN_TOOLS
+            .iter()
+            .filter(|tool| config.allow.iter().flatten().any(|name| name == *tool))
+            .filter(|tool| config.enables(tool))
+            .map(ToString::to_string)
+            .collect();
+        Ok(self)
+    }
+
+    /// Names in `tools.allow` / `tools.deny` that are not tools of this server.
+    pub fn tool_issues(config: &ToolsConfig) -> Vec<ConfigIssue> {
+        let router = Self::tool_router();
+        let allowed = config
+            .allow
+            .iter()
+            .flatten()
+            .map(|name| ("tools.allow", name));
+        let denied = config.deny.iter().map(|name| ("tools.deny", name));
+        allowed
+            .chain(denied)
+            .filter(|(_, name)| !router.has_route(name))
+            .map(|(path, name)| {
+                let normalized = name.replace('-', "_");
+                let prefixed = if normalized.starts_with("airbnb_") {
+                    normalized
+                } else {
+                    format!("airbnb_{normalized}")
+                };
+                ConfigIssue {
+                    path: path.into(),
+                    message: format!("unknown tool '{name}'"),
+                    suggestion: Some(if router.has_route(&prefixed) {
+                        format!("did you mean '{prefixed}'?")
+                    } else {
+                        "run `mcp-airbnb analyze --help` or list tools from an MCP client for valid names".into()
+                    }),
+                }
+            })
+            .collect()
+    }
+
+    /// Set how sessions created with [`Self::for_session`] share state.
+    #[must_use]
+    pub fn with_session_mode(mut self, mode: SessionMode) -> Self {
+        self.session_mode = mode;
+        self
+    }
+
+    /// Set the capacity, TTLs and page size of the resource stores.
+    #[must_use]
+    pub fn with_resource_config(mut self, config: &ResourceConfig) -> Self {
+        self.resources.shared = ResourceStore::new(config);
+        self
+    }
+
+    /// Expose `cache` to the `airbnb_cache_stats` and `airbnb_cache_invalidate` tools;
+    /// without it they report that no cache is configured.
+    #[must_use]
+    pub fn with_cache(mut self, cache: Arc<dyn ListingCache>) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
+    /// Forward log events to clients through `forwarder` (see [`LogForwarder::layer`]).
+    #[must_use]
+    pub fn with_log_forwarder(mut self, forwarder: LogForwarder) -> Self {
+        self.logs = forwarder;
+        self
+    }
+
+    /// Create the server instance for a new MCP session.
+    ///
+    /// In [`SessionMode::Shared`] this is a plain clone. In [`SessionMode::Isolated`] the
+    /// session gets its own private resource layer and price cache on top of the shared
+    /// listing resources, so concurrent clients never see each other's searches or analyses.
+    #[must_use]
+    pub fn for_session(&self) -> Self {
+        let mut session = self.clone();
+        session.resources.session = self.resources.notifier.next_session_id();
+        if self.session_mode == SessionMode::Isolated {
+            session.resources.private = Some(self.resources.shared.empty_like());
+            session.price_cache = PriceCache::default();
+        }
+        session
+    }
+
+    /// Values this session has already seen for a completable argument: listing IDs from
+    /// search results and listing resources for `id`, searched locations for `location`.
+    async fn completion_candidates(&self, argument: &str) -> BTreeSet<String> {
+        let mut values = BTreeSet::new();
+        if argument == "id" {
+            values.extend(self.price_cache.ids().await);
+        } else if argument != "location" {
+            return values;
+        }
+        for summary in self.resources.list().await {
+            let value = match (argument, ResourceUri::parse(&summary.uri)) {
+                (
+                    "id",
+                    Some(
+                        ResourceUri::Listing(id)
+                        | ResourceUri::Calendar(id)
+                        | ResourceUri::Reviews(id)
+                        | ResourceUri::Host(id)
+                        | ResourceUri::Occupancy(id),
+                    ),
+                ) => id,
+                (
+                    "location",
+                    Some(ResourceUri::Search(location) | ResourceUri::Neighborhood(location)),
+                ) => location,
+                _ => continue,
+            };
+            values.insert(value);
+        }
+        values
     }
 
     /// Get listing detail with price fallback from search cache.
@@ -315,19 +877,85 @@ impl AirbnbMcpServer {
         Ok(detail)
     }
 
+    /// Fetch a resource template live by running the tool that normally populates it.
+    ///
+    /// The tool stores the result under the canonical URI as a side effect, so the caller
+    /// reads it back from the resource store.
+    async fn fetch_resource(&self, uri: ResourceUri) -> Result<CallToolResult, McpError> {
+        match uri {
+            ResourceUri::Listing(id) => {
+                self.airbnb_listing_details(Parameters(DetailToolParams { id }))
+                    .await
+            }
+            ResourceUri::Calendar(id) => {
+                self.airbnb_price_calendar(Parameters(CalendarToolParams { id, months: None }))
+                    .await
+            }
+            ResourceUri::Reviews(id) => {
+                self.airbnb_reviews(Parameters(ReviewsToolParams { id, cursor: None }))
+                    .await
+            }
+            ResourceUri::Host(id) => {
+                self.airbnb_host_profile(Parameters(HostProfileToolParams { id }))
+                    .await
+            }
+            ResourceUri::Occupancy(id) => {
+                self.airbnb_occupancy_estimate(Parameters(OccupancyEstimateToolParams {
+                    id,
+                    months: None,
+                }))
+                .await
+            }
+            ResourceUri::Search(location) => {
+                self.airbnb_search(
+                    Parameters(SearchToolParams {
+                        location,
+                        checkin: None,
+                        checkout: None,
+                        adults: None,
+                        children: None,
+                        infants: None,
+                        pets: None,
+                        min_price: None,
+                        max_price: None,
+                        property_type: None,
+                        cursor: None,
+                    }),
+                    Elicitor::unavailable(),
+                )
+                .await
+            }
+            ResourceUri::Neighborhood(location) => {
+                self.airbnb_neighborhood_stats(Parameters(NeighborhoodStatsToolParams {
+                    location,
+                    checkin: None,
+                    checkout: None,
+                    property_type: None,
+                }))
+                .await
+            }
+            ResourceUri::Analysis { .. } => Err(McpError::invalid_params(
+                "analysis resources cannot be fetched on demand; call the analytical tool",
+                None,
+            )),
+        }
+    }
+
     /// Search Airbnb listings by location, dates, and guest count.
     /// Returns a list of available listings matching the search criteria.
     #[tool(
         name = "airbnb_search",
         description = "Search Airbnb listings by location, dates, and guest count. Returns a list of available listings with prices, ratings, and links. Use this as the starting point to discover listings and get their IDs for other tools.",
-        annotations(read_only_hint = true, open_world_hint = true)
+        annotations(read_only_hint = true, open_world_hint = true),
+        output_schema = output_schema::<crate::domain::listing::SearchResult>()
     )]
     #[allow(clippy::too_many_lines)]
     async fn airbnb_search(
         &self,
         Parameters(params): Parameters<SearchToolParams>,
+        elicitor: Elicitor,
     ) -> Result<CallToolResult, McpError> {
-        let search_params = SearchParams {
+        let mut search_params = SearchParams {
             location: params.location,
             checkin: params.checkin,
             checkout: params.checkout,
@@ -340,6 +968,12 @@ impl AirbnbMcpServer {
             property_type: params.property_type,
             cursor: params.cursor,
         };
+        // Only the first page asks; later pages reuse the parameters of the first
+        if elicitor.is_available() && search_params.cursor.is_none() {
+            let seen = self.completion_candidates("location").await;
+            elicitor.resolve_location(&mut search_params, &seen).await;
+            elicitor.fill_trip_details(&mut search_params).await;
+        }
 
         match self.client.search_listings(&search_params).await {
             Ok(result) => {
@@ -384,10 +1018,10 @@ impl AirbnbMcpServer {
                         .insert(&listing.id, listing.price_per_night, &listing.currency)
                         .await;
                 }
-                let uri = format!("airbnb://search/{}", search_params.location);
+                let uri = ResourceUri::Search(search_params.location.clone()).to_string();
                 let name = format!("Search: {}", search_params.location);
                 self.resources.insert(uri, name, text.clone()).await;
-                Ok(CallToolResult::success(vec![Content::text(text)]))
+                structured_success(text, &result)
             }
             Err(e) => Ok(CallToolResult::error(vec![Content::text(format!(
                 "Search failed: {e}. Try broadening your search criteria (remove date/price filters) or check the location spelling."
@@ -400,7 +1034,8 @@ impl AirbnbMcpServer {
     #[tool(
         name = "airbnb_listing_details",
         description = "Get detailed information about a specific Airbnb listing including description, amenities, house rules, photos, and host info. Requires a listing ID from airbnb_search.",
-        annotations(read_only_hint = true, open_world_hint = true)
+        annotations(read_only_hint = true, open_world_hint = true),
+        output_schema = output_schema::<crate::domain::listing::ListingDetail>()
     )]
     async fn airbnb_listing_details(
         &self,
@@ -409,10 +1044,10 @@ impl AirbnbMcpServer {
         match self.get_detail_with_price(&params.id).await {
             Ok(detail) => {
                 let text = detail.to_string();
-                let uri = format!("airbnb://listing/{}", params.id);
+                let uri = ResourceUri::Listing(params.id.clone()).to_string();
                 let name = format!("Listing: {}", detail.name);
                 self.resources.insert(uri, name, text.clone()).await;
-                Ok(CallToolResult::success(vec![Content::text(text)]))
+                structured_success(text, &detail)
             }
             Err(e) => Ok(CallToolResult::error(vec![Content::text(format!(
                 "Failed to get listing details for ID '{}': {e}. Verify the listing ID is correct — use airbnb_search to find valid IDs.",
@@ -425,7 +1060,8 @@ impl AirbnbMcpServer {
     #[tool(
         name = "airbnb_reviews",
         description = "Get reviews for an Airbnb listing including ratings summary, individual reviews with comments, and pagination support. Requires a listing ID. Use cursor from previous response to load more reviews.",
-        annotations(read_only_hint = true, open_world_hint = true)
+        annotations(read_only_hint = true, open_world_hint = true),
+        output_schema = output_schema::<crate::domain::review::ReviewsPage>()
     )]
     async fn airbnb_reviews(
         &self,
@@ -438,10 +1074,10 @@ impl AirbnbMcpServer {
         {
             Ok(page) => {
                 let text = page.to_string();
-                let uri = format!("airbnb://listing/{}/reviews", params.id);
+                let uri = ResourceUri::Reviews(params.id.clone()).to_string();
                 let name = format!("Reviews: listing {}", params.id);
                 self.resources.insert(uri, name, text.clone()).await;
-                Ok(CallToolResult::success(vec![Content::text(text)]))
+                structured_success(text, &page)
             }
             Err(e) => Ok(CallToolResult::error(vec![Content::text(format!(
                 "Failed to get reviews for listing '{}': {e}. The listing may have no reviews yet.",
@@ -454,7 +1090,8 @@ impl AirbnbMcpServer {
     #[tool(
         name = "airbnb_price_calendar",
         description = "Get price and availability calendar for an Airbnb listing showing daily prices, availability status, and minimum night requirements. Useful for analyzing seasonal pricing and finding available dates.",
-        annotations(read_only_hint = true, open_world_hint = true)
+        annotations(read_only_hint = true, open_world_hint = true),
+        output_schema = output_schema::<crate::domain::calendar::PriceCalendar>()
     )]
     async fn airbnb_price_calendar(
         &self,
@@ -465,10 +1102,10 @@ impl AirbnbMcpServer {
         match self.client.get_price_calendar(&params.id, months).await {
             Ok(calendar) => {
                 let text = calendar.to_string();
-                let uri = format!("airbnb://listing/{}/calendar", params.id);
+                let uri = ResourceUri::Calendar(params.id.clone()).to_string();
                 let name = format!("Calendar: listing {}", params.id);
                 self.resources.insert(uri, name, text.clone()).await;
-                Ok(CallToolResult::success(vec![Content::text(text)]))
+                structured_success(text, &calendar)
             }
             Err(e) => Ok(CallToolResult::error(vec![Content::text(format!(
                 "Failed to get price calendar for listing '{}': {e}. The listing may be unlisted or the calendar unavailable.",
@@ -481,7 +1118,8 @@ impl AirbnbMcpServer {
     #[tool(
         name = "airbnb_host_profile",
         description = "Get detailed host profile including superhost status, response rate, languages, bio, and listing count. Requires a listing ID to identify the host.",
-        annotations(read_only_hint = true, open_world_hint = true)
+        annotations(read_only_hint = true, open_world_hint = true),
+        output_schema = output_schema::<analytics::HostProfile>()
     )]
     async fn airbnb_host_profile(
         &self,
@@ -490,10 +1128,10 @@ impl AirbnbMcpServer {
         match self.client.get_host_profile(&params.id).await {
             Ok(profile) => {
                 let text = profile.to_string();
-                let uri = format!("airbnb://listing/{}/host", params.id);
+                let uri = ResourceUri::Host(params.id.clone()).to_string();
                 let name = format!("Host: listing {}", params.id);
                 self.resources.insert(uri, name, text.clone()).await;
-                Ok(CallToolResult::success(vec![Content::text(text)]))
+                structured_success(text, &profile)
             }
             Err(e) => Ok(CallToolResult::error(vec![Content::text(format!(
                 "Failed to get host profile for listing '{}': {e}. Try airbnb_listing_details instead for basic host info.",
@@ -506,7 +1144,8 @@ impl AirbnbMcpServer {
     #[tool(
         name = "airbnb_neighborhood_stats",
         description = "Get aggregated statistics for a neighborhood: average/median prices, ratings, property type distribution, and superhost percentage. Use this for market analysis and price benchmarking — does not require a listing ID, only a location.",
-        annotations(read_only_hint = true, open_world_hint = true)
+        annotations(read_only_hint = true, open_world_hint = true),
+        output_schema = output_schema::<analytics::NeighborhoodStats>()
     )]
     async fn airbnb_neighborhood_stats(
         &self,
@@ -530,10 +1169,10 @@ impl AirbnbMcpServer {
         match self.client.get_neighborhood_stats(&search_params).await {
             Ok(stats) => {
                 let text = stats.to_string();
-                let uri = format!("airbnb://neighborhood/{location}");
+                let uri = ResourceUri::Neighborhood(location.clone()).to_string();
                 let name = format!("Neighborhood: {location}");
                 self.resources.insert(uri, name, text.clone()).await;
-                Ok(CallToolResult::success(vec![Content::text(text)]))
+                structured_success(text, &stats)
             }
             Err(e) => Ok(CallToolResult::error(vec![Content::text(format!(
                 "Failed to get neighborhood stats for '{location}': {e}. Try a broader location name or check spelling."
@@ -545,7 +1184,8 @@ impl AirbnbMcpServer {
     #[tool(
         name = "airbnb_occupancy_estimate",
         description = "Estimate occupancy rate, average prices (weekday vs weekend), and monthly breakdown for a listing based on calendar data. Useful for hosts evaluating rental income potential.",
-        annotations(read_only_hint = true, open_world_hint = true)
+        annotations(read_only_hint = true, open_world_hint = true),
+        output_schema = output_schema::<analytics::OccupancyEstimate>()
     )]
     async fn airbnb_occupancy_estimate(
         &self,
@@ -556,10 +1196,10 @@ impl AirbnbMcpServer {
         match self.client.get_occupancy_estimate(&params.id, months).await {
             Ok(estimate) => {
                 let text = estimate.to_string();
-                let uri = format!("airbnb://listing/{}/occupancy", params.id);
+                let uri = ResourceUri::Occupancy(params.id.clone()).to_string();
                 let name = format!("Occupancy: listing {}", params.id);
                 self.resources.insert(uri, name, text.clone()).await;
-                Ok(CallToolResult::success(vec![Content::text(text)]))
+                structured_success(text, &estimate)
             }
             Err(e) => Ok(CallToolResult::error(vec![Content::text(format!(
                 "Failed to get occupancy estimate for listing '{}': {e}. This requires calendar data — verify the listing ID.",
@@ -574,12 +1214,14 @@ impl AirbnbMcpServer {
     #[tool(
         name = "airbnb_compare_listings",
         description = "Compare 2-100+ Airbnb listings side-by-side with price percentiles, ratings, and market summary. Provide listing IDs for detailed comparison (2-10), OR a location for market-scale comparison (up to 100 listings via paginated search). Returns ranking table with percentile positions.",
-        annotations(read_only_hint = true, open_world_hint = true)
+        annotations(read_only_hint = true, open_world_hint = true),
+        output_schema = output_schema::<analytics::CompareListingsResult>()
     )]
     #[allow(clippy::too_many_lines)]
     async fn airbnb_compare_listings(
         &self,
         Parameters(params): Parameters<CompareListingsToolParams>,
+        progress: ToolProgress,
     ) -> Result<CallToolResult, McpError> {
         if params.ids.is_none() && params.location.is_none() {
             return Ok(CallToolResult::error(vec![Content::text(
@@ -588,11 +1230,18 @@ impl AirbnbMcpServer {
         }
 
         let mut pages_fetched: u32 = 0;
+        let mut cancelled = None;
         let listings = if let Some(ref ids) = params.ids {
             // Mode 1: Fetch by IDs — use search results for lightweight comparison
+            let ids: Vec<&String> = ids.iter().take(self.limits.max_compare_ids).collect();
+            let total = u32::try_from(ids.len()).unwrap_or(u32::MAX);
             let mut all = Vec::new();
-            for id in ids.iter().take(10) {
-                match self.get_detail_with_price(id).await {
+            for (done, id) in (0..total).zip(ids) {
+                let Some(fetched) = progress.run(self.get_detail_with_price(id)).await else {
+                    cancelled = Some(partial_note(done, total, "listings"));
+                    break;
+                };
+                match fetched {
                     Ok(d) => all.push(crate::domain::listing::Listing {
                         id: d.id,
                         name: d.name,
@@ -620,13 +1269,21 @@ impl AirbnbMcpServer {
                         ))]));
                     }
                 }
+                progress
+                    .report(done + 1, total, format!("Fetched listing {id}"))
+                    .await;
             }
             all
         } else {
             // Mode 2: Location discovery — paginate search
             let location = params.location.as_deref().unwrap_or("");
-            let max = params.max_listings.unwrap_or(20).clamp(2, 100) as usize;
+            let max = params
+                .max_listings
+                .unwrap_or(20)
+                .min(self.limits.max_listings)
+                .max(2) as usize;
             let max_pages = max.div_ceil(20);
+            let total_pages = u32::try_from(max_pages).unwrap_or(u32::MAX);
             let mut all = Vec::new();
             let mut cursor = None;
 
@@ -644,11 +1301,25 @@ impl AirbnbMcpServer {
                     property_type: params.property_type.clone(),
                     cursor: cursor.clone(),
                 };
-                match self.client.search_listings(&sp).await {
+                let Some(searched) = progress.run(self.client.search_listings(&sp)).await else {
+                    cancelled = Some(partial_note(pages_fetched, total_pages, "search pages"));
+                    break;
+                };
+                match searched {
                     Ok(result) => {
                         pages_fetched += 1;
                         all.extend(result.listings);
                         cursor = result.next_cursor;
+                        progress
+                            .report(
+                                pages_fetched,
+                                total_pages,
+                                format!(
+                                    "Fetched search page {pages_fetched} ({} listings)",
+                                    all.len()
+                                ),
+                            )
+                            .await;
                         if cursor.is_none() || all.len() >= max {
                             break;
                         }
@@ -678,7 +1349,10 @@ impl AirbnbMcpServer {
             .or(params.location.clone())
             .unwrap_or_default();
         let text = result.to_string();
-        let uri = format!("airbnb://analysis/compare/{key}");
+        if let Some(note) = cancelled {
+            return structured_success(format!("{note}{text}"), &result);
+        }
+        let uri = ResourceUri::analysis("compare", key.clone()).to_string();
         let name = format!("Comparison: {key}");
         self.resources.insert(uri, name, text.clone()).await;
 
@@ -692,14 +1366,15 @@ impl AirbnbMcpServer {
         } else {
             text
         };
-        Ok(CallToolResult::success(vec![Content::text(output)]))
+        structured_success(output, &result)
     }
 
     /// Analyze seasonal price trends for a listing.
     #[tool(
         name = "airbnb_price_trends",
         description = "Analyze seasonal price trends for an Airbnb listing: monthly averages, weekend vs weekday premiums, price volatility, peak/off-peak months, and day-of-week breakdown. Based on calendar data.",
-        annotations(read_only_hint = true, open_world_hint = true)
+        annotations(read_only_hint = true, open_world_hint = true),
+        output_schema = output_schema::<analytics::PriceTrends>()
     )]
     async fn airbnb_price_trends(
         &self,
@@ -711,10 +1386,10 @@ impl AirbnbMcpServer {
             Ok(calendar) => {
                 let trends = analytics::compute_price_trends(&params.id, &calendar);
                 let text = trends.to_string();
-                let uri = format!("airbnb://analysis/price-trends/{}", params.id);
+                let uri = ResourceUri::analysis("price-trends", params.id.clone()).to_string();
                 let name = format!("Price Trends: listing {}", params.id);
                 self.resources.insert(uri, name, text.clone()).await;
-                Ok(CallToolResult::success(vec![Content::text(text)]))
+                structured_success(text, &trends)
             }
             Err(e) => Ok(CallToolResult::error(vec![Content::text(format!(
                 "Failed to get price data for listing '{}': {e}",
@@ -727,7 +1402,8 @@ impl AirbnbMcpServer {
     #[tool(
         name = "airbnb_gap_finder",
         description = "Detect booking gaps and orphan nights (1-3 night gaps between reservations) in an Airbnb listing's calendar. Shows potential lost revenue and suggests minimum stay adjustments. Essential for occupancy optimization.",
-        annotations(read_only_hint = true, open_world_hint = true)
+        annotations(read_only_hint = true, open_world_hint = true),
+        output_schema = output_schema::<analytics::GapFinderResult>()
     )]
     async fn airbnb_gap_finder(
         &self,
@@ -739,10 +1415,10 @@ impl AirbnbMcpServer {
             Ok(calendar) => {
                 let result = analytics::compute_gap_finder(&params.id, &calendar);
                 let text = result.to_string();
-                let uri = format!("airbnb://analysis/gaps/{}", params.id);
+                let uri = ResourceUri::analysis("gaps", params.id.clone()).to_string();
                 let name = format!("Gap Finder: listing {}", params.id);
                 self.resources.insert(uri, name, text.clone()).await;
-                Ok(CallToolResult::success(vec![Content::text(text)]))
+                structured_success(text, &result)
             }
             Err(e) => Ok(CallToolResult::error(vec![Content::text(format!(
                 "Failed to get calendar for listing '{}': {e}",
@@ -755,7 +1431,8 @@ impl AirbnbMcpServer {
     #[tool(
         name = "airbnb_revenue_estimate",
         description = "Estimate projected revenue for an Airbnb listing: ADR (Average Daily Rate), occupancy rate, monthly and annual revenue projections, and comparison vs neighborhood average. Provide a listing ID for specific estimates, or just a location for market-based projections.",
-        annotations(read_only_hint = true, open_world_hint = true)
+        annotations(read_only_hint = true, open_world_hint = true),
+        output_schema = output_schema::<analytics::RevenueEstimate>()
     )]
     async fn airbnb_revenue_estimate(
         &self,
@@ -807,17 +1484,18 @@ impl AirbnbMcpServer {
         );
         let text = result.to_string();
         let key = params.id.as_deref().unwrap_or(&location);
-        let uri = format!("airbnb://analysis/revenue/{key}");
+        let uri = ResourceUri::analysis("revenue", key).to_string();
         let name = format!("Revenue Estimate: {key}");
         self.resources.insert(uri, name, text.clone()).await;
-        Ok(CallToolResult::success(vec![Content::text(text)]))
+        structured_success(text, &result)
     }
 
     /// Score a listing's quality and optimization level.
     #[tool(
         name = "airbnb_listing_score",
         description = "Score an Airbnb listing's quality (0-100) across 6 categories: photos, description, amenities, reviews, host profile, and pricing vs market. Provides actionable improvement suggestions. Like a free listing audit.",
-        annotations(read_only_hint = true, open_world_hint = true)
+        annotations(read_only_hint = true, open_world_hint = true),
+        output_schema = output_schema::<analytics::ListingScore>()
     )]
     async fn airbnb_listing_score(
         &self,
@@ -833,10 +1511,10 @@ impl AirbnbMcpServer {
                 let neighborhood = self.client.get_neighborhood_stats(&sp).await.ok();
                 let score = analytics::compute_listing_score(&detail, neighborhood.as_ref());
                 let text = score.to_string();
-                let uri = format!("airbnb://analysis/score/{}", params.id);
+                let uri = ResourceUri::analysis("score", params.id.clone()).to_string();
                 let name = format!("Listing Score: listing {}", params.id);
                 self.resources.insert(uri, name, text.clone()).await;
-                Ok(CallToolResult::success(vec![Content::text(text)]))
+                structured_success(text, &score)
             }
             Err(e) => Ok(CallToolResult::error(vec![Content::text(format!(
                 "Failed to get listing '{}': {e}",
@@ -849,7 +1527,8 @@ impl AirbnbMcpServer {
     #[tool(
         name = "airbnb_amenity_analysis",
         description = "Compare an Airbnb listing's amenities against neighborhood competition. Identifies missing popular amenities and highlights unique ones you have. Helps optimize your listing to match or beat competitors.",
-        annotations(read_only_hint = true, open_world_hint = true)
+        annotations(read_only_hint = true, open_world_hint = true),
+        output_schema = output_schema::<analytics::AmenityAnalysis>()
     )]
     async fn airbnb_amenity_analysis(
         &self,
@@ -892,17 +1571,18 @@ impl AirbnbMcpServer {
 
         let analysis = analytics::compute_amenity_analysis(&detail, &neighbor_details);
         let text = analysis.to_string();
-        let uri = format!("airbnb://analysis/amenities/{}", params.id);
+        let uri = ResourceUri::analysis("amenities", params.id.clone()).to_string();
         let name = format!("Amenity Analysis: listing {}", params.id);
         self.resources.insert(uri, name, text.clone()).await;
-        Ok(CallToolResult::success(vec![Content::text(text)]))
+        structured_success(text, &analysis)
     }
 
     /// Compare multiple locations/neighborhoods side-by-side.
     #[tool(
         name = "airbnb_market_comparison",
         description = "Compare 2-5 Airbnb markets side-by-side: average/median prices, ratings, superhost percentage, and dominant property types. Ideal for deciding where to invest or list a property.",
-        annotations(read_only_hint = true, open_world_hint = true)
+        annotations(read_only_hint = true, open_world_hint = true),
+        output_schema = output_schema::<analytics::MarketComparison>()
     )]
     async fn airbnb_market_comparison(
         &self,
@@ -936,17 +1616,18 @@ impl AirbnbMcpServer {
         let result = analytics::compute_market_comparison(&stats);
         let text = result.to_string();
         let key = params.locations.join("_");
-        let uri = format!("airbnb://analysis/market/{key}");
+        let uri = ResourceUri::analysis("market", key.clone()).to_string();
         let name = format!("Market Comparison: {key}");
         self.resources.insert(uri, name, text.clone()).await;
-        Ok(CallToolResult::success(vec![Content::text(text)]))
+        structured_success(text, &result)
     }
 
     /// Analyze a host's full property portfolio.
     #[tool(
         name = "airbnb_host_portfolio",
         description = "Analyze an Airbnb host's full portfolio: all their properties, average rating, pricing strategy, total reviews, and geographic distribution. Useful for competitive intelligence on professional operators. Requires any listing ID from the host.",
-        annotations(read_only_hint = true, open_world_hint = true)
+        annotations(read_only_hint = true, open_world_hint = true),
+        output_schema = output_schema::<analytics::HostPortfolio>()
     )]
     async fn airbnb_host_portfolio(
         &self,
@@ -1039,31 +1720,54 @@ impl AirbnbMcpServer {
             &listings,
         );
         let text = result.to_string();
-        let uri = format!("airbnb://analysis/portfolio/{}", params.id);
+        let uri = ResourceUri::analysis("portfolio", params.id.clone()).to_string();
         let name = format!("Host Portfolio: listing {}", params.id);
         self.resources.insert(uri, name, text.clone()).await;
-        Ok(CallToolResult::success(vec![Content::text(text)]))
+        structured_success(text, &result)
     }
 
     /// Analyze review sentiment for a listing.
     #[tool(
         name = "airbnb_review_sentiment",
         description = "Analyze guest review sentiment for an Airbnb listing: positive/negative/neutral breakdown, recurring themes (cleanliness, location, communication, amenities, value), and top keywords. Helps identify strengths and weaknesses from guest feedback.",
-        annotations(read_only_hint = true, open_world_hint = true)
+        annotations(read_only_hint = true, open_world_hint = true),
+        output_schema = output_schema::<analytics::ReviewSentiment>()
     )]
     async fn airbnb_review_sentiment(
         &self,
         Parameters(params): Parameters<ReviewSentimentToolParams>,
+        progress: ToolProgress,
     ) -> Result<CallToolResult, McpError> {
-        let max_pages = params.max_pages.unwrap_or(5).clamp(1, 20);
+        let max_pages = params
+            .max_pages
+            .unwrap_or(5)
+            .min(self.limits.max_review_pages)
+            .max(1);
         let mut all_reviews = Vec::new();
         let mut cursor = None;
+        let mut cancelled = None;
 
-        for _ in 0..max_pages {
-            match self.client.get_reviews(&params.id, cursor.as_deref()).await {
+        for page_no in 0..max_pages {
+            let fetch = self.client.get_reviews(&params.id, cursor.as_deref());
+            let Some(fetched) = progress.run(fetch).await else {
+                cancelled = Some(partial_note(page_no, max_pages, "review pages"));
+                break;
+            };
+            match fetched {
                 Ok(page) => {
                     all_reviews.extend(page.reviews);
                     cursor = page.next_cursor;
+                    progress
+                        .report(
+                            page_no + 1,
+                            max_pages,
+                            format!(
+                                "Fetched review page {} ({} reviews)",
+                                page_no + 1,
+                                all_reviews.len()
+                            ),
+                        )
+                        .await;
                     if cursor.is_none() {
                         break;
                     }
@@ -1082,17 +1786,102 @@ impl AirbnbMcpServer {
 
         let result = analytics::compute_review_sentiment(&params.id, &all_reviews);
         let text = result.to_string();
-        let uri = format!("airbnb://analysis/sentiment/{}", params.id);
+        if let Some(note) = cancelled {
+            return structured_success(format!("{note}{text}"), &result);
+        }
+        let uri = ResourceUri::analysis("sentiment", params.id.clone()).to_string();
         let name = format!("Review Sentiment: listing {}", params.id);
         self.resources.insert(uri, name, text.clone()).await;
-        Ok(CallToolResult::success(vec![Content::text(text)]))
+        structured_success(text, &result)
+    }
+
+    /// Summarize reviews into pros and cons with the client's model (MCP sampling).
+    #[tool(
+        name = "airbnb_review_summary",
+        description = "Summarize guest reviews of an Airbnb listing into pros and cons, each citing the reviews it comes from. Uses the client's own model through MCP sampling; when the client does not support sampling or declines, falls back to the keyword-based sentiment analysis.",
+        annotations(read_only_hint = true, open_world_hint = true),
+        output_schema = output_schema::<analytics::ReviewSummary>()
+    )]
+    async fn airbnb_review_summary(
+        &self,
+        Parameters(params): Parameters<ReviewSummaryToolParams>,
+        sampler: Sampler,
+    ) -> Result<CallToolResult, McpError> {
+        let max_pages = params
+            .max_pages
+            .unwrap_or(3)
+            .min(self.limits.max_review_pages)
+            .max(1);
+        let mut all_reviews = Vec::new();
+        let mut cursor = None;
+
+        for _ in 0..max_pages {
+            match self.client.get_reviews(&params.id, cursor.as_deref()).await {
+                Ok(page) => {
+                    all_reviews.extend(page.reviews);
+                    cursor = page.next_cursor;
+                    if cursor.is_none() {
+                        break;
+                    }
+                }
+                Err(e) => {
+                    if all_reviews.is_empty() {
+                        return Ok(CallToolResult::error(vec![Content::text(format!(
+                            "Failed to get reviews for listing '{}': {e}",
+                            params.id
+                        ))]));
+                    }
+                    break;
+                }
+            }
+        }
+
+        let result = if all_reviews.is_empty() {
+            analytics::fallback_review_summary(
+                &params.id,
+                &all_reviews,
+                "No reviews to summarize.".into(),
+            )
+        } else {
+            let cited = &all_reviews[..all_reviews.len().min(SUMMARY_MAX_REVIEWS)];
+            let prompt = format!(
+                "Summarize these guest reviews of an Airbnb listing.\n\n{}",
+                analytics::format_reviews_for_citation(cited, SUMMARY_MAX_COMMENT_CHARS)
+            );
+            match sampler
+                .complete(SUMMARY_SYSTEM_PROMPT, prompt, SUMMARY_MAX_TOKENS)
+                .await
+            {
+                Ok(sampled) => analytics::sampled_review_summary(
+                    &params.id,
+                    cited,
+                    sampled.model,
+                    sampled.text,
+                ),
+                Err(reason) => {
+                    tracing::debug!(id = %params.id, %reason, "Review summary falls back to keyword sentiment");
+                    analytics::fallback_review_summary(
+                        &params.id,
+                        &all_reviews,
+                        format!("Keyword-based fallback: {reason}."),
+                    )
+                }
+            }
+        };
+
+        let text = result.to_string();
+        let uri = ResourceUri::analysis("summary", params.id.clone()).to_string();
+        let name = format!("Review Summary: listing {}", params.id);
+        self.resources.insert(uri, name, text.clone()).await;
+        structured_success(text, &result)
     }
 
     /// Analyze a listing's competitive positioning vs its market.
     #[tool(
         name = "airbnb_competitive_positioning",
         description = "Evaluate an Airbnb listing's competitive position across 5 axes: price value, rating, amenity count, review volume, and occupancy. Returns percentile rankings, overall competitiveness score (0-100), strengths, and weaknesses vs the neighborhood.",
-        annotations(read_only_hint = true, open_world_hint = true)
+        annotations(read_only_hint = true, open_world_hint = true),
+        output_schema = output_schema::<analytics::CompetitivePositioning>()
     )]
     async fn airbnb_competitive_positioning(
         &self,
@@ -1154,23 +1943,37 @@ impl AirbnbMcpServer {
             amenity_analysis.as_ref(),
         );
         let text = result.to_string();
-        let uri = format!("airbnb://analysis/positioning/{}", params.id);
+        let uri = ResourceUri::analysis("positioning", params.id.clone()).to_string();
         let name = format!("Competitive Positioning: listing {}", params.id);
         self.resources.insert(uri, name, text.clone()).await;
-        Ok(CallToolResult::success(vec![Content::text(text)]))
+        structured_success(text, &result)
     }
 
     /// Suggest optimal pricing for a listing based on market data.
     #[tool(
         name = "airbnb_optimal_pricing",
         description = "Suggest optimal pricing for an Airbnb listing based on neighborhood comparables, seasonal trends, rating premium, and amenity analysis. Returns recommended price, range, weekday/weekend split, and detailed reasoning.",
-        annotations(read_only_hint = true, open_world_hint = true)
+        annotations(read_only_hint = true, open_world_hint = true),
+        output_schema = output_schema::<analytics::PricingRecommendation>()
     )]
+    #[allow(clippy::too_many_lines)]
     async fn airbnb_optimal_pricing(
         &self,
         Parameters(params): Parameters<OptimalPricingToolParams>,
+        progress: ToolProgress,
     ) -> Result<CallToolResult, McpError> {
-        let detail = match self.get_detail_with_price(&params.id).await {
+        // Detail, neighborhood stats, calendar and comparable search, then one detail per neighbor
+        const NEIGHBORS: u32 = 5;
+        let mut total = 4 + NEIGHBORS;
+        let mut cancelled = None;
+
+        let Some(detail) = progress.run(self.get_detail_with_price(&params.id)).await else {
+            return Ok(CallToolResult::error(vec![Content::text(format!(
+                "Cancelled before listing '{}' was fetched",
+                params.id
+            ))]));
+        };
+        let detail = match detail {
             Ok(d) => d,
             Err(e) => {
                 return Ok(CallToolResult::error(vec![Content::text(format!(
@@ -1179,6 +1982,7 @@ impl AirbnbMcpServer {
                 ))]));
             }
         };
+        progress.report(1, total, "Fetched listing detail").await;
 
         let location = params.location.unwrap_or_else(|| detail.location.clone());
         let sp = SearchParams {
@@ -1186,34 +1990,72 @@ impl AirbnbMcpServer {
             ..SearchParams::default()
         };
 
-        let neighborhood = self.client.get_neighborhood_stats(&sp).await.ok();
-        let price_trends = match self.client.get_price_calendar(&params.id, 12).await {
-            Ok(calendar) => Some(analytics::compute_price_trends(&params.id, &calendar)),
-            Err(_) => None,
-        };
+        let mut neighborhood = None;
+        let mut price_trends = None;
+        let mut amenity_analysis = None;
+        'fetch: {
+            let Some(stats) = progress.run(self.client.get_neighborhood_stats(&sp)).await else {
+                cancelled = Some(partial_note(1, total, "steps"));
+                break 'fetch;
+            };
+            neighborhood = stats.ok();
+            progress
+                .report(2, total, "Fetched neighborhood stats")
+                .await;
 
-        // Get amenity analysis
-        let amenity_analysis = if let Ok(search_result) = self.client.search_listings(&sp).await {
+            let Some(calendar) = progress
+                .run(self.client.get_price_calendar(&params.id, 12))
+                .await
+            else {
+                cancelled = Some(partial_note(2, total, "steps"));
+                break 'fetch;
+            };
+            price_trends = calendar
+                .ok()
+                .map(|calendar| analytics::compute_price_trends(&params.id, &calendar));
+            progress.report(3, total, "Fetched price calendar").await;
+
+            // Get amenity analysis
+            let Some(search) = progress.run(self.client.search_listings(&sp)).await else {
+                cancelled = Some(partial_note(3, total, "steps"));
+                break 'fetch;
+            };
+            let Ok(search_result) = search else {
+                progress
+                    .report(total, total, "Comparable search failed")
+                    .await;
+                break 'fetch;
+            };
             let neighbor_ids: Vec<String> = search_result
                 .listings
                 .into_iter()
                 .filter(|l| l.id != params.id)
-                .take(5)
+                .take(NEIGHBORS as usize)
                 .map(|l| l.id)
                 .collect();
+            total = 4 + u32::try_from(neighbor_ids.len()).unwrap_or(0);
+            progress
+                .report(4, total, "Searched comparable listings")
+                .await;
+
             let mut neighbor_details = Vec::new();
-            for id in &neighbor_ids {
-                if let Ok(d) = self.get_detail_with_price(id).await {
+            for (done, id) in (4..).zip(&neighbor_ids) {
+                let Some(neighbor) = progress.run(self.get_detail_with_price(id)).await else {
+                    cancelled = Some(partial_note(done, total, "steps"));
+                    break;
+                };
+                if let Ok(d) = neighbor {
                     neighbor_details.push(d);
                 }
+                progress
+                    .report(done + 1, total, format!("Fetched comparable listing {id}"))
+                    .await;
             }
-            Some(analytics::compute_amenity_analysis(
+            amenity_analysis = Some(analytics::compute_amenity_analysis(
                 &detail,
                 &neighbor_details,
-            ))
-        } else {
-            None
-        };
+            ));
+        }
 
         let result = analytics::compute_optimal_pricing(
             &detail,
@@ -1222,21 +2064,281 @@ impl AirbnbMcpServer {
             amenity_analysis.as_ref(),
         );
         let text = result.to_string();
-        let uri = format!("airbnb://analysis/pricing/{}", params.id);
+        if let Some(note) = cancelled {
+            return structured_success(format!("{note}{text}"), &result);
+        }
+        let uri = ResourceUri::analysis("pricing", params.id.clone()).to_string();
         let name = format!("Optimal Pricing: listing {}", params.id);
         self.resources.insert(uri, name, text.clone()).await;
-        Ok(CallToolResult::success(vec![Content::text(text)]))
+        structured_success(text, &result)
+    }
+
+    /// Report cache hit/miss counts, evictions and fill level per key prefix.
+    #[tool(
+        name = "airbnb_cache_stats",
+        description = "Show how the Airbnb data cache is doing: hits (and how many were stale), misses, evictions, entries purged because an older version wrote them, and how many entries it holds per kind of data (search:, detail:, gql:detail:, calendar:, ...) against its capacity. Use it to tell whether answers come from cache, and to pick a prefix for airbnb_cache_invalidate.",
+        annotations(read_only_hint = true, open_world_hint = false),
+        output_schema = output_schema::<CacheStats>()
+    )]
+    async fn airbnb_cache_stats(&self) -> Result<CallToolResult, McpError> {
+        let Some(ref cache) = self.cache else {
+            return Ok(no_cache_error());
+        };
+        let stats = cache.stats();
+        let mut text = String::from("## Cache\n\n");
+        let reads = stats.hits + stats.misses;
+        let _ = writeln!(
+            text,
+            "- Entries: {} / {}\n- Hits: {} ({} stale)\n- Misses: {}\n- Evictions: {}\n- Incompatible (purged): {}",
+            stats.entries,
+            stats.capacity,
+            stats.hits,
+            stats.stale_hits,
+            stats.misses,
+            stats.evictions,
+            stats.incompatible,
+        );
+        let _ = match stats.max_bytes {
+            Some(max) => writeln!(
+                text,
+                "- Size: {} / {}",
+                format_bytes(stats.bytes),
+                format_bytes(max)
+            ),
+            None => writeln!(text, "- Size: {}", format_bytes(stats.bytes)),
+        };
+        if reads > 0 {
+            #[allow(clippy::cast_precision_loss)]
+            let rate = stats.hits as f64 / reads as f64 * 100.0;
+            let _ = writeln!(text, "- Hit rate: {rate:.1}%");
+        }
+        if !stats.entries_by_prefix.is_empty() {
+            text.push_str("\n| Prefix | Entries |\n|--------|---------|\n");
+            for (prefix, count) in &stats.entries_by_prefix {
+                let _ = writeln!(text, "| `{prefix}` | {count} |");
+            }
+        }
+        structured_success(text, &stats)
+    }
+
+    /// Drop cached entries by key prefix so the next call fetches them again.
+    #[tool(
+        name = "airbnb_cache_invalidate",
+        description = "Remove cached Airbnb data whose cache key starts with a prefix, so the next tool call fetches it again. A prefix matches entries from both the scraper and GraphQL (whose keys start with \"gql:\"). Examples: \"detail:123:\" for one listing's details, \"calendar:\" for all calendars, \"gql:\" for everything fetched via GraphQL only, \"\" for the whole cache. To refetch a single call instead, pass fresh: true to that tool.",
+        annotations(
+            read_only_hint = false,
+            destructive_hint = true,
+            idempotent_hint = true,
+            open_world_hint = false
+        ),
+        output_schema = output_schema::<CacheInvalidation>()
+    )]
+    async fn airbnb_cache_invalidate(
+        &self,
+        Parameters(params): Parameters<CacheInvalidateToolParams>,
+    ) -> Result<CallToolResult, McpError> {
+        let Some(ref cache) = self.cache else {
+            return Ok(no_cache_error());
+        };
+        let mut removed = cache.invalidate_prefix(&params.prefix);
+        // The GraphQL adapter stores the same operations under `gql:`
+        if !params.prefix.is_empty() && !params.prefix.starts_with(GRAPHQL_KEY_TAG) {
+            removed += cache.invalidate_prefix(&format!("{GRAPHQL_KEY_TAG}{}", params.prefix));
+        }
+        tracing::info!(prefix = %params.prefix, removed, "Cache invalidated");
+        let text = if params.prefix.is_empty() {
+            format!("Cleared the cache ({removed} entries removed).")
+        } else {
+            format!(
+                "Removed {removed} cache entries starting with `{}`.",
+                params.prefix
+            )
+        };
+        structured_success(
+            text,
+            &CacheInvalidation {
+                prefix: params.prefix,
+                removed,
+            },
+        )
+    }
+}
+
+impl AirbnbMcpServer {
+    /// Whether `tool` is enabled for the caller behind `identity` (none for stdio and
+    /// open HTTP): [`ADMIN_TOOLS`] need `tools.allow` or the token to name them. The
+    /// token allowlist itself is checked by the callers.
+    fn admin_tool_enabled(&self, identity: Option<&AuthIdentity>, tool: &str) -> bool {
+        !ADMIN_TOOLS.contains(&tool)
+            || self.admin_tools.iter().any(|name| name == tool)
+            || identity.is_some_and(|identity| identity.names_tool(tool))
+    }
+
+    /// Check the caller may use the tool, route it (in [`fetch_fresh`] when asked) and
+    /// note any cached data it used.
+    async fn run_tool(
+        &self,
+        mut request: CallToolRequestParams,
+        context: RequestContext<RoleServer>,
+    ) -> Result<CallToolResult, McpError> {
+        let identity = auth::identity(&context);
+        if let Some(identity) = identity
+            && !identity.allows_tool(&request.name)
+        {
+            tracing::warn!(token = %identity.name, tool = %request.name, "Tool call denied by token allowlist");
+            return Err(McpError::invalid_request(
+                format!(
+                    "token '{}' is not allowed to call tool '{}'",
+                    identity.name, request.name
+                ),
+                None,
+            ));
+        }
+        if !self.admin_tool_enabled(identity, &request.name) {
+            return Err(McpError::invalid_request(
+                format!(
+                    "tool '{}' administers the shared cache; list it in tools.allow or in a token's allowed_tools to enable it",
+                    request.name
+                ),
+                None,
+            ));
+        }
+        let takes_fresh = self.tool_router.get(&request.name).is_some_and(|tool| {
+            tool.input_schema
+                .get("properties")
+                .and_then(|p| p.get(FRESH_ARGUMENT))
+                .is_some()
+        });
+        let fresh = takes_fresh
+            && request
+                .arguments
+                .as_mut()
+                .and_then(|arguments| arguments.remove(FRESH_ARGUMENT))
+                == Some(serde_json::Value::Bool(true));
+        let tcc = ToolCallContext::new(self, request, context);
+        let call = self.tool_router.call(tcc);
+        let (mut result, reads) = if fresh {
+            track_cache_reads(fetch_fresh(call)).await
+        } else {
+            track_cache_reads(call).await
+        };
+        if let Ok(ref mut result) = result
+            && result.is_error != Some(true)
+            && let Some(note) = cache_note(&reads, Utc::now())
+        {
+            result.content.push(Content::text(note));
+        }
+        result
+    }
+
+    /// A stored resource, or one resolved live through its backing tool.
+    async fn resolve_resource(
+        &self,
+        request: ReadResourceRequestParams,
+        context: RequestContext<RoleServer>,
+    ) -> Result<ReadResourceResult, McpError> {
+        let parsed = ResourceUri::parse(&request.uri);
+        // Entries are stored under the canonical URI; accept any valid encoding of it
+        let key = parsed
+            .as_ref()
+            .map_or_else(|| request.uri.clone(), ToString::to_string);
+        let not_found =
+            || McpError::resource_not_found(format!("resource not found: {}", request.uri), None);
+
+        if let Some(entry) = self.resources.get(&key).await {
+            return Ok(ReadResourceResult {
+                contents: vec![ResourceContents::text(entry.text, request.uri)],
+            });
+        }
+
+        // Not fetched yet: resolve the template live through the backing tool
+        let Some((uri, tool)) = parsed.and_then(|uri| uri.source_tool().map(|tool| (uri, tool)))
+        else {
+            return Err(not_found());
+        };
+        if !self.tool_router.has_route(tool) {
+            return Err(McpError::invalid_request(
+                format!(
+                    "resource '{}' requires tool '{tool}', which is disabled on this server",
+                    request.uri
+                ),
+                None,
+            ));
+        }
+        if let Some(identity) = auth::identity(&context)
+            && !identity.allows_tool(tool)
+        {
+            return Err(McpError::invalid_request(
+                format!(
+                    "resource '{}' requires tool '{tool}', which token '{}' is not allowed to call",
+                    request.uri, identity.name
+                ),
+                None,
+            ));
+        }
+
+        let result = self.fetch_resource(uri).await?;
+        if result.is_error == Some(true) {
+            let reason = result
+                .content
+                .first()
+                .and_then(|c| c.raw.as_text())
+                .map_or_else(
+                    || format!("failed to fetch {}", request.uri),
+                    |t| t.text.clone(),
+                );
+            return Err(McpError::resource_not_found(reason, None));
+        }
+        let entry = self.resources.get(&key).await.ok_or_else(not_found)?;
+        Ok(ReadResourceResult {
+            contents: vec![ResourceContents::text(entry.text, request.uri)],
+        })
+    }
+}
+
+#[prompt_handler]
+impl ServerHandler for AirbnbMcpServer {
+    async fn call_tool(
+        &self,
+        request: CallToolRequestParams,
+        context: RequestContext<RoleServer>,
+    ) -> Result<CallToolResult, McpError> {
+        logging::in_session(self.resources.session, self.run_tool(request, context)).await
+    }
+
+    async fn list_tools(
+        &self,
+        _request: Option<PaginatedRequestParams>,
+        context: RequestContext<RoleServer>,
+    ) -> Result<ListToolsResult, McpError> {
+        let mut tools = self.tool_router.list_all();
+        let identity = auth::identity(&context);
+        tools.retain(|tool| {
+            identity.is_none_or(|identity| identity.allows_tool(&tool.name))
+                && self.admin_tool_enabled(identity, &tool.name)
+        });
+        Ok(ListToolsResult {
+            tools,
+            meta: None,
+            next_cursor: None,
+        })
+    }
+
+    fn get_tool(&self, name: &str) -> Option<Tool> {
+        self.tool_router.get(name).cloned()
     }
-}
 
-#[tool_handler]
-impl ServerHandler for AirbnbMcpServer {
     fn get_info(&self) -> ServerInfo {
         ServerInfo {
             protocol_version: ProtocolVersion::LATEST,
             capabilities: ServerCapabilities::builder()
                 .enable_tools()
                 .enable_resources()
+                .enable_resources_subscribe()
+                .enable_resources_list_changed()
+                .enable_prompts()
+                .enable_completions()
+                .enable_logging()
                 .build(),
             server_info: Implementation::from_build_env(),
             instructions: Some(
@@ -1262,9 +2364,20 @@ impl ServerHandler for AirbnbMcpServer {
                  - airbnb_market_comparison: compare 2-5 neighborhoods side-by-side\n\
                  - airbnb_host_portfolio: analyze a host's full property portfolio\n\
                  - airbnb_review_sentiment: keyword-based sentiment analysis of guest reviews\n\
+                 - airbnb_review_summary: pros/cons review summary with citations, written by your model via sampling\n\
                  - airbnb_competitive_positioning: 5-axis competitive score vs neighborhood\n\
                  - airbnb_optimal_pricing: data-driven pricing recommendation with reasoning\n\
                  \n\
+                 ## Cache Tools (only when the server operator enables them)\n\
+                 - airbnb_cache_stats: hits, misses, evictions and entries per key prefix\n\
+                 - airbnb_cache_invalidate: drop cached data by key prefix so it is fetched again\n\
+                 Every data and analytical tool also accepts fresh: true to skip cached data for one call; \
+                 answers built from cached data end with a note saying when it was fetched.\n\
+                 \n\
+                 ## Prompts\n\
+                 Ready-made workflows that chain the tools above: listing_audit (id), \
+                 investment_analysis (location, budget) and trip_shortlist (location, dates, guests).\n\
+                 \n\
                  ## Resources\n\
                  Data fetched by tools is cached as MCP resources. Use resource URIs to reference \
                  previously fetched data without re-scraping.\n\
@@ -1281,21 +2394,31 @@ impl ServerHandler for AirbnbMcpServer {
 
     async fn list_resources(
         &self,
-        _request: Option<PaginatedRequestParams>,
+        request: Option<PaginatedRequestParams>,
         _context: RequestContext<RoleServer>,
     ) -> Result<ListResourcesResult, McpError> {
+        let after = request
+            .and_then(|r| r.cursor)
+            .map(|cursor| decode_cursor(&cursor))
+            .transpose()?;
         let entries = self.resources.list().await;
+        let start = after.map_or(0, |after| entries.partition_point(|e| e.uri <= after));
+        let end = entries.len().min(start + self.resources.page_size());
+        let next_cursor = (end < entries.len()).then(|| encode_cursor(&entries[end - 1].uri));
+
         let resources: Vec<Resource> = entries
             .into_iter()
-            .map(|(uri, name)| Resource {
+            .skip(start)
+            .take(end - start)
+            .map(|summary| Resource {
                 annotations: None,
                 raw: RawResource {
-                    uri,
-                    name,
+                    description: ResourceUri::parse(&summary.uri).map(|uri| uri.description()),
+                    uri: summary.uri,
+                    name: summary.name,
                     title: None,
-                    description: None,
                     mime_type: Some("text/plain".into()),
-                    size: None,
+                    size: u32::try_from(summary.size).ok(),
                     icons: None,
                     meta: None,
                 },
@@ -1303,7 +2426,7 @@ impl ServerHandler for AirbnbMcpServer {
             .collect();
         Ok(ListResourcesResult {
             resources,
-            next_cursor: None,
+            next_cursor,
             meta: None,
         })
     }
@@ -1322,7 +2445,7 @@ impl ServerHandler for AirbnbMcpServer {
                     name: "Airbnb Listing".into(),
                     title: Some("Listing details".into()),
                     description: Some(
-                        "Full listing details (fetched via airbnb_listing_details)".into(),
+                        "Full listing details (fetched via airbnb_listing_details, or live on first read)".into(),
                     ),
                     mime_type: Some("text/plain".into()),
                     icons: None,
@@ -1335,7 +2458,7 @@ impl ServerHandler for AirbnbMcpServer {
                     name: "Price Calendar".into(),
                     title: Some("Price & availability calendar".into()),
                     description: Some(
-                        "Daily prices and availability (fetched via airbnb_price_calendar)".into(),
+                        "Daily prices and availability (fetched via airbnb_price_calendar, or live on first read)".into(),
                     ),
                     mime_type: Some("text/plain".into()),
                     icons: None,
@@ -1348,7 +2471,7 @@ impl ServerHandler for AirbnbMcpServer {
                     name: "Reviews".into(),
                     title: Some("Guest reviews".into()),
                     description: Some(
-                        "Guest reviews and ratings (fetched via airbnb_reviews)".into(),
+                        "Guest reviews and ratings (fetched via airbnb_reviews, or live on first read)".into(),
                     ),
                     mime_type: Some("text/plain".into()),
                     icons: None,
@@ -1361,7 +2484,7 @@ impl ServerHandler for AirbnbMcpServer {
                     name: "Host Profile".into(),
                     title: Some("Host profile".into()),
                     description: Some(
-                        "Host bio, superhost status, response rate (fetched via airbnb_host_profile)"
+                        "Host bio, superhost status, response rate (fetched via airbnb_host_profile, or live on first read)"
                             .into(),
                     ),
                     mime_type: Some("text/plain".into()),
@@ -1375,7 +2498,7 @@ impl ServerHandler for AirbnbMcpServer {
                     name: "Occupancy Estimate".into(),
                     title: Some("Occupancy estimate".into()),
                     description: Some(
-                        "Occupancy rate and revenue breakdown (fetched via airbnb_occupancy_estimate)"
+                        "Occupancy rate and revenue breakdown (fetched via airbnb_occupancy_estimate, or live on first read)"
                             .into(),
                     ),
                     mime_type: Some("text/plain".into()),
@@ -1389,7 +2512,7 @@ impl ServerHandler for AirbnbMcpServer {
                     name: "Search Results".into(),
                     title: Some("Search results".into()),
                     description: Some(
-                        "Listings found for a location (fetched via airbnb_search)".into(),
+                        "Listings found for a location (fetched via airbnb_search, or live on first read)".into(),
                     ),
                     mime_type: Some("text/plain".into()),
                     icons: None,
@@ -1402,7 +2525,7 @@ impl ServerHandler for AirbnbMcpServer {
                     name: "Neighborhood Stats".into(),
                     title: Some("Neighborhood statistics".into()),
                     description: Some(
-                        "Area-level price/rating stats (fetched via airbnb_neighborhood_stats)"
+                        "Area-level price/rating stats (fetched via airbnb_neighborhood_stats, or live on first read)"
                             .into(),
                     ),
                     mime_type: Some("text/plain".into()),
@@ -1434,17 +2557,73 @@ impl ServerHandler for AirbnbMcpServer {
     async fn read_resource(
         &self,
         request: ReadResourceRequestParams,
-        _context: RequestContext<RoleServer>,
+        context: RequestContext<RoleServer>,
     ) -> Result<ReadResourceResult, McpError> {
-        match self.resources.get(&request.uri).await {
-            Some(entry) => Ok(ReadResourceResult {
-                contents: vec![ResourceContents::text(entry.text, request.uri)],
-            }),
-            None => Err(McpError::resource_not_found(
-                format!("resource not found: {}", request.uri),
-                None,
-            )),
-        }
+        logging::in_session(
+            self.resources.session,
+            self.resolve_resource(request, context),
+        )
+        .await
+    }
+
+    async fn subscribe(
+        &self,
+        request: SubscribeRequestParams,
+        context: RequestContext<RoleServer>,
+    ) -> Result<(), McpError> {
+        self.resources
+            .notifier
+            .subscribe(
+                self.resources.session,
+                context.peer,
+                canonical_uri(&request.uri),
+            )
+            .await;
+        Ok(())
+    }
+
+    async fn unsubscribe(
+        &self,
+        request: UnsubscribeRequestParams,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<(), McpError> {
+        self.resources
+            .notifier
+            .unsubscribe(self.resources.session, &canonical_uri(&request.uri))
+            .await;
+        Ok(())
+    }
+
+    /// Suggest `id` and `location` values for prompt arguments and resource template
+    /// variables. MCP has no completion for tool arguments, so tools are not covered.
+    async fn complete(
+        &self,
+        request: CompleteRequestParams,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<CompleteResult, McpError> {
+        let candidates = self.completion_candidates(&request.argument.name).await;
+        Ok(CompleteResult {
+            completion: completion_info(candidates, &request.argument.value),
+        })
+    }
+
+    async fn set_level(
+        &self,
+        request: SetLevelRequestParams,
+        context: RequestContext<RoleServer>,
+    ) -> Result<(), McpError> {
+        self.logs
+            .set_level(self.resources.session, context.peer, request.level);
+        Ok(())
+    }
+
+    async fn on_initialized(&self, context: NotificationContext<RoleServer>) {
+        self.logs
+            .register(self.resources.session, context.peer.clone());
+        self.resources
+            .notifier
+            .register(self.resources.session, context.peer)
+            .await;
     }
 }
 
@@ -1454,6 +2633,7 @@ mod tests {
     use crate::domain::calendar::{CalendarDay, PriceCalendar};
     use crate::error::AirbnbError;
     use crate::test_helpers::*;
+    use tokio_util::sync::CancellationToken;
 
     fn extract_text(result: &CallToolResult) -> &str {
         result.content[0]
@@ -1468,6 +2648,10 @@ mod tests {
         AirbnbMcpServer::new(Arc::new(mock))
     }
 
+    fn no_progress() -> ToolProgress {
+        ToolProgress::detached(CancellationToken::new())
+    }
+
     #[tokio::test]
     async fn search_returns_formatted_listings() {
         let mock = MockAirbnbClient::new().with_search(|_| {
@@ -1478,19 +2662,22 @@ mod tests {
         });
         let server = make_server(mock);
         let result = server
-            .airbnb_search(Parameters(SearchToolParams {
-                location: "Paris".into(),
-                checkin: None,
-                checkout: None,
-                adults: None,
-                children: None,
-                infants: None,
-                pets: None,
-                min_price: None,
-                max_price: None,
-                property_type: None,
-                cursor: None,
-            }))
+            .airbnb_search(
+                Parameters(SearchToolParams {
+                    location: "Paris".into(),
+                    checkin: None,
+                    checkout: None,
+                    adults: None,
+                    children: None,
+                    infants: None,
+                    pets: None,
+                    min_price: None,
+                    max_price: None,
+                    property_type: None,
+                    cursor: None,
+                }),
+                Elicitor::unavailable(),
+            )
             .await
             .unwrap();
 
@@ -1509,19 +2696,22 @@ mod tests {
         let mock = MockAirbnbClient::new().with_search(|_| Ok(make_search_result(vec![])));
         let server = make_server(mock);
         let result = server
-            .airbnb_search(Parameters(SearchToolParams {
-                location: "Nowhere".into(),
-                checkin: None,
-                checkout: None,
-                adults: None,
-                children: None,
-                infants: None,
-                pets: None,
-                min_price: None,
-                max_price: None,
-                property_type: None,
-                cursor: None,
-            }))
+            .airbnb_search(
+                Parameters(SearchToolParams {
+                    location: "Nowhere".into(),
+                    checkin: None,
+                    checkout: None,
+                    adults: None,
+                    children: None,
+                    infants: None,
+                    pets: None,
+                    min_price: None,
+                    max_price: None,
+                    property_type: None,
+                    cursor: None,
+                }),
+                Elicitor::unavailable(),
+            )
             .await
             .unwrap();
 
@@ -1538,19 +2728,22 @@ mod tests {
         });
         let server = make_server(mock);
         let result = server
-            .airbnb_search(Parameters(SearchToolParams {
-                location: "Tokyo".into(),
-                checkin: None,
-                checkout: None,
-                adults: None,
-                children: None,
-                infants: None,
-                pets: None,
-                min_price: None,
-                max_price: None,
-                property_type: None,
-                cursor: None,
-            }))
+            .airbnb_search(
+                Parameters(SearchToolParams {
+                    location: "Tokyo".into(),
+                    checkin: None,
+                    checkout: None,
+                    adults: None,
+                    children: None,
+                    infants: None,
+                    pets: None,
+                    min_price: None,
+                    max_price: None,
+                    property_type: None,
+                    cursor: None,
+                }),
+                Elicitor::unavailable(),
+            )
             .await
             .unwrap();
 
@@ -1564,19 +2757,22 @@ mod tests {
         let mock = MockAirbnbClient::new().with_search(|_| Err(AirbnbError::RateLimited));
         let server = make_server(mock);
         let result = server
-            .airbnb_search(Parameters(SearchToolParams {
-                location: "Paris".into(),
-                checkin: None,
-                checkout: None,
-                adults: None,
-                children: None,
-                infants: None,
-                pets: None,
-                min_price: None,
-                max_price: None,
-                property_type: None,
-                cursor: None,
-            }))
+            .airbnb_search(
+                Parameters(SearchToolParams {
+                    location: "Paris".into(),
+                    checkin: None,
+                    checkout: None,
+                    adults: None,
+                    children: None,
+                    infants: None,
+                    pets: None,
+                    min_price: None,
+                    max_price: None,
+                    property_type: None,
+                    cursor: None,
+                }),
+                Elicitor::unavailable(),
+            )
             .await
             .unwrap();
 
@@ -1916,19 +3112,22 @@ mod tests {
         });
         let server = make_server(mock);
         let _ = server
-            .airbnb_search(Parameters(SearchToolParams {
-                location: "Paris".into(),
-                checkin: Some("2025-07-01".into()),
-                checkout: Some("2025-07-05".into()),
-                adults: Some(2),
-                children: Some(1),
-                infants: Some(0),
-                pets: Some(1),
-                min_price: Some(50),
-                max_price: Some(200),
-                property_type: Some("Entire home".into()),
-                cursor: Some("page2".into()),
-            }))
+            .airbnb_search(
+                Parameters(SearchToolParams {
+                    location: "Paris".into(),
+                    checkin: Some("2025-07-01".into()),
+                    checkout: Some("2025-07-05".into()),
+                    adults: Some(2),
+                    children: Some(1),
+                    infants: Some(0),
+                    pets: Some(1),
+                    min_price: Some(50),
+                    max_price: Some(200),
+                    property_type: Some("Entire home".into()),
+                    cursor: Some("page2".into()),
+                }),
+                Elicitor::unavailable(),
+            )
             .await
             .unwrap();
 
@@ -2003,19 +3202,22 @@ mod tests {
         });
         let server = make_server(mock);
         let result = server
-            .airbnb_search(Parameters(SearchToolParams {
-                location: "Bali".into(),
-                checkin: None,
-                checkout: None,
-                adults: None,
-                children: None,
-                infants: None,
-                pets: None,
-                min_price: None,
-                max_price: None,
-                property_type: None,
-                cursor: None,
-            }))
+            .airbnb_search(
+                Parameters(SearchToolParams {
+                    location: "Bali".into(),
+                    checkin: None,
+                    checkout: None,
+                    adults: None,
+                    children: None,
+                    infants: None,
+                    pets: None,
+                    min_price: None,
+                    max_price: None,
+                    property_type: None,
+                    cursor: None,
+                }),
+                Elicitor::unavailable(),
+            )
             .await
             .unwrap();
         let text = extract_text(&result);
@@ -2037,19 +3239,22 @@ mod tests {
         });
         let server = make_server(mock);
         let result = server
-            .airbnb_search(Parameters(SearchToolParams {
-                location: "Tokyo".into(),
-                checkin: None,
-                checkout: None,
-                adults: None,
-                children: None,
-                infants: None,
-                pets: None,
-                min_price: None,
-                max_price: None,
-                property_type: None,
-                cursor: None,
-            }))
+            .airbnb_search(
+                Parameters(SearchToolParams {
+                    location: "Tokyo".into(),
+                    checkin: None,
+                    checkout: None,
+                    adults: None,
+                    children: None,
+                    infants: None,
+                    pets: None,
+                    min_price: None,
+                    max_price: None,
+                    property_type: None,
+                    cursor: None,
+                }),
+                Elicitor::unavailable(),
+            )
             .await
             .unwrap();
         let text = extract_text(&result);
@@ -2216,14 +3421,17 @@ mod tests {
         });
         let server = make_server(mock);
         let result = server
-            .airbnb_compare_listings(Parameters(CompareListingsToolParams {
-                ids: None,
-                location: Some("Paris".into()),
-                max_listings: Some(20),
-                checkin: None,
-                checkout: None,
-                property_type: None,
-            }))
+            .airbnb_compare_listings(
+                Parameters(CompareListingsToolParams {
+                    ids: None,
+                    location: Some("Paris".into()),
+                    max_listings: Some(20),
+                    checkin: None,
+                    checkout: None,
+                    property_type: None,
+                }),
+                no_progress(),
+            )
             .await
             .unwrap();
 
@@ -2233,58 +3441,203 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn compare_listings_requires_ids_or_location() {
-        let mock = MockAirbnbClient::new();
+    async fn compare_listings_cancelled_returns_partial_comparison() {
+        let ct = CancellationToken::new();
+        let cancel = ct.clone();
+        let mock = MockAirbnbClient::new().with_search(move |_| {
+            // The client cancels while the first page is in flight
+            cancel.cancel();
+            let listings = (0..20)
+                .map(|i| make_listing(&i.to_string(), "L", 100.0 + f64::from(i)))
+                .collect();
+            let mut result = make_search_result(listings);
+            result.next_cursor = Some("next".into());
+            Ok(result)
+        });
         let server = make_server(mock);
         let result = server
-            .airbnb_compare_listings(Parameters(CompareListingsToolParams {
-                ids: None,
-                location: None,
-                max_listings: None,
-                checkin: None,
-                checkout: None,
-                property_type: None,
-            }))
+            .airbnb_compare_listings(
+                Parameters(CompareListingsToolParams {
+                    ids: None,
+                    location: Some("Paris".into()),
+                    max_listings: Some(60),
+                    checkin: None,
+                    checkout: None,
+                    property_type: None,
+                }),
+                ToolProgress::detached(ct),
+            )
             .await
             .unwrap();
 
-        assert_eq!(result.is_error, Some(true));
+        let text = extract_text(&result);
+        assert!(
+            text.contains("Cancelled after 1 of 3 search pages"),
+            "{text}"
+        );
+        assert!(text.contains("Listing Comparison (20 listings)"));
+        assert!(
+            server
+                .resources
+                .get("airbnb://analysis/compare/Paris")
+                .await
+                .is_none()
+        );
     }
 
     #[tokio::test]
-    async fn revenue_estimate_success() {
-        let mock = MockAirbnbClient::new()
-            .with_calendar(|id, _| {
-                let days = vec![
-                    make_calendar_day("2025-06-01", Some(100.0), true),
-                    make_calendar_day("2025-06-02", Some(100.0), false),
-                ];
-                Ok(make_price_calendar(id, days))
-            })
-            .with_occupancy(|id, _| Ok(make_occupancy_estimate(id)));
+    async fn compare_listings_requires_ids_or_location() {
+        let mock = MockAirbnbClient::new();
         let server = make_server(mock);
         let result = server
-            .airbnb_revenue_estimate(Parameters(RevenueEstimateToolParams {
-                id: Some("42".into()),
-                location: Some("Paris".into()),
-                months: Some(12),
-            }))
+            .airbnb_compare_listings(
+                Parameters(CompareListingsToolParams {
+                    ids: None,
+                    location: None,
+                    max_listings: None,
+                    checkin: None,
+                    checkout: None,
+                    property_type: None,
+                }),
+                no_progress(),
+            )
             .await
             .unwrap();
 
-        let text = extract_text(&result);
-        assert!(text.contains("Revenue Estimate"));
-        assert!(result.is_error.is_none() || result.is_error == Some(false));
+        assert_eq!(result.is_error, Some(true));
     }
 
-    #[tokio::test]
-    async fn revenue_estimate_requires_id_or_location() {
-        let mock = MockAirbnbClient::new();
-        let server = make_server(mock);
-        let result = server
-            .airbnb_revenue_estimate(Parameters(RevenueEstimateToolParams {
-                id: None,
-                location: None,
+    #[test]
+    fn tool_config_filters_tools_and_sets_schema_limits() {
+        let config = ToolsConfig {
+            allow: Some(vec![
+                "airbnb_search".into(),
+                "airbnb_review_sentiment".into(),
+                "airbnb_review_summary".into(),
+            ]),
+            deny: vec!["airbnb_search".into()],
+            limits: ToolLimits {
+                max_review_pages: 4,
+                ..ToolLimits::default()
+            },
+        };
+        let server = make_server(MockAirbnbClient::new())
+            .with_tools(&config)
+            .unwrap();
+        let tools = server.tool_router.list_all();
+        let names: Vec<&str> = tools.iter().map(|t| t.name.as_ref()).collect();
+        assert_eq!(names, ["airbnb_review_sentiment", "airbnb_review_summary"]);
+        assert_eq!(
+            tools[0].input_schema["properties"]["max_pages"]["maximum"],
+            4
+        );
+    }
+
+    #[test]
+    fn tool_config_rejects_unknown_names() {
+        let config = ToolsConfig {
+            deny: vec!["price-trends".into()],
+            ..ToolsConfig::default()
+        };
+        let err = make_server(MockAirbnbClient::new())
+            .with_tools(&config)
+            .err()
+            .unwrap()
+            .to_string();
+        assert!(
+            err.contains("tools.deny: unknown tool 'price-trends'"),
+            "{err}"
+        );
+        assert!(err.contains("did you mean 'airbnb_price_trends'?"), "{err}");
+    }
+
+    #[tokio::test]
+    async fn tool_limits_cap_compare_ids_and_review_pages() {
+        let mock = MockAirbnbClient::new()
+            .with_search(|_| Ok(make_search_result(vec![])))
+            .with_detail(|id| Ok(make_listing_detail(id)))
+            .with_reviews(|id, cursor| {
+                let page: u32 = cursor.map_or(1, |c| c.parse().unwrap());
+                let mut reviews = make_reviews_page(id, vec![make_review("Guest", "Lovely stay")]);
+                reviews.next_cursor = Some((page + 1).to_string());
+                Ok(reviews)
+            });
+        let config = ToolsConfig {
+            limits: ToolLimits {
+                max_compare_ids: 3,
+                max_review_pages: 2,
+                ..ToolLimits::default()
+            },
+            ..ToolsConfig::default()
+        };
+        let server = make_server(mock).with_tools(&config).unwrap();
+
+        let result = server
+            .airbnb_compare_listings(
+                Parameters(CompareListingsToolParams {
+                    ids: Some((1..=6).map(|i| i.to_string()).collect()),
+                    location: None,
+                    max_listings: None,
+                    checkin: None,
+                    checkout: None,
+                    property_type: None,
+                }),
+                no_progress(),
+            )
+            .await
+            .unwrap();
+        assert!(extract_text(&result).contains("Listing Comparison (3 listings)"));
+
+        let result = server
+            .airbnb_review_sentiment(
+                Parameters(ReviewSentimentToolParams {
+                    id: "42".into(),
+                    max_pages: Some(10),
+                }),
+                no_progress(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(
+            result.structured_content.unwrap()["total_reviews_analyzed"],
+            2
+        );
+    }
+
+    #[tokio::test]
+    async fn revenue_estimate_success() {
+        let mock = MockAirbnbClient::new()
+            .with_calendar(|id, _| {
+                let days = vec![
+                    make_calendar_day("2025-06-01", Some(100.0), true),
+                    make_calendar_day("2025-06-02", Some(100.0), false),
+                ];
+                Ok(make_price_calendar(id, days))
+            })
+            .with_occupancy(|id, _| Ok(make_occupancy_estimate(id)));
+        let server = make_server(mock);
+        let result = server
+            .airbnb_revenue_estimate(Parameters(RevenueEstimateToolParams {
+                id: Some("42".into()),
+                location: Some("Paris".into()),
+                months: Some(12),
+            }))
+            .await
+            .unwrap();
+
+        let text = extract_text(&result);
+        assert!(text.contains("Revenue Estimate"));
+        assert!(result.is_error.is_none() || result.is_error == Some(false));
+    }
+
+    #[tokio::test]
+    async fn revenue_estimate_requires_id_or_location() {
+        let mock = MockAirbnbClient::new();
+        let server = make_server(mock);
+        let result = server
+            .airbnb_revenue_estimate(Parameters(RevenueEstimateToolParams {
+                id: None,
+                location: None,
                 months: None,
             }))
             .await
@@ -2394,6 +3747,121 @@ mod tests {
         assert!(text.contains("Host Portfolio: Test Host"));
     }
 
+    // ---- Completion tests ----
+
+    #[tokio::test]
+    async fn completion_candidates_come_from_searches_and_resources() {
+        let mock = MockAirbnbClient::new()
+            .with_search(|_| {
+                Ok(make_search_result(vec![
+                    make_listing("101", "Cozy Flat", 100.0),
+                    make_listing("102", "Beach House", 250.0),
+                ]))
+            })
+            .with_detail(|id| Ok(make_listing_detail(id)));
+        let server = make_server(mock);
+        let _ = server
+            .airbnb_search(
+                Parameters(SearchToolParams {
+                    location: "Paris, France".into(),
+                    checkin: None,
+                    checkout: None,
+                    adults: None,
+                    children: None,
+                    infants: None,
+                    pets: None,
+                    min_price: None,
+                    max_price: None,
+                    property_type: None,
+                    cursor: None,
+                }),
+                Elicitor::unavailable(),
+            )
+            .await
+            .unwrap();
+        let _ = server
+            .airbnb_listing_details(Parameters(DetailToolParams { id: "7".into() }))
+            .await
+            .unwrap();
+
+        let ids: Vec<_> = server
+            .completion_candidates("id")
+            .await
+            .into_iter()
+            .collect();
+        assert_eq!(ids, ["101", "102", "7"]);
+        let locations: Vec<_> = server
+            .completion_candidates("location")
+            .await
+            .into_iter()
+            .collect();
+        assert_eq!(locations, ["Paris, France"]);
+        assert!(server.completion_candidates("budget").await.is_empty());
+    }
+
+    #[test]
+    fn format_bytes_picks_a_unit() {
+        assert_eq!(format_bytes(512), "512 B");
+        assert_eq!(format_bytes(1536), "1.5 KiB");
+        assert_eq!(format_bytes(64 * 1024 * 1024), "64.0 MiB");
+    }
+
+    #[test]
+    fn cache_note_reports_oldest_age_and_stale_keys() {
+        let now = DateTime::UNIX_EPOCH + chrono::Duration::days(1);
+        assert!(cache_note(&[], now).is_none());
+        let read = |key: &str, secs, stale| CacheRead {
+            key: key.into(),
+            fetched_at: now - chrono::Duration::seconds(secs),
+            stale,
+        };
+        let note = cache_note(
+            &[read("search:1", 60, false), read("host:1", 150, false)],
+            now,
+        )
+        .unwrap();
+        assert_eq!(
+            note,
+            "ℹ️ Served from cache: fetched up to 2m ago (oldest at 1970-01-01 23:57 UTC)."
+        );
+        let note = cache_note(
+            &[
+                read("detail:1", 7500, true),
+                read("calendar:1:m=3", 90, true),
+                read("host:1", 30, false),
+                read("detail:1", 7500, true),
+            ],
+            now,
+        )
+        .unwrap();
+        assert!(note.contains("up to 2h 5m old"), "{note}");
+        assert!(note.contains("(`calendar:1:m=3`, `detail:1`)"), "{note}");
+        assert_eq!(format_age(Duration::from_secs(45)), "45s");
+        assert_eq!(format_age(Duration::from_hours(25)), "1d 1h");
+    }
+
+    #[test]
+    fn completion_info_filters_by_prefix_ignoring_case() {
+        let candidates: BTreeSet<String> = ["Paris", "Porto", "parma", "Lisbon"]
+            .into_iter()
+            .map(String::from)
+            .collect();
+        let info = completion_info(candidates.clone(), "pa");
+        assert_eq!(info.values, ["Paris", "parma"]);
+        assert_eq!(info.total, Some(2));
+        assert_eq!(info.has_more, Some(false));
+        assert_eq!(completion_info(candidates, "").values.len(), 4);
+    }
+
+    #[test]
+    fn completion_info_caps_values() {
+        let candidates: BTreeSet<String> = (0..150).map(|i| format!("{i:03}")).collect();
+        let info = completion_info(candidates, "");
+        assert_eq!(info.values.len(), CompletionInfo::MAX_VALUES);
+        assert_eq!(info.total, Some(150));
+        assert_eq!(info.has_more, Some(true));
+    }
+
     // ---- Resource store tests ----
 
     #[tokio::test]
@@ -2428,6 +3896,70 @@ mod tests {
         assert_eq!(list.len(), 2);
     }
 
+    #[tokio::test]
+    async fn resource_store_insert_reports_added_then_updated() {
+        let store = ResourceStore::default();
+        let first = store
+            .insert("airbnb://listing/1", "Listing 1", "v1".into())
+            .await;
+        let second = store
+            .insert("airbnb://listing/1", "Listing 1", "v2".into())
+            .await;
+        assert_eq!(first, ResourceChange::Added);
+        assert_eq!(second, ResourceChange::Updated);
+        assert_eq!(store.get("airbnb://listing/1").await.unwrap().text, "v2");
+    }
+
+    #[tokio::test]
+    async fn resource_store_expired_entry_is_missing_and_re_added() {
+        let store = ResourceStore::default();
+        store.entries.write().await.put(
+            "airbnb://search/Paris".into(),
+            ResourceEntry {
+                name: "Search: Paris".into(),
+                text: "old".into(),
+                expires_at: Some(Instant::now()),
+            },
+        );
+
+        assert!(store.get("airbnb://search/Paris").await.is_none());
+        assert!(store.list().await.is_empty());
+        let change = store
+            .insert("airbnb://search/Paris", "Search: Paris", "new".into())
+            .await;
+        assert_eq!(change, ResourceChange::Added);
+    }
+
+    #[test]
+    fn resource_store_ttl_depends_on_kind() {
+        let store = ResourceStore::new(&ResourceConfig {
+            search_ttl_secs: 60,
+            calendar_ttl_secs: 120,
+            analysis_ttl_secs: 0,
+            ..ResourceConfig::default()
+        });
+        assert_eq!(
+            store.ttl("airbnb://search/Paris"),
+            Some(Duration::from_mins(1))
+        );
+        assert_eq!(
+            store.ttl("airbnb://listing/1/calendar"),
+            Some(Duration::from_mins(2))
+        );
+        assert_eq!(
+            store.ttl("airbnb://listing/1/reviews"),
+            Some(Duration::from_hours(1))
+        );
+        assert_eq!(store.ttl("airbnb://analysis/gaps/1"), None);
+    }
+
+    #[test]
+    fn cursor_round_trips_and_rejects_garbage() {
+        let uri = "airbnb://search/Paris%2C%20France";
+        assert_eq!(decode_cursor(&encode_cursor(uri)).unwrap(), uri);
+        assert!(decode_cursor("not a cursor!").is_err());
+    }
+
     #[tokio::test]
     async fn resource_store_get_missing_returns_none() {
         let store = ResourceStore::default();
@@ -2458,26 +3990,80 @@ mod tests {
         let server = make_server(mock);
 
         let _ = server
-            .airbnb_search(Parameters(SearchToolParams {
-                location: "Paris".into(),
+            .airbnb_search(
+                Parameters(SearchToolParams {
+                    location: "Paris".into(),
+                    checkin: None,
+                    checkout: None,
+                    adults: None,
+                    children: None,
+                    infants: None,
+                    pets: None,
+                    min_price: None,
+                    max_price: None,
+                    property_type: None,
+                    cursor: None,
+                }),
+                Elicitor::unavailable(),
+            )
+            .await
+            .unwrap();
+
+        let entry = server.resources.get("airbnb://search/Paris").await;
+        assert!(entry.is_some());
+    }
+
+    #[tokio::test]
+    async fn resource_uri_percent_encodes_location() {
+        let mock =
+            MockAirbnbClient::new().with_neighborhood(|p| Ok(make_neighborhood_stats(&p.location)));
+        let server = make_server(mock);
+
+        let _ = server
+            .airbnb_neighborhood_stats(Parameters(NeighborhoodStatsToolParams {
+                location: "New York, NY".into(),
                 checkin: None,
                 checkout: None,
-                adults: None,
-                children: None,
-                infants: None,
-                pets: None,
-                min_price: None,
-                max_price: None,
                 property_type: None,
-                cursor: None,
             }))
             .await
             .unwrap();
 
-        let entry = server.resources.get("airbnb://search/Paris").await;
+        let entry = server
+            .resources
+            .get("airbnb://neighborhood/New%20York%2C%20NY")
+            .await;
         assert!(entry.is_some());
     }
 
+    #[tokio::test]
+    async fn fetch_resource_populates_store() {
+        let mock = MockAirbnbClient::new()
+            .with_calendar(|id, _| Ok(make_price_calendar(id, vec![])))
+            .with_detail(|id| Err(AirbnbError::ListingNotFound { id: id.to_string() }));
+        let server = make_server(mock);
+
+        let result = server
+            .fetch_resource(ResourceUri::Calendar("42".into()))
+            .await
+            .unwrap();
+        assert_ne!(result.is_error, Some(true));
+        assert!(
+            server
+                .resources
+                .get("airbnb://listing/42/calendar")
+                .await
+                .is_some()
+        );
+
+        let missing = server
+            .fetch_resource(ResourceUri::Listing("999".into()))
+            .await
+            .unwrap();
+        assert_eq!(missing.is_error, Some(true));
+        assert!(server.resources.get("airbnb://listing/999").await.is_none());
+    }
+
     #[tokio::test]
     async fn resource_stored_after_calendar() {
         let mock = MockAirbnbClient::new().with_calendar(|id, _| {
@@ -2500,6 +4086,206 @@ mod tests {
         assert!(entry.is_some());
     }
 
+    #[tokio::test]
+    async fn isolated_sessions_share_listings_but_not_analysis() {
+        let mock = MockAirbnbClient::new().with_calendar(|id, _| {
+            Ok(make_price_calendar(
+                id,
+                vec![make_calendar_day("2025-06-01", Some(100.0), true)],
+            ))
+        });
+        let server = make_server(mock).with_session_mode(SessionMode::Isolated);
+        let alice = server.for_session();
+        let bob = server.for_session();
+
+        let _ = alice
+            .airbnb_price_calendar(Parameters(CalendarToolParams {
+                id: "42".into(),
+                months: None,
+            }))
+            .await
+            .unwrap();
+        let _ = alice
+            .airbnb_gap_finder(Parameters(GapFinderToolParams {
+                id: "42".into(),
+                months: None,
+            }))
+            .await
+            .unwrap();
+
+        // Public listing data is read through the shared layer
+        assert!(
+            bob.resources
+                .get("airbnb://listing/42/calendar")
+                .await
+                .is_some()
+        );
+        // Analysis results stay private to the session that produced them
+        assert!(
+            alice
+                .resources
+                .get("airbnb://analysis/gaps/42")
+                .await
+                .is_some()
+        );
+        assert!(
+            bob.resources
+                .get("airbnb://analysis/gaps/42")
+                .await
+                .is_none()
+        );
+        assert_eq!(bob.resources.list().await.len(), 1);
+        assert_eq!(alice.resources.list().await.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn isolated_sessions_do_not_share_search_prices() {
+        let mock = MockAirbnbClient::new()
+            .with_search(|_| Ok(make_search_result(vec![make_listing("7", "Loft", 321.0)])))
+            .with_detail(|id| {
+                let mut d = make_listing_detail(id);
+                d.price_per_night = 0.0;
+                Ok(d)
+            });
+        let server = make_server(mock).with_session_mode(SessionMode::Isolated);
+        let alice = server.for_session();
+        let bob = server.for_session();
+
+        let _ = alice
+            .airbnb_search(
+                Parameters(SearchToolParams {
+                    location: "Paris".into(),
+                    checkin: None,
+                    checkout: None,
+                    adults: None,
+                    children: None,
+                    infants: None,
+                    pets: None,
+                    min_price: None,
+                    max_price: None,
+                    property_type: None,
+                    cursor: None,
+                }),
+                Elicitor::unavailable(),
+            )
+            .await
+            .unwrap();
+
+        let alice_detail = alice.get_detail_with_price("7").await.unwrap();
+        let bob_detail = bob.get_detail_with_price("7").await.unwrap();
+        assert!((alice_detail.price_per_night - 321.0).abs() < f64::EPSILON);
+        assert!(bob_detail.price_per_night.abs() < f64::EPSILON);
+        assert!(bob.resources.get("airbnb://search/Paris").await.is_none());
+    }
+
+    #[tokio::test]
+    async fn shared_sessions_see_each_others_analysis() {
+        let server = make_server(MockAirbnbClient::new()).with_session_mode(SessionMode::Shared);
+        let alice = server.for_session();
+        let bob = server.for_session();
+
+        let _ = alice
+            .airbnb_gap_finder(Parameters(GapFinderToolParams {
+                id: "42".into(),
+                months: None,
+            }))
+            .await
+            .unwrap();
+
+        assert!(
+            bob.resources
+                .get("airbnb://analysis/gaps/42")
+                .await
+                .is_some()
+        );
+    }
+
+    #[test]
+    fn every_tool_declares_object_output_schema() {
+        let server = make_server(MockAirbnbClient::new());
+        for tool in server.tool_router.list_all() {
+            let schema = tool
+                .output_schema
+                .unwrap_or_else(|| panic!("{} has no output schema", tool.name));
+            assert_eq!(schema.get("type"), Some(&serde_json::json!("object")));
+        }
+    }
+
+    #[tokio::test]
+    async fn listing_details_returns_structured_content() {
+        let mock = MockAirbnbClient::new().with_detail(|id| Ok(make_listing_detail(id)));
+        let server = make_server(mock);
+        let result = server
+            .airbnb_listing_details(Parameters(DetailToolParams { id: "42".into() }))
+            .await
+            .unwrap();
+
+        // Markdown stays the first content block, typed data rides alongside it
+        assert!(extract_text(&result).contains("42"));
+        let detail: crate::domain::listing::ListingDetail =
+            result.into_typed().expect("structured listing detail");
+        assert_eq!(detail.id, "42");
+    }
+
+    #[tokio::test]
+    async fn price_trends_returns_structured_content() {
+        let mock = MockAirbnbClient::new().with_calendar(|id, _| {
+            let days = vec![
+                make_calendar_day("2025-06-06", Some(200.0), true),
+                make_calendar_day("2025-06-09", Some(100.0), true),
+            ];
+            Ok(make_price_calendar(id, days))
+        });
+        let server = make_server(mock);
+        let result = server
+            .airbnb_price_trends(Parameters(PriceTrendsToolParams {
+                id: "42".into(),
+                months: Some(6),
+            }))
+            .await
+            .unwrap();
+
+        let structured = result.structured_content.as_ref().unwrap();
+        assert_eq!(structured["listing_id"], "42");
+        assert!((structured["overall_avg"].as_f64().unwrap() - 150.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn unserializable_output_is_an_internal_error() {
+        // JSON objects need string keys
+        let data: HashMap<(u8, u8), u8> = HashMap::from([((1, 2), 3)]);
+        let error = structured_success("text".into(), &data).unwrap_err();
+        assert_eq!(error.code, rmcp::model::ErrorCode::INTERNAL_ERROR);
+    }
+
+    #[tokio::test]
+    async fn error_results_have_no_structured_content() {
+        let mock = MockAirbnbClient::new().with_search(|_| Err(AirbnbError::RateLimited));
+        let server = make_server(mock);
+        let result = server
+            .airbnb_search(
+                Parameters(SearchToolParams {
+                    location: "Paris".into(),
+                    checkin: None,
+                    checkout: None,
+                    adults: None,
+                    children: None,
+                    infants: None,
+                    pets: None,
+                    min_price: None,
+                    max_price: None,
+                    property_type: None,
+                    cursor: None,
+                }),
+                Elicitor::unavailable(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(result.is_error, Some(true));
+        assert!(result.structured_content.is_none());
+    }
+
     #[test]
     fn server_capabilities_include_resources() {
         let mock = MockAirbnbClient::new();
@@ -2522,10 +4308,13 @@ mod tests {
         });
         let server = make_server(mock);
         let result = server
-            .airbnb_review_sentiment(Parameters(ReviewSentimentToolParams {
-                id: "42".into(),
-                max_pages: Some(1),
-            }))
+            .airbnb_review_sentiment(
+                Parameters(ReviewSentimentToolParams {
+                    id: "42".into(),
+                    max_pages: Some(1),
+                }),
+                no_progress(),
+            )
             .await
             .unwrap();
 
@@ -2538,6 +4327,41 @@ mod tests {
         assert!(result.is_error.is_none() || result.is_error == Some(false));
     }
 
+    #[tokio::test]
+    async fn review_summary_falls_back_without_sampling() {
+        let mock = MockAirbnbClient::new().with_reviews(|id, _| {
+            let reviews = vec![
+                make_review("Alice", "Amazing place, super clean!"),
+                make_review("Bob", "Terrible noise, very dirty."),
+            ];
+            Ok(make_reviews_page(id, reviews))
+        });
+        let server = make_server(mock);
+        let result = server
+            .airbnb_review_summary(
+                Parameters(ReviewSummaryToolParams {
+                    id: "42".into(),
+                    max_pages: Some(1),
+                }),
+                Sampler::unavailable(),
+            )
+            .await
+            .unwrap();
+
+        let text = extract_text(&result);
+        assert!(text.contains("Review Summary"), "got: {text}");
+        assert!(text.contains("does not support sampling"), "got: {text}");
+        let structured = result.structured_content.unwrap();
+        assert_eq!(structured["method"], "keyword_sentiment");
+        assert!(
+            server
+                .resources
+                .get("airbnb://analysis/summary/42")
+                .await
+                .is_some()
+        );
+    }
+
     #[tokio::test]
     async fn competitive_positioning_success() {
         let mock = MockAirbnbClient::new()
@@ -2581,6 +4405,66 @@ mod tests {
         assert!(result.is_error.is_none() || result.is_error == Some(false));
     }
 
+    #[tokio::test]
+    async fn review_sentiment_cancelled_stops_paging() {
+        let ct = CancellationToken::new();
+        let cancel = ct.clone();
+        let mock = MockAirbnbClient::new().with_reviews(move |id, cursor| {
+            let page: u32 = cursor.map_or(1, |c| c.parse().unwrap());
+            if page == 2 {
+                cancel.cancel();
+            }
+            let mut reviews = make_reviews_page(id, vec![make_review("Guest", "Lovely stay")]);
+            reviews.next_cursor = Some((page + 1).to_string());
+            Ok(reviews)
+        });
+        let server = make_server(mock);
+        let result = server
+            .airbnb_review_sentiment(
+                Parameters(ReviewSentimentToolParams {
+                    id: "42".into(),
+                    max_pages: Some(5),
+                }),
+                ToolProgress::detached(ct),
+            )
+            .await
+            .unwrap();
+
+        let text = extract_text(&result);
+        assert!(
+            text.contains("Cancelled after 2 of 5 review pages"),
+            "{text}"
+        );
+        let structured = result.structured_content.unwrap();
+        assert_eq!(structured["total_reviews_analyzed"], 2);
+        assert!(
+            server
+                .resources
+                .get("airbnb://analysis/sentiment/42")
+                .await
+                .is_none()
+        );
+    }
+
+    #[tokio::test]
+    async fn optimal_pricing_cancelled_before_detail_is_an_error() {
+        let ct = CancellationToken::new();
+        ct.cancel();
+        let server = make_server(MockAirbnbClient::new());
+        let result = server
+            .airbnb_optimal_pricing(
+                Parameters(OptimalPricingToolParams {
+                    id: "42".into(),
+                    location: None,
+                }),
+                ToolProgress::detached(ct),
+            )
+            .await
+            .unwrap();
+        assert_eq!(result.is_error, Some(true));
+        assert!(extract_text(&result).contains("Cancelled"));
+    }
+
     #[tokio::test]
     async fn optimal_pricing_success() {
         let mock = MockAirbnbClient::new()
@@ -2607,10 +4491,13 @@ mod tests {
             .with_search(|_| Ok(make_search_result(vec![])));
         let server = make_server(mock);
         let result = server
-            .airbnb_optimal_pricing(Parameters(OptimalPricingToolParams {
-                id: "42".into(),
-                location: None,
-            }))
+            .airbnb_optimal_pricing(
+                Parameters(OptimalPricingToolParams {
+                    id: "42".into(),
+                    location: None,
+                }),
+                no_progress(),
+            )
             .await
             .unwrap();
 
@@ -2722,10 +4609,13 @@ mod tests {
         });
         let server = make_server(mock);
         let result = server
-            .airbnb_review_sentiment(Parameters(ReviewSentimentToolParams {
-                id: "42".into(),
-                max_pages: None,
-            }))
+            .airbnb_review_sentiment(
+                Parameters(ReviewSentimentToolParams {
+                    id: "42".into(),
+                    max_pages: None,
+                }),
+                no_progress(),
+            )
             .await
             .unwrap();
 
@@ -2758,10 +4648,13 @@ mod tests {
             .with_detail(|id| Err(AirbnbError::ListingNotFound { id: id.to_string() }));
         let server = make_server(mock);
         let result = server
-            .airbnb_optimal_pricing(Parameters(OptimalPricingToolParams {
-                id: "42".into(),
-                location: None,
-            }))
+            .airbnb_optimal_pricing(
+                Parameters(OptimalPricingToolParams {
+                    id: "42".into(),
+                    location: None,
+                }),
+                no_progress(),
+            )
             .await
             .unwrap();
 
@@ -2783,10 +4676,13 @@ mod tests {
         let server = make_server(mock);
 
         let _ = server
-            .airbnb_review_sentiment(Parameters(ReviewSentimentToolParams {
-                id: "42".into(),
-                max_pages: Some(1),
-            }))
+            .airbnb_review_sentiment(
+                Parameters(ReviewSentimentToolParams {
+                    id: "42".into(),
+                    max_pages: Some(1),
+                }),
+                no_progress(),
+            )
             .await
             .unwrap();
 
@@ -2839,10 +4735,13 @@ mod tests {
         let server = make_server(mock);
 
         let _ = server
-            .airbnb_optimal_pricing(Parameters(OptimalPricingToolParams {
-                id: "42".into(),
-                location: None,
-            }))
+            .airbnb_optimal_pricing(
+                Parameters(OptimalPricingToolParams {
+                    id: "42".into(),
+                    location: None,
+                }),
+                no_progress(),
+            )
             .await
             .unwrap();
 
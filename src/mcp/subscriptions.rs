@@ -0,0 +1,187 @@
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use rmcp::{Peer, RoleServer, model::ResourceUpdatedNotificationParam};
+use tokio::sync::RwLock;
+
+/// Identifies one MCP session (one server instance from `AirbnbMcpServer::for_session`).
+pub type SessionId = u64;
+
+/// Tracks connected sessions and their resource subscriptions, and sends
+/// `notifications/resources/updated` and `notifications/resources/list_changed`.
+///
+/// Shared by every session of a server so that a re-fetch in one session reaches
+/// subscribers in all sessions that can see the resource.
+#[derive(Clone, Default)]
+pub struct ResourceNotifier {
+    state: Arc<RwLock<NotifierState>>,
+    next_session: Arc<AtomicU64>,
+}
+
+#[derive(Default)]
+struct NotifierState {
+    peers: HashMap<SessionId, Peer<RoleServer>>,
+    subscriptions: HashMap<String, HashSet<SessionId>>,
+}
+
+impl NotifierState {
+    /// Forget sessions whose transport has gone away, with their subscriptions.
+    fn prune_closed(&mut self) {
+        let closed: Vec<SessionId> = self
+            .peers
+            .iter()
+            .filter(|(_, peer)| peer.is_transport_closed())
+            .map(|(id, _)| *id)
+            .collect();
+        for id in closed {
+            self.forget(id);
+        }
+    }
+
+    fn forget(&mut self, session: SessionId) {
+        self.peers.remove(&session);
+        self.subscriptions.retain(|_, sessions| {
+            sessions.remove(&session);
+            !sessions.is_empty()
+        });
+    }
+}
+
+/// What happened to a resource when it was written.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResourceChange {
+    /// The URI did not exist before.
+    Added,
+    /// The URI existed and was re-fetched.
+    Updated,
+}
+
+impl ResourceNotifier {
+    /// Allocate an id for a new session. Id 0 belongs to the server built by `new`.
+    pub fn next_session_id(&self) -> SessionId {
+        self.next_session.fetch_add(1, Ordering::Relaxed) + 1
+    }
+
+    /// Remember the peer of `session` so it can receive `list_changed` notifications.
+    pub async fn register(&self, session: SessionId, peer: Peer<RoleServer>) {
+        let mut state = self.state.write().await;
+        state.prune_closed();
+        state.peers.insert(session, peer);
+    }
+
+    pub async fn subscribe(&self, session: SessionId, peer: Peer<RoleServer>, uri: String) {
+        let mut state = self.state.write().await;
+        state.prune_closed();
+        state.peers.insert(session, peer);
+        state.subscriptions.entry(uri).or_default().insert(session);
+    }
+
+    pub async fn unsubscribe(&self, session: SessionId, uri: &str) {
+        let mut state = self.state.write().await;
+        if let Some(sessions) = state.subscriptions.get_mut(uri) {
+            sessions.remove(&session);
+            if sessions.is_empty() {
+                state.subscriptions.remove(uri);
+            }
+        }
+    }
+
+    /// Notify sessions about a write to `uri` made by `origin`.
+    ///
+    /// `shared` says whether other sessions can see the resource; private resources only
+    /// notify their own session. Subscribers get `resources/updated` on every write, and
+    /// new URIs additionally trigger `resources/list_changed`.
+    pub async fn notify(&self, origin: SessionId, uri: &str, shared: bool, change: ResourceChange) {
+        let visible = |session: &SessionId| shared || *session == origin;
+        let (updated, list_changed) = {
+            let state = self.state.read().await;
+            let updated: Vec<Peer<RoleServer>> = state
+                .subscriptions
+                .get(uri)
+                .into_iter()
+                .flatten()
+                .filter(|session| visible(session))
+                .filter_map(|session| state.peers.get(session).cloned())
+                .collect();
+            let list_changed: Vec<Peer<RoleServer>> = if change == ResourceChange::Added {
+                state
+                    .peers
+                    .iter()
+                    .filter(|(session, _)| visible(session))
+                    .map(|(_, peer)| peer.clone())
+                    .collect()
+            } else {
+                Vec::new()
+            };
+            (updated, list_changed)
+        };
+        if updated.is_empty() && list_changed.is_empty() {
+            return;
+        }
+
+        // Deliver off the tool's critical path: a slow client must not stall the caller
+        let uri = uri.to_string();
+        tokio::spawn(async move {
+            for peer in list_changed {
+                if let Err(e) = peer.notify_resource_list_changed().await {
+                    tracing::debug!(error = %e, "Failed to send resources/list_changed");
+                }
+            }
+            for peer in updated {
+                let param = ResourceUpdatedNotificationParam { uri: uri.clone() };
+                if let Err(e) = peer.notify_resource_updated(param).await {
+                    tracing::debug!(error = %e, uri = %uri, "Failed to send resources/updated");
+                }
+            }
+        });
+    }
+}
+
+impl std::fmt::Debug for ResourceNotifier {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ResourceNotifier").finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn session_ids_are_unique_and_skip_the_base_server() {
+        let notifier = ResourceNotifier::default();
+        let a = notifier.next_session_id();
+        let b = notifier.clone().next_session_id();
+        assert_ne!(a, 0);
+        assert_ne!(a, b);
+    }
+
+    #[test]
+    fn forget_drops_empty_subscriptions() {
+        let mut state = NotifierState::default();
+        state
+            .subscriptions
+            .entry("airbnb://listing/1".into())
+            .or_default()
+            .extend([1, 2]);
+        state
+            .subscriptions
+            .entry("airbnb://listing/2".into())
+            .or_default()
+            .insert(1);
+
+        state.forget(1);
+        assert_eq!(state.subscriptions.len(), 1);
+        assert!(state.subscriptions["airbnb://listing/1"].contains(&2));
+    }
+
+    #[tokio::test]
+    async fn notify_without_sessions_is_a_no_op() {
+        let notifier = ResourceNotifier::default();
+        notifier
+            .notify(0, "airbnb://listing/1", true, ResourceChange::Added)
+            .await;
+        notifier.unsubscribe(0, "airbnb://listing/1").await;
+    }
+}
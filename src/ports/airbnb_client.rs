@@ -1,3 +1,5 @@
+use std::sync::Arc;
+
 use async_trait::async_trait;
 
 use crate::domain::analytics::{HostProfile, NeighborhoodStats, OccupancyEstimate};
@@ -18,3 +20,31 @@ pub trait AirbnbClient: Send + Sync {
     async fn get_neighborhood_stats(&self, params: &SearchParams) -> Result<NeighborhoodStats>;
     async fn get_occupancy_estimate(&self, id: &str, months: u32) -> Result<OccupancyEstimate>;
 }
+
+/// Lets a shared client be wrapped by another one (e.g. `CompositeClient`) while the
+/// caller keeps a handle to it, for instance to reload its settings.
+#[async_trait]
+impl<T: AirbnbClient + ?Sized> AirbnbClient for Arc<T> {
+    async fn search_listings(&self, params: &SearchParams) -> Result<SearchResult> {
+        (**self).search_listings(params).await
+    }
+    async fn get_listing_detail(&self, id: &str) -> Result<ListingDetail> {
+        (**self).get_listing_detail(id).await
+    }
+    async fn get_reviews(&self, id: &str, cursor: Option<&str>) -> Result<ReviewsPage> {
+        (**self).get_reviews(id, cursor).await
+    }
+    async fn get_price_calendar(&self, id: &str, months: u32) -> Result<PriceCalendar> {
+        (**self).get_price_calendar(id, months).await
+    }
+
+    async fn get_host_profile(&self, listing_id: &str) -> Result<HostProfile> {
+        (**self).get_host_profile(listing_id).await
+    }
+    async fn get_neighborhood_stats(&self, params: &SearchParams) -> Result<NeighborhoodStats> {
+        (**self).get_neighborhood_stats(params).await
+    }
+    async fn get_occupancy_estimate(&self, id: &str, months: u32) -> Result<OccupancyEstimate> {
+        (**self).get_occupancy_estimate(id, months).await
+    }
+}
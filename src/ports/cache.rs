@@ -1,6 +1,195 @@
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::future::Future;
 use std::time::Duration;
 
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+/// A value read from the cache.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CachedValue {
+    pub value: String,
+    /// Time since the value was stored.
+    pub age: Duration,
+    /// Past its TTL but still within its grace period; the caller should refresh it.
+    pub stale: bool,
+}
+
+/// The kinds of Airbnb data the adapters cache, each with its own TTL and grace period.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CacheKind {
+    Search,
+    Detail,
+    Reviews,
+    Calendar,
+    HostProfile,
+}
+
+/// How long a cached value is fresh, and how long after that it may be served stale
+/// while a refresh runs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CachePolicy {
+    pub ttl: Duration,
+    pub grace: Duration,
+}
+
 pub trait ListingCache: Send + Sync {
-    fn get(&self, key: &str) -> Option<String>;
-    fn set(&self, key: &str, value: &str, ttl: Duration);
+    /// The value under `key`, while fresh or within its grace period (then `stale`).
+    fn get(&self, key: &str) -> Option<CachedValue>;
+
+    /// Like [`get`](Self::get), but not counted in [`stats`](Self::stats): for a
+    /// second look at a key whose read was already counted.
+    fn peek(&self, key: &str) -> Option<CachedValue>;
+
+    /// Store `value`, fresh for `ttl` and then served as stale for a further `grace`.
+    fn set_with_grace(&self, key: &str, value: &str, ttl: Duration, grace: Duration);
+
+    fn set(&self, key: &str, value: &str, ttl: Duration) {
+        self.set_with_grace(key, value, ttl, Duration::ZERO);
+    }
+
+    /// Read counters since startup and the live entries, grouped by [`key_prefix`].
+    fn stats(&self) -> CacheStats;
+
+    /// Remove every entry whose key starts with `prefix` (all of them for `""`),
+    /// returning how many were removed.
+    fn invalidate_prefix(&self, prefix: &str) -> usize;
+
+    /// Remove the entry under `key` because its reader could not use it (written with
+    /// another schema version, or not decodable), counting it in
+    /// [`CacheStats::incompatible`].
+    fn purge_incompatible(&self, key: &str);
+}
+
+/// How a cache has been used since startup and what it holds now.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, schemars::JsonSchema)]
+pub struct CacheStats {
+    /// Reads that returned a value, fresh or stale.
+    pub hits: u64,
+    /// The part of `hits` that was served stale.
+    pub stale_hits: u64,
+    pub misses: u64,
+    /// Entries dropped to make room; expired entries are not counted.
+    pub evictions: u64,
+    /// Entries purged because they were written in another schema version or could not
+    /// be decoded; each of these reads is also counted in `hits`.
+    pub incompatible: u64,
+    pub entries: usize,
+    pub capacity: usize,
+    /// Stored size of keys and values, after compression.
+    pub bytes: u64,
+    /// Byte budget, when the cache has one.
+    pub max_bytes: Option<u64>,
+    /// Live entries per key prefix, such as `search:` or `gql:detail:`.
+    pub entries_by_prefix: BTreeMap<String, usize>,
+}
+
+impl CacheStats {
+    /// Count `key` under its prefix.
+    pub fn add_entry(&mut self, key: &str) {
+        self.entries += 1;
+        *self
+            .entries_by_prefix
+            .entry(key_prefix(key).to_string())
+            .or_default() += 1;
+    }
+}
+
+/// Tag in front of the keys of the GraphQL adapter, which caches the same operations
+/// as the scraper under different keys.
+pub const GRAPHQL_KEY_TAG: &str = "gql:";
+
+/// The kind part of a cache key: `detail:` for `detail:42`, and the kind after the
+/// adapter tag for GraphQL keys (`gql:detail:` for `gql:detail:42`).
+pub fn key_prefix(key: &str) -> &str {
+    let start = if key.starts_with(GRAPHQL_KEY_TAG) {
+        GRAPHQL_KEY_TAG.len()
+    } else {
+        0
+    };
+    key[start..]
+        .find(':')
+        .map_or(key, |end| &key[..=start + end])
+}
+
+/// A value served from the cache instead of being fetched.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CacheRead {
+    pub key: String,
+    /// When the value was fetched from Airbnb.
+    pub fetched_at: DateTime<Utc>,
+    /// Served past its TTL while a refresh runs.
+    pub stale: bool,
+}
+
+tokio::task_local! {
+    static CACHE_READS: RefCell<Vec<CacheRead>>;
+}
+
+/// Run `fut`, collecting the cache reads recorded while it runs on this task.
+pub async fn track_cache_reads<F: Future>(fut: F) -> (F::Output, Vec<CacheRead>) {
+    CACHE_READS
+        .scope(RefCell::new(Vec::new()), async {
+            let output = fut.await;
+            let reads = CACHE_READS.with(RefCell::take);
+            (output, reads)
+        })
+        .await
+}
+
+/// Note that a value was served from the cache; a no-op outside [`track_cache_reads`].
+pub fn record_cache_read(read: CacheRead) {
+    let _ = CACHE_READS.try_with(|reads| reads.borrow_mut().push(read));
+}
+
+tokio::task_local! {
+    static FRESH: ();
+}
+
+/// Run `fut` with cache reads skipped: every value is fetched again (and stored).
+pub async fn fetch_fresh<F: Future>(fut: F) -> F::Output {
+    FRESH.scope((), fut).await
+}
+
+/// Whether this task runs inside [`fetch_fresh`].
+pub fn fresh_requested() -> bool {
+    FRESH.try_with(|()| ()).is_ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn cache_reads_are_collected_per_scope() {
+        let read = |key: &str| CacheRead {
+            key: key.into(),
+            fetched_at: DateTime::UNIX_EPOCH,
+            stale: true,
+        };
+        record_cache_read(read("detail:0"));
+        let ((), reads) = track_cache_reads(async {
+            record_cache_read(read("detail:1"));
+        })
+        .await;
+        assert_eq!(reads, [read("detail:1")]);
+    }
+
+    #[test]
+    fn key_prefixes() {
+        assert_eq!(key_prefix("detail:42"), "detail:");
+        assert_eq!(key_prefix("search:paris:a=2"), "search:");
+        assert_eq!(key_prefix("gql:detail:42"), "gql:detail:");
+        assert_eq!(key_prefix("gql:search:paris"), "gql:search:");
+        assert_eq!(key_prefix("plain"), "plain");
+        assert_eq!(key_prefix("gql:"), "gql:");
+    }
+
+    #[tokio::test]
+    async fn fresh_is_scoped_to_the_future() {
+        assert!(!fresh_requested());
+        assert!(fetch_fresh(async { fresh_requested() }).await);
+        assert!(!fresh_requested());
+    }
 }
@@ -4,6 +4,7 @@ use std::sync::Arc;
 
 use async_trait::async_trait;
 
+use mcp_airbnb::config::types::ResourceConfig;
 use mcp_airbnb::domain::analytics::{
     HostProfile, MonthlyOccupancy, NeighborhoodStats, OccupancyEstimate, PropertyTypeCount,
 };
@@ -15,7 +16,10 @@ use mcp_airbnb::error::Result;
 use mcp_airbnb::mcp::server::AirbnbMcpServer;
 use mcp_airbnb::ports::airbnb_client::AirbnbClient;
 
-use rmcp::model::{CallToolRequestParams, CallToolResult, ClientInfo, ReadResourceRequestParams};
+use rmcp::model::{
+    CallToolRequestParams, CallToolResult, ClientInfo, PaginatedRequestParams,
+    ReadResourceRequestParams, ResourceContents,
+};
 use rmcp::{ClientHandler, ServiceExt};
 
 // ---------------------------------------------------------------------------
@@ -839,10 +843,18 @@ fn tool_params(name: &str, args: serde_json::Value) -> CallToolRequestParams {
 async fn setup() -> (
     rmcp::service::RunningService<rmcp::RoleClient, DummyClientHandler>,
     tokio::task::JoinHandle<anyhow::Result<()>>,
+) {
+    setup_with(AirbnbMcpServer::new(Arc::new(AnalyticalMock))).await
+}
+
+async fn setup_with(
+    server: AirbnbMcpServer,
+) -> (
+    rmcp::service::RunningService<rmcp::RoleClient, DummyClientHandler>,
+    tokio::task::JoinHandle<anyhow::Result<()>>,
 ) {
     let (server_transport, client_transport) = tokio::io::duplex(65536);
 
-    let server = AirbnbMcpServer::new(Arc::new(AnalyticalMock));
     let server_handle = tokio::spawn(async move {
         server.serve(server_transport).await?.waiting().await?;
         anyhow::Ok(())
@@ -1394,18 +1406,235 @@ async fn read_resource_returns_content() {
 async fn read_resource_not_found_returns_error() {
     let (client, server_handle) = setup().await;
 
+    // Analysis results cannot be resolved on demand, and unknown templates never match
+    for uri in [
+        "airbnb://analysis/price-trends/1",
+        "airbnb://listing/1/photos",
+    ] {
+        let result = client
+            .peer()
+            .read_resource(ReadResourceRequestParams {
+                uri: uri.into(),
+                meta: None,
+            })
+            .await;
+
+        assert!(
+            result.is_err(),
+            "read_resource for {uri} should return error"
+        );
+    }
+
+    teardown(client, server_handle).await;
+}
+
+#[tokio::test]
+async fn read_resource_fetches_listing_templates_on_demand() {
+    let (client, server_handle) = setup().await;
+
+    // No tool has been called: the server resolves the templates live
+    for uri in [
+        "airbnb://listing/1",
+        "airbnb://listing/1/calendar",
+        "airbnb://listing/1/reviews",
+        "airbnb://listing/1/host",
+        "airbnb://listing/1/occupancy",
+    ] {
+        let result = client
+            .peer()
+            .read_resource(ReadResourceRequestParams {
+                uri: uri.into(),
+                meta: None,
+            })
+            .await
+            .unwrap_or_else(|e| panic!("read_resource {uri} failed: {e}"));
+        assert_eq!(result.contents.len(), 1, "{uri}");
+    }
+
+    // The fetched entries are now listed like tool-populated ones
+    let listed = client.peer().list_resources(None).await.unwrap();
+    assert!(
+        listed
+            .resources
+            .iter()
+            .any(|r| r.raw.uri == "airbnb://listing/1/calendar")
+    );
+
+    teardown(client, server_handle).await;
+}
+
+#[tokio::test]
+async fn read_resource_decodes_location_segments() {
+    let (client, server_handle) = setup().await;
+
     let result = client
         .peer()
         .read_resource(ReadResourceRequestParams {
-            uri: "airbnb://listing/nonexistent".into(),
+            uri: "airbnb://search/Paris%2C%20France".into(),
             meta: None,
         })
+        .await
+        .expect("search resource should resolve");
+    let ResourceContents::TextResourceContents { text, .. } = &result.contents[0] else {
+        panic!("expected text contents");
+    };
+    assert!(text.contains("Found"));
+
+    // Tool-populated entries use the same percent-encoded URI, so a raw read hits the store
+    let listed = client.peer().list_resources(None).await.unwrap();
+    let uris: Vec<_> = listed
+        .resources
+        .iter()
+        .map(|r| r.raw.uri.as_str())
+        .collect();
+    assert!(
+        uris.contains(&"airbnb://search/Paris%2C%20France"),
+        "{uris:?}"
+    );
+
+    teardown(client, server_handle).await;
+}
+
+async fn read_listings(
+    client: &rmcp::service::RunningService<rmcp::RoleClient, DummyClientHandler>,
+    ids: std::ops::RangeInclusive<u32>,
+) {
+    for id in ids {
+        client
+            .peer()
+            .read_resource(ReadResourceRequestParams {
+                uri: format!("airbnb://listing/{id}"),
+                meta: None,
+            })
+            .await
+            .expect("listing should resolve");
+    }
+}
+
+#[tokio::test]
+async fn list_resources_paginates_with_cursor() {
+    let config = ResourceConfig {
+        page_size: 2,
+        ..ResourceConfig::default()
+    };
+    let server = AirbnbMcpServer::new(Arc::new(AnalyticalMock)).with_resource_config(&config);
+    let (client, server_handle) = setup_with(server).await;
+    read_listings(&client, 1..=5).await;
+
+    let mut uris = Vec::new();
+    let mut pages = 0;
+    let mut cursor = None;
+    loop {
+        let page = client
+            .peer()
+            .list_resources(Some(PaginatedRequestParams { meta: None, cursor }))
+            .await
+            .expect("list_resources should succeed");
+        pages += 1;
+        assert!(page.resources.len() <= 2);
+        for resource in &page.resources {
+            assert!(resource.raw.size.is_some_and(|size| size > 0));
+            assert!(resource.raw.description.is_some());
+            uris.push(resource.raw.uri.clone());
+        }
+        cursor = page.next_cursor;
+        if cursor.is_none() {
+            break;
+        }
+    }
+
+    assert_eq!(pages, 3);
+    assert_eq!(
+        uris,
+        (1..=5)
+            .map(|id| format!("airbnb://listing/{id}"))
+            .collect::<Vec<_>>()
+    );
+
+    let invalid = client
+        .peer()
+        .list_resources(Some(PaginatedRequestParams {
+            meta: None,
+            cursor: Some("not a cursor!".into()),
+        }))
         .await;
+    assert!(invalid.is_err());
 
-    assert!(
-        result.is_err(),
-        "read_resource for nonexistent URI should return error"
+    teardown(client, server_handle).await;
+}
+
+#[tokio::test]
+async fn resource_store_evicts_least_recently_used_beyond_capacity() {
+    let config = ResourceConfig {
+        max_entries: 3,
+        ..ResourceConfig::default()
+    };
+    let server = AirbnbMcpServer::new(Arc::new(AnalyticalMock)).with_resource_config(&config);
+    let (client, server_handle) = setup_with(server).await;
+    read_listings(&client, 1..=5).await;
+
+    let listed = client.peer().list_resources(None).await.unwrap();
+    let uris: Vec<_> = listed
+        .resources
+        .iter()
+        .map(|r| r.raw.uri.as_str())
+        .collect();
+    assert_eq!(
+        uris,
+        [
+            "airbnb://listing/3",
+            "airbnb://listing/4",
+            "airbnb://listing/5"
+        ]
     );
 
     teardown(client, server_handle).await;
 }
+
+#[tokio::test]
+async fn complete_suggests_seen_listing_ids_and_locations() {
+    use rmcp::model::{ArgumentInfo, CompleteRequestParams, Reference};
+
+    let (client, server_handle) = setup().await;
+    let _ = client
+        .call_tool(tool_params(
+            "airbnb_search",
+            serde_json::json!({ "location": "Lisbon, Portugal" }),
+        ))
+        .await
+        .expect("search should succeed");
+
+    let complete = |r#ref: Reference, name: &str, value: &str| CompleteRequestParams {
+        meta: None,
+        r#ref,
+        argument: ArgumentInfo {
+            name: name.into(),
+            value: value.into(),
+        },
+        context: None,
+    };
+
+    let locations = client
+        .peer()
+        .complete(complete(
+            Reference::for_prompt("investment_analysis"),
+            "location",
+            "lis",
+        ))
+        .await
+        .expect("complete should succeed");
+    assert_eq!(locations.completion.values, ["Lisbon, Portugal"]);
+
+    let ids = client
+        .peer()
+        .complete(complete(
+            Reference::for_resource("airbnb://listing/{id}/calendar"),
+            "id",
+            "",
+        ))
+        .await
+        .expect("complete should succeed");
+    assert!(!ids.completion.values.is_empty());
+
+    teardown(client, server_handle).await;
+}
@@ -24,7 +24,7 @@ use rmcp::model::{CallToolRequestParams, CallToolResult, ClientInfo};
 use rmcp::{ClientHandler, ServiceExt};
 
 // ---------------------------------------------------------------------------
-// FunctionalMock — realistic data for all 18 tool paths
+// FunctionalMock — realistic data for all 19 tool paths
 // ---------------------------------------------------------------------------
 
 struct FunctionalMock;
@@ -768,7 +768,7 @@ fn listing_host_id_serde_roundtrip() {
 // ===========================================================================
 
 #[tokio::test]
-async fn list_tools_returns_18() {
+async fn list_tools_returns_19() {
     let (client, server_handle) = setup().await;
 
     let tools = client
@@ -779,8 +779,8 @@ async fn list_tools_returns_18() {
     let tool_names: Vec<String> = tools.tools.iter().map(|t| t.name.to_string()).collect();
     assert_eq!(
         tool_names.len(),
-        18,
-        "Expected 18 tools, got {}: {:?}",
+        19,
+        "Expected 19 tools, got {}: {:?}",
         tool_names.len(),
         tool_names
     );
@@ -803,6 +803,7 @@ async fn list_tools_returns_18() {
         "airbnb_market_comparison",
         "airbnb_host_portfolio",
         "airbnb_review_sentiment",
+        "airbnb_review_summary",
         "airbnb_competitive_positioning",
         "airbnb_optimal_pricing",
     ];
@@ -812,6 +813,12 @@ async fn list_tools_returns_18() {
             "Missing tool: {name}"
         );
     }
+    // The cache tools are only listed when tools.allow or a token names them
+    assert!(
+        !tool_names
+            .iter()
+            .any(|name| name.starts_with("airbnb_cache_"))
+    );
 
     teardown(client, server_handle).await;
 }
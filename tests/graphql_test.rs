@@ -7,7 +7,7 @@ use mcp_airbnb::config::types::{CacheConfig, ScraperConfig};
 use mcp_airbnb::domain::search_params::SearchParams;
 use mcp_airbnb::ports::airbnb_client::AirbnbClient;
 
-use wiremock::matchers::{method, path, path_regex};
+use wiremock::matchers::{method, path, path_regex, query_param};
 use wiremock::{Mock, MockServer, ResponseTemplate};
 
 // ---------------------------------------------------------------------------
@@ -397,6 +397,60 @@ async fn graphql_search_different_params_different_cache_keys() {
     // wiremock verifies expect(2)
 }
 
+#[tokio::test]
+async fn graphql_search_filters_and_cursor_are_part_of_the_cache_key() {
+    let server = MockServer::start().await;
+    let cache = Arc::new(MemoryCache::new(100));
+    let client = build_client_with_cache(&server, cache).await;
+
+    Mock::given(method("POST"))
+        .and(path_regex("/api/v3/StaysSearch/.*"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(search_response_json()))
+        .expect(3) // Same location, but dates and cursor change the request
+        .mount(&server)
+        .await;
+
+    let first = base_params();
+    let mut dated = base_params();
+    dated.checkin = Some("2025-06-01".into());
+    dated.checkout = Some("2025-06-05".into());
+    let mut next_page = base_params();
+    next_page.cursor = Some("page2".into());
+
+    for params in [&first, &dated, &next_page, &first] {
+        client.search_listings(params).await.unwrap();
+    }
+}
+
+#[tokio::test]
+async fn graphql_sends_configured_locale_and_currency() {
+    let server = MockServer::start().await;
+    mount_api_key_mock(&server).await;
+    let config = ScraperConfig {
+        locale: "fr".into(),
+        currency: "EUR".into(),
+        ..fast_graphql_config(&server.uri())
+    };
+    let client = AirbnbGraphQLClient::new(
+        &config,
+        test_cache_config(),
+        Arc::new(MemoryCache::new(100)),
+        test_api_key_manager(&server.uri()),
+    )
+    .unwrap();
+
+    Mock::given(method("POST"))
+        .and(path_regex("/api/v3/StaysSearch/.*"))
+        .and(query_param("locale", "fr"))
+        .and(query_param("currency", "EUR"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(search_response_json()))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    client.search_listings(&base_params()).await.unwrap();
+}
+
 // ---------------------------------------------------------------------------
 // Detail tests
 // ---------------------------------------------------------------------------
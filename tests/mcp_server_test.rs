@@ -238,7 +238,7 @@ fn server_lists_seven_tools() {
     let server = AirbnbMcpServer::new(Arc::new(IntegrationMock));
     let info = server.get_info();
     let instructions = info.instructions.unwrap();
-    // Verify all 18 tools are mentioned
+    // Verify all 21 tools are mentioned
     assert!(instructions.contains("airbnb_search"));
     assert!(instructions.contains("airbnb_listing_details"));
     assert!(instructions.contains("airbnb_reviews"));
@@ -255,11 +255,15 @@ fn server_lists_seven_tools() {
     assert!(instructions.contains("airbnb_market_comparison"));
     assert!(instructions.contains("airbnb_host_portfolio"));
     assert!(instructions.contains("airbnb_review_sentiment"));
+    assert!(instructions.contains("airbnb_review_summary"));
     assert!(instructions.contains("airbnb_competitive_positioning"));
     assert!(instructions.contains("airbnb_optimal_pricing"));
-    // Verify capabilities include tools and resources
+    assert!(instructions.contains("airbnb_cache_stats"));
+    assert!(instructions.contains("airbnb_cache_invalidate"));
+    // Verify capabilities include tools, resources and prompts
     assert!(info.capabilities.tools.is_some());
     assert!(info.capabilities.resources.is_some());
+    assert!(info.capabilities.prompts.is_some());
 }
 
 #[test]
@@ -277,3 +281,908 @@ fn server_creates_with_different_clients() {
     let _server2 = AirbnbMcpServer::new(Arc::new(ErrorMock));
     // Both should construct without panicking
 }
+
+/// A running HTTP transport bound to an ephemeral port.
+struct HttpHarness {
+    url: String,
+    shutdown: tokio::sync::oneshot::Sender<()>,
+    handle: tokio::task::JoinHandle<mcp_airbnb::error::Result<()>>,
+}
+
+impl HttpHarness {
+    async fn start(
+        server: AirbnbMcpServer,
+        config: mcp_airbnb::config::types::ServerConfig,
+    ) -> Self {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/mcp", listener.local_addr().unwrap());
+        let (shutdown, shutdown_rx) = tokio::sync::oneshot::channel::<()>();
+        let handle = tokio::spawn(async move {
+            mcp_airbnb::mcp::http::serve_http(server, listener, &config, async {
+                let _ = shutdown_rx.await;
+            })
+            .await
+        });
+        Self {
+            url,
+            shutdown,
+            handle,
+        }
+    }
+
+    async fn stop(self) {
+        self.shutdown.send(()).unwrap();
+        self.handle.await.unwrap().unwrap();
+    }
+}
+
+fn tool_call(name: &'static str, args: serde_json::Value) -> rmcp::model::CallToolRequestParams {
+    rmcp::model::CallToolRequestParams {
+        meta: None,
+        name: name.into(),
+        arguments: match args {
+            serde_json::Value::Object(map) => Some(map),
+            _ => None,
+        },
+        task: None,
+    }
+}
+
+fn read_request(uri: &str) -> rmcp::model::ReadResourceRequestParams {
+    rmcp::model::ReadResourceRequestParams {
+        meta: None,
+        uri: uri.into(),
+    }
+}
+
+#[tokio::test]
+async fn http_transport_serves_tools_and_resources() {
+    use mcp_airbnb::config::types::ServerConfig;
+    use rmcp::ServiceExt;
+    use rmcp::transport::StreamableHttpClientTransport;
+
+    let server = AirbnbMcpServer::new(Arc::new(IntegrationMock));
+    let harness = HttpHarness::start(server, ServerConfig::default()).await;
+
+    let transport = StreamableHttpClientTransport::from_uri(harness.url.clone());
+    let client = ().serve(transport).await.unwrap();
+
+    let tools = client.list_all_tools().await.unwrap();
+    assert_eq!(tools.len(), 19);
+
+    let result = client
+        .call_tool(tool_call(
+            "airbnb_listing_details",
+            serde_json::json!({ "id": "101" }),
+        ))
+        .await
+        .unwrap();
+    assert_ne!(result.is_error, Some(true));
+
+    // Resources populated by the tool call are readable over the same transport
+    let resource = client
+        .read_resource(read_request("airbnb://listing/101"))
+        .await
+        .unwrap();
+    assert_eq!(resource.contents.len(), 1);
+
+    client.cancel().await.unwrap();
+    harness.stop().await;
+}
+
+#[tokio::test]
+async fn http_transport_isolates_analysis_between_sessions() {
+    use mcp_airbnb::config::types::{ServerConfig, SessionMode};
+    use rmcp::ServiceExt;
+    use rmcp::transport::StreamableHttpClientTransport;
+
+    let server =
+        AirbnbMcpServer::new(Arc::new(IntegrationMock)).with_session_mode(SessionMode::Isolated);
+    let harness = HttpHarness::start(server, ServerConfig::default()).await;
+
+    let alice =
+        ().serve(StreamableHttpClientTransport::from_uri(harness.url.clone()))
+            .await
+            .unwrap();
+    let bob = ().serve(StreamableHttpClientTransport::from_uri(harness.url.clone())).await.unwrap();
+
+    alice
+        .call_tool(tool_call(
+            "airbnb_price_trends",
+            serde_json::json!({ "id": "101" }),
+        ))
+        .await
+        .unwrap();
+
+    assert!(
+        alice
+            .read_resource(read_request("airbnb://analysis/price-trends/101"))
+            .await
+            .is_ok()
+    );
+    assert!(
+        bob.read_resource(read_request("airbnb://analysis/price-trends/101"))
+            .await
+            .is_err()
+    );
+
+    alice.cancel().await.unwrap();
+    bob.cancel().await.unwrap();
+    harness.stop().await;
+}
+
+#[tokio::test]
+async fn http_transport_serves_prompts() {
+    use mcp_airbnb::config::types::ServerConfig;
+    use rmcp::ServiceExt;
+    use rmcp::model::{GetPromptRequestParams, PromptMessageContent};
+    use rmcp::transport::StreamableHttpClientTransport;
+
+    let server = AirbnbMcpServer::new(Arc::new(IntegrationMock));
+    let harness = HttpHarness::start(server, ServerConfig::default()).await;
+    let client =
+        ().serve(StreamableHttpClientTransport::from_uri(harness.url.clone()))
+            .await
+            .unwrap();
+
+    let prompts = client.list_all_prompts().await.unwrap();
+    assert_eq!(prompts.len(), 3);
+
+    let result = client
+        .get_prompt(GetPromptRequestParams {
+            meta: None,
+            name: "investment_analysis".into(),
+            arguments: serde_json::json!({ "location": "Berlin", "budget": "250000" })
+                .as_object()
+                .cloned(),
+        })
+        .await
+        .unwrap();
+    let PromptMessageContent::Text { text } = &result.messages[1].content else {
+        panic!("expected text plan");
+    };
+    assert!(text.contains("airbnb_neighborhood_stats"));
+
+    client.cancel().await.unwrap();
+    harness.stop().await;
+}
+
+fn auth_config() -> mcp_airbnb::config::types::ServerConfig {
+    use mcp_airbnb::config::types::{ServerConfig, TokenConfig};
+
+    let mut config = ServerConfig::default();
+    config.auth.tokens = vec![
+        TokenConfig {
+            name: "admin".into(),
+            token: "admin-secret".into(),
+            allowed_tools: None,
+        },
+        TokenConfig {
+            name: "search-only".into(),
+            token: "search-secret".into(),
+            allowed_tools: Some(vec!["airbnb_search".into()]),
+        },
+        TokenConfig {
+            name: "cache-operator".into(),
+            token: "cache-secret".into(),
+            allowed_tools: Some(vec!["airbnb_cache_stats".into()]),
+        },
+    ];
+    config
+}
+
+#[tokio::test]
+async fn http_transport_rejects_missing_or_invalid_token() {
+    let server = AirbnbMcpServer::new(Arc::new(IntegrationMock));
+    let harness = HttpHarness::start(server, auth_config()).await;
+
+    let initialize = serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": "initialize",
+        "params": {
+            "protocolVersion": "2025-03-26",
+            "capabilities": {},
+            "clientInfo": { "name": "test", "version": "0" }
+        }
+    });
+    let http = reqwest::Client::new();
+    for auth in [None, Some("Bearer wrong-secret")] {
+        let mut request = http
+            .post(&harness.url)
+            .header("Accept", "application/json, text/event-stream")
+            .json(&initialize);
+        if let Some(value) = auth {
+            request = request.header("Authorization", value);
+        }
+        let response = request.send().await.unwrap();
+        assert_eq!(response.status(), reqwest::StatusCode::UNAUTHORIZED);
+        assert!(response.headers().contains_key("www-authenticate"));
+        let body: serde_json::Value = response.json().await.unwrap();
+        assert_eq!(body["error"]["code"], -32001);
+    }
+
+    harness.stop().await;
+}
+
+#[tokio::test]
+async fn http_transport_enforces_token_allowlist() {
+    use rmcp::ServiceExt;
+    use rmcp::transport::StreamableHttpClientTransport;
+    use rmcp::transport::streamable_http_client::StreamableHttpClientTransportConfig;
+
+    let server = AirbnbMcpServer::new(Arc::new(IntegrationMock));
+    let harness = HttpHarness::start(server, auth_config()).await;
+
+    let connect = |token: &str| {
+        StreamableHttpClientTransport::from_config(
+            StreamableHttpClientTransportConfig::with_uri(harness.url.clone()).auth_header(token),
+        )
+    };
+
+    let admin = ().serve(connect("admin-secret")).await.unwrap();
+    assert_eq!(admin.list_all_tools().await.unwrap().len(), 19);
+    let denied = admin
+        .call_tool(tool_call("airbnb_cache_stats", serde_json::json!({})))
+        .await;
+    assert!(denied.is_err());
+
+    // Only a token that names a cache tool gets it
+    let operator = ().serve(connect("cache-secret")).await.unwrap();
+    let tools = operator.list_all_tools().await.unwrap();
+    assert_eq!(tools.len(), 1);
+    assert_eq!(tools[0].name, "airbnb_cache_stats");
+    let stats = operator
+        .call_tool(tool_call("airbnb_cache_stats", serde_json::json!({})))
+        .await
+        .unwrap();
+    // Reaches the tool, which reports the missing cache
+    assert_eq!(stats.is_error, Some(true));
+
+    let limited = ().serve(connect("search-secret")).await.unwrap();
+    let tools = limited.list_all_tools().await.unwrap();
+    assert_eq!(tools.len(), 1);
+    assert_eq!(tools[0].name, "airbnb_search");
+
+    let allowed = limited
+        .call_tool(tool_call(
+            "airbnb_search",
+            serde_json::json!({ "location": "Berlin" }),
+        ))
+        .await;
+    assert!(allowed.is_ok());
+    let denied = limited
+        .call_tool(tool_call(
+            "airbnb_compare_listings",
+            serde_json::json!({ "location": "Berlin", "max_listings": 100 }),
+        ))
+        .await;
+    assert!(denied.unwrap_err().to_string().contains("not allowed"));
+
+    admin.cancel().await.unwrap();
+    limited.cancel().await.unwrap();
+    harness.stop().await;
+}
+
+/// Client handler that forwards resource notifications to a channel.
+#[derive(Clone)]
+struct NotificationRecorder(tokio::sync::mpsc::UnboundedSender<String>);
+
+impl rmcp::ClientHandler for NotificationRecorder {
+    async fn on_resource_updated(
+        &self,
+        params: rmcp::model::ResourceUpdatedNotificationParam,
+        _context: rmcp::service::NotificationContext<rmcp::RoleClient>,
+    ) {
+        let _ = self.0.send(format!("updated {}", params.uri));
+    }
+
+    async fn on_resource_list_changed(
+        &self,
+        _context: rmcp::service::NotificationContext<rmcp::RoleClient>,
+    ) {
+        let _ = self.0.send("list_changed".into());
+    }
+
+    async fn on_progress(
+        &self,
+        params: rmcp::model::ProgressNotificationParam,
+        _context: rmcp::service::NotificationContext<rmcp::RoleClient>,
+    ) {
+        let total = params.total.unwrap_or_default();
+        let _ = self.0.send(format!("progress {}/{total}", params.progress));
+    }
+
+    async fn on_logging_message(
+        &self,
+        params: rmcp::model::LoggingMessageNotificationParam,
+        _context: rmcp::service::NotificationContext<rmcp::RoleClient>,
+    ) {
+        let _ = self
+            .0
+            .send(format!("log {:?} {}", params.level, params.data["message"]));
+    }
+}
+
+async fn next_notification(
+    rx: &mut tokio::sync::mpsc::UnboundedReceiver<String>,
+) -> Option<String> {
+    tokio::time::timeout(std::time::Duration::from_millis(500), rx.recv())
+        .await
+        .ok()
+        .flatten()
+}
+
+#[tokio::test]
+async fn http_transport_notifies_resource_subscribers() {
+    use mcp_airbnb::config::types::ServerConfig;
+    use rmcp::ServiceExt;
+    use rmcp::model::{SubscribeRequestParams, UnsubscribeRequestParams};
+    use rmcp::transport::StreamableHttpClientTransport;
+
+    let server = AirbnbMcpServer::new(Arc::new(IntegrationMock));
+    let harness = HttpHarness::start(server, ServerConfig::default()).await;
+
+    let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+    let watcher = NotificationRecorder(tx)
+        .serve(StreamableHttpClientTransport::from_uri(harness.url.clone()))
+        .await
+        .unwrap();
+    let fetcher =
+        ().serve(StreamableHttpClientTransport::from_uri(harness.url.clone()))
+            .await
+            .unwrap();
+
+    let uri = "airbnb://listing/101/calendar";
+    watcher
+        .subscribe(SubscribeRequestParams {
+            meta: None,
+            uri: uri.into(),
+        })
+        .await
+        .unwrap();
+
+    // First fetch creates the resource: list changes, and the subscriber hears about it
+    let calendar = tool_call("airbnb_price_calendar", serde_json::json!({ "id": "101" }));
+    fetcher.call_tool(calendar.clone()).await.unwrap();
+    assert_eq!(
+        next_notification(&mut rx).await.as_deref(),
+        Some("list_changed")
+    );
+    assert_eq!(
+        next_notification(&mut rx).await,
+        Some(format!("updated {uri}"))
+    );
+
+    // A re-fetch only sends the update
+    fetcher.call_tool(calendar.clone()).await.unwrap();
+    assert_eq!(
+        next_notification(&mut rx).await,
+        Some(format!("updated {uri}"))
+    );
+
+    // Analysis resources of another isolated session are invisible to the watcher
+    fetcher
+        .call_tool(tool_call(
+            "airbnb_price_trends",
+            serde_json::json!({ "id": "101" }),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(next_notification(&mut rx).await, None);
+
+    watcher
+        .unsubscribe(UnsubscribeRequestParams {
+            meta: None,
+            uri: uri.into(),
+        })
+        .await
+        .unwrap();
+    fetcher.call_tool(calendar).await.unwrap();
+    assert_eq!(next_notification(&mut rx).await, None);
+
+    watcher.cancel().await.unwrap();
+    fetcher.cancel().await.unwrap();
+    harness.stop().await;
+}
+
+#[tokio::test]
+async fn http_transport_reports_tool_progress() {
+    use mcp_airbnb::config::types::ServerConfig;
+    use rmcp::ServiceExt;
+    use rmcp::transport::StreamableHttpClientTransport;
+
+    let harness = HttpHarness::start(
+        AirbnbMcpServer::new(Arc::new(IntegrationMock)),
+        ServerConfig::default(),
+    )
+    .await;
+    let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+    let client = NotificationRecorder(tx)
+        .serve(StreamableHttpClientTransport::from_uri(harness.url.clone()))
+        .await
+        .unwrap();
+
+    // The rmcp client attaches a progress token to every request
+    client
+        .call_tool(tool_call(
+            "airbnb_compare_listings",
+            serde_json::json!({ "ids": ["1", "2", "3"] }),
+        ))
+        .await
+        .unwrap();
+
+    let mut progress = Vec::new();
+    while let Some(notification) = next_notification(&mut rx).await {
+        if notification.starts_with("progress") {
+            progress.push(notification);
+        }
+    }
+    assert_eq!(progress, ["progress 1/3", "progress 2/3", "progress 3/3"]);
+
+    client.cancel().await.unwrap();
+    harness.stop().await;
+}
+
+#[tokio::test]
+async fn log_events_are_forwarded_at_the_client_level() {
+    use mcp_airbnb::mcp::logging::{LogForwarder, in_session};
+    use rmcp::ServiceExt;
+    use rmcp::model::{LoggingLevel, SetLevelRequestParams};
+    use tracing_subscriber::layer::SubscriberExt;
+
+    // Current-thread runtime: the thread-local subscriber also covers the server task
+    let forwarder = LogForwarder::default();
+    let _guard =
+        tracing::subscriber::set_default(tracing_subscriber::registry().with(forwarder.layer()));
+
+    let (server_transport, client_transport) = tokio::io::duplex(65536);
+    let server = AirbnbMcpServer::new(Arc::new(IntegrationMock)).with_log_forwarder(forwarder);
+    let server_handle = tokio::spawn(async move {
+        server.serve(server_transport).await?.waiting().await?;
+        anyhow::Ok(())
+    });
+    let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+    let client = NotificationRecorder(tx)
+        .serve(client_transport)
+        .await
+        .unwrap();
+    // Let the server process notifications/initialized before logging
+    client.list_tools(None).await.unwrap();
+
+    // Events of this session (id 0, the server built by `new`) at warning and above are
+    // forwarded by default; info is not, nor are other crates' events, events of other
+    // sessions, or events logged outside any session
+    tracing::warn!(target: "mcp_airbnb::mcp::auth", "Rejected unauthenticated MCP request");
+    in_session(1, async {
+        tracing::warn!(target: "mcp_airbnb::mcp::server", "Tool call denied for another tenant");
+    })
+    .await;
+    in_session(0, async {
+        tracing::info!(target: "mcp_airbnb::adapters::composite", "Listing fetched");
+        tracing::warn!(target: "hyper::proto", "connection reset");
+        tracing::warn!(target: "mcp_airbnb::adapters::composite", "GraphQL failed, falling back");
+    })
+    .await;
+    assert_eq!(
+        next_notification(&mut rx).await.as_deref(),
+        Some(r#"log Warning "GraphQL failed, falling back""#)
+    );
+    assert_eq!(next_notification(&mut rx).await, None);
+
+    client
+        .set_level(SetLevelRequestParams {
+            meta: None,
+            level: LoggingLevel::Info,
+        })
+        .await
+        .unwrap();
+    in_session(0, async {
+        tracing::info!(target: "mcp_airbnb::adapters::composite", "Listing fetched");
+    })
+    .await;
+    assert_eq!(
+        next_notification(&mut rx).await.as_deref(),
+        Some(r#"log Info "Listing fetched""#)
+    );
+
+    client.cancel().await.unwrap();
+    let _ = server_handle.await;
+}
+
+/// Client that declares `sampling` and answers every `sampling/createMessage` with a
+/// canned summary citing the first review.
+struct SamplingClient;
+
+impl rmcp::ClientHandler for SamplingClient {
+    async fn create_message(
+        &self,
+        params: rmcp::model::CreateMessageRequestParams,
+        _context: rmcp::service::RequestContext<rmcp::RoleClient>,
+    ) -> std::result::Result<rmcp::model::CreateMessageResult, rmcp::ErrorData> {
+        let prompt = serde_json::to_string(&params.messages).unwrap();
+        assert!(prompt.contains("[R1] Tester"), "prompt: {prompt}");
+        assert!(params.system_prompt.unwrap().contains("**Pros**"));
+        Ok(rmcp::model::CreateMessageResult {
+            model: "test-model".into(),
+            stop_reason: Some(rmcp::model::CreateMessageResult::STOP_REASON_END_TURN.into()),
+            message: rmcp::model::SamplingMessage::assistant_text(
+                "**Pros**\n- Guests enjoyed the stay [R1]\n\n**Cons**\n- None mentioned",
+            ),
+        })
+    }
+
+    fn get_info(&self) -> rmcp::model::ClientInfo {
+        rmcp::model::ClientInfo {
+            capabilities: rmcp::model::ClientCapabilities::builder()
+                .enable_sampling()
+                .build(),
+            ..Default::default()
+        }
+    }
+}
+
+#[tokio::test]
+async fn review_summary_is_written_by_the_client_model() {
+    use rmcp::ServiceExt;
+    use rmcp::model::CallToolRequestParams;
+
+    let (server_transport, client_transport) = tokio::io::duplex(65536);
+    let server = AirbnbMcpServer::new(Arc::new(IntegrationMock));
+    let server_handle = tokio::spawn(async move {
+        server.serve(server_transport).await?.waiting().await?;
+        anyhow::Ok(())
+    });
+    let client = SamplingClient.serve(client_transport).await.unwrap();
+
+    let args = serde_json::json!({ "id": "101" });
+    let result = client
+        .call_tool(CallToolRequestParams {
+            meta: None,
+            name: "airbnb_review_summary".into(),
+            arguments: args.as_object().cloned(),
+            task: None,
+        })
+        .await
+        .unwrap();
+
+    let structured = result.structured_content.unwrap();
+    assert_eq!(structured["method"], "sampling");
+    assert_eq!(structured["model"], "test-model");
+    assert_eq!(structured["cited_reviews"][0]["reference"], "R1");
+    let text = result.content[0].as_text().unwrap().text.clone();
+    assert!(text.contains("Guests enjoyed the stay [R1]"), "got: {text}");
+
+    client.cancel().await.unwrap();
+    let _ = server_handle.await;
+}
+
+/// Mock that records the parameters each search reaches the client with.
+#[derive(Default)]
+struct RecordingMock(std::sync::Mutex<Vec<SearchParams>>);
+
+#[async_trait]
+impl AirbnbClient for RecordingMock {
+    async fn search_listings(&self, params: &SearchParams) -> Result<SearchResult> {
+        self.0.lock().unwrap().push(params.clone());
+        IntegrationMock.search_listings(params).await
+    }
+    async fn get_listing_detail(&self, id: &str) -> Result<ListingDetail> {
+        IntegrationMock.get_listing_detail(id).await
+    }
+    async fn get_reviews(&self, id: &str, cursor: Option<&str>) -> Result<ReviewsPage> {
+        IntegrationMock.get_reviews(id, cursor).await
+    }
+    async fn get_price_calendar(&self, id: &str, months: u32) -> Result<PriceCalendar> {
+        IntegrationMock.get_price_calendar(id, months).await
+    }
+    async fn get_host_profile(&self, listing_id: &str) -> Result<HostProfile> {
+        IntegrationMock.get_host_profile(listing_id).await
+    }
+    async fn get_neighborhood_stats(&self, params: &SearchParams) -> Result<NeighborhoodStats> {
+        IntegrationMock.get_neighborhood_stats(params).await
+    }
+    async fn get_occupancy_estimate(&self, id: &str, months: u32) -> Result<OccupancyEstimate> {
+        IntegrationMock.get_occupancy_estimate(id, months).await
+    }
+}
+
+/// Client that declares form elicitation and fills in every form it is shown.
+struct ElicitingClient;
+
+impl rmcp::ClientHandler for ElicitingClient {
+    async fn create_elicitation(
+        &self,
+        params: rmcp::model::CreateElicitationRequestParams,
+        _context: rmcp::service::RequestContext<rmcp::RoleClient>,
+    ) -> std::result::Result<rmcp::model::CreateElicitationResult, rmcp::ErrorData> {
+        let rmcp::model::CreateElicitationRequestParams::FormElicitationParams {
+            requested_schema,
+            ..
+        } = params
+        else {
+            panic!("expected a form elicitation");
+        };
+        let content = if requested_schema.properties.contains_key("location") {
+            serde_json::json!({ "location": "Paris, Texas, United States" })
+        } else {
+            serde_json::json!({ "checkin": "2026-07-01", "checkout": "2026-07-04", "adults": 2 })
+        };
+        Ok(rmcp::model::CreateElicitationResult {
+            action: rmcp::model::ElicitationAction::Accept,
+            content: Some(content),
+        })
+    }
+
+    fn get_info(&self) -> rmcp::model::ClientInfo {
+        rmcp::model::ClientInfo {
+            capabilities: rmcp::model::ClientCapabilities::builder()
+                .enable_elicitation()
+                .build(),
+            ..Default::default()
+        }
+    }
+}
+
+#[tokio::test]
+async fn search_elicits_ambiguous_location_and_missing_dates() {
+    use rmcp::ServiceExt;
+    use rmcp::model::CallToolRequestParams;
+
+    let mock = Arc::new(RecordingMock::default());
+    let (server_transport, client_transport) = tokio::io::duplex(65536);
+    let server = AirbnbMcpServer::new(mock.clone());
+    let server_handle = tokio::spawn(async move {
+        server.serve(server_transport).await?.waiting().await?;
+        anyhow::Ok(())
+    });
+    let client = ElicitingClient.serve(client_transport).await.unwrap();
+
+    let args = serde_json::json!({ "location": "Paris" });
+    let result = client
+        .call_tool(CallToolRequestParams {
+            meta: None,
+            name: "airbnb_search".into(),
+            arguments: args.as_object().cloned(),
+            task: None,
+        })
+        .await
+        .unwrap();
+    assert_ne!(result.is_error, Some(true));
+
+    let searched = mock.0.lock().unwrap().clone();
+    assert_eq!(searched.len(), 1);
+    assert_eq!(searched[0].location, "Paris, Texas, United States");
+    assert_eq!(searched[0].checkin.as_deref(), Some("2026-07-01"));
+    assert_eq!(searched[0].checkout.as_deref(), Some("2026-07-04"));
+    assert_eq!(searched[0].adults, Some(2));
+
+    client.cancel().await.unwrap();
+    let _ = server_handle.await;
+}
+
+#[tokio::test]
+async fn search_without_elicitation_support_runs_as_requested() {
+    use rmcp::ServiceExt;
+    use rmcp::model::CallToolRequestParams;
+
+    let mock = Arc::new(RecordingMock::default());
+    let (server_transport, client_transport) = tokio::io::duplex(65536);
+    let server = AirbnbMcpServer::new(mock.clone());
+    let server_handle = tokio::spawn(async move {
+        server.serve(server_transport).await?.waiting().await?;
+        anyhow::Ok(())
+    });
+    let client = ().serve(client_transport).await.unwrap();
+
+    let args = serde_json::json!({ "location": "Paris" });
+    client
+        .call_tool(CallToolRequestParams {
+            meta: None,
+            name: "airbnb_search".into(),
+            arguments: args.as_object().cloned(),
+            task: None,
+        })
+        .await
+        .unwrap();
+
+    let searched = mock.0.lock().unwrap().clone();
+    assert_eq!(searched[0].location, "Paris");
+    assert!(searched[0].checkin.is_none());
+    assert!(searched[0].adults.is_none());
+
+    client.cancel().await.unwrap();
+    let _ = server_handle.await;
+}
+
+#[tokio::test]
+async fn local_session_runs_tools_without_a_host() {
+    use mcp_airbnb::mcp::local::{LocalSession, arguments_from_pairs};
+
+    let server = AirbnbMcpServer::new(Arc::new(IntegrationMock));
+    let session = LocalSession::start(server).await.unwrap();
+
+    let tool = session.tool("airbnb_price_trends").await.unwrap().unwrap();
+    let arguments = arguments_from_pairs(&tool, &["id=101".into(), "months=1".into()]).unwrap();
+    let result = session
+        .call("airbnb_price_trends", arguments)
+        .await
+        .unwrap();
+    assert_ne!(result.is_error, Some(true));
+    assert_eq!(result.structured_content.unwrap()["listing_id"], "101");
+
+    assert!(session.tool("airbnb_nope").await.unwrap().is_none());
+    let err = session
+        .call("airbnb_listing_details", serde_json::Map::new())
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("airbnb_listing_details"));
+    session.close().await;
+}
+
+/// Serves `IntegrationMock` details through a real cache and counts the fetches.
+struct CachingMock {
+    cache: mcp_airbnb::adapters::cache::cache_aside::CacheAside,
+    fetches: Arc<std::sync::atomic::AtomicUsize>,
+}
+
+#[async_trait]
+impl AirbnbClient for CachingMock {
+    async fn search_listings(&self, params: &SearchParams) -> Result<SearchResult> {
+        IntegrationMock.search_listings(params).await
+    }
+    async fn get_listing_detail(&self, id: &str) -> Result<ListingDetail> {
+        let fetches = Arc::clone(&self.fetches);
+        let id = id.to_string();
+        let policy = mcp_airbnb::ports::cache::CachePolicy {
+            ttl: std::time::Duration::from_mins(1),
+            grace: std::time::Duration::ZERO,
+        };
+        self.cache
+            .get_or_fetch(format!("detail:{id}"), policy, move || async move {
+                fetches.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+                IntegrationMock.get_listing_detail(&id).await
+            })
+            .await
+    }
+    async fn get_reviews(&self, id: &str, cursor: Option<&str>) -> Result<ReviewsPage> {
+        IntegrationMock.get_reviews(id, cursor).await
+    }
+    async fn get_price_calendar(&self, id: &str, months: u32) -> Result<PriceCalendar> {
+        IntegrationMock.get_price_calendar(id, months).await
+    }
+    async fn get_host_profile(&self, listing_id: &str) -> Result<HostProfile> {
+        IntegrationMock.get_host_profile(listing_id).await
+    }
+    async fn get_neighborhood_stats(&self, params: &SearchParams) -> Result<NeighborhoodStats> {
+        IntegrationMock.get_neighborhood_stats(params).await
+    }
+    async fn get_occupancy_estimate(&self, id: &str, months: u32) -> Result<OccupancyEstimate> {
+        IntegrationMock.get_occupancy_estimate(id, months).await
+    }
+}
+
+#[tokio::test]
+async fn cache_tools_and_fresh_option() {
+    use mcp_airbnb::adapters::cache::cache_aside::CacheAside;
+    use mcp_airbnb::adapters::cache::memory_cache::MemoryCache;
+    use mcp_airbnb::config::types::ToolsConfig;
+    use mcp_airbnb::mcp::local::{LocalSession, arguments_from_pairs};
+    use mcp_airbnb::ports::cache::ListingCache;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    let cache: Arc<dyn ListingCache> = Arc::new(MemoryCache::new(10));
+    let fetches = Arc::new(AtomicUsize::new(0));
+    let client = CachingMock {
+        cache: CacheAside::new(Arc::clone(&cache), "mock"),
+        fetches: Arc::clone(&fetches),
+    };
+    let tools = ToolsConfig {
+        allow: Some(vec![
+            "airbnb_listing_details".into(),
+            "airbnb_cache_stats".into(),
+            "airbnb_cache_invalidate".into(),
+        ]),
+        ..ToolsConfig::default()
+    };
+    let server = AirbnbMcpServer::new(Arc::new(client))
+        .with_cache(Arc::clone(&cache))
+        .with_tools(&tools)
+        .unwrap();
+    let session = LocalSession::start(server).await.unwrap();
+    let details = session
+        .tool("airbnb_listing_details")
+        .await
+        .unwrap()
+        .unwrap();
+    let call_details = |pairs: &[&str]| {
+        let pairs: Vec<String> = pairs.iter().map(ToString::to_string).collect();
+        let arguments = arguments_from_pairs(&details, &pairs).unwrap();
+        session.call("airbnb_listing_details", arguments)
+    };
+
+    call_details(&["id=101"]).await.unwrap();
+    let cached = call_details(&["id=101"]).await.unwrap();
+    assert_eq!(fetches.load(Ordering::SeqCst), 1);
+    let note = cached.content.last().and_then(|c| c.as_text()).unwrap();
+    assert!(
+        note.text
+            .starts_with("ℹ️ Served from cache: fetched up to 0s ago")
+    );
+
+    // A value cached by an older build is purged and fetched again
+    cache.set(
+        "detail:102",
+        "{\"id\":\"102\"}",
+        std::time::Duration::from_mins(1),
+    );
+    call_details(&["id=102"]).await.unwrap();
+    assert_eq!(fetches.load(Ordering::SeqCst), 2);
+
+    let stats = session
+        .call("airbnb_cache_stats", serde_json::Map::new())
+        .await
+        .unwrap()
+        .structured_content
+        .unwrap();
+    assert_eq!(stats["hits"], 2);
+    assert_eq!(stats["misses"], 1);
+    assert_eq!(stats["incompatible"], 1);
+    assert_eq!(stats["entries_by_prefix"]["detail:"], 2);
+
+    let fresh = call_details(&["id=101", "fresh=true"]).await.unwrap();
+    assert_ne!(fresh.is_error, Some(true));
+    assert_eq!(fetches.load(Ordering::SeqCst), 3);
+
+    // A prefix without the adapter tag also matches GraphQL entries; `gql:` only those
+    cache.set("gql:detail:103", "{}", std::time::Duration::from_mins(1));
+    cache.set("gql:detail:104", "{}", std::time::Duration::from_mins(1));
+    let invalidate = |prefix: &str| {
+        let mut arguments = serde_json::Map::new();
+        arguments.insert("prefix".into(), prefix.into());
+        session.call("airbnb_cache_invalidate", arguments)
+    };
+    let invalidated = invalidate("gql:detail:104").await.unwrap();
+    assert_eq!(invalidated.structured_content.unwrap()["removed"], 1);
+    let invalidated = invalidate("detail:").await.unwrap();
+    assert_eq!(invalidated.structured_content.unwrap()["removed"], 3);
+    call_details(&["id=101"]).await.unwrap();
+    assert_eq!(fetches.load(Ordering::SeqCst), 4);
+
+    // Tools that don't fetch from Airbnb don't take the option
+    let stats_tool = session.tool("airbnb_cache_stats").await.unwrap().unwrap();
+    assert!(arguments_from_pairs(&stats_tool, &["fresh=true".into()]).is_err());
+    session.close().await;
+}
+
+#[tokio::test]
+async fn cache_tools_are_hidden_unless_allowed() {
+    use mcp_airbnb::config::types::ToolsConfig;
+    use mcp_airbnb::mcp::local::LocalSession;
+
+    let session = LocalSession::start(AirbnbMcpServer::new(Arc::new(IntegrationMock)))
+        .await
+        .unwrap();
+    assert!(session.tool("airbnb_cache_stats").await.unwrap().is_none());
+    let denied = session
+        .call("airbnb_cache_invalidate", serde_json::Map::new())
+        .await;
+    assert!(denied.is_err());
+    session.close().await;
+
+    // Allowed, but without a cache they report an error
+    let tools = ToolsConfig {
+        allow: Some(vec!["airbnb_cache_stats".into()]),
+        ..ToolsConfig::default()
+    };
+    let server = AirbnbMcpServer::new(Arc::new(IntegrationMock))
+        .with_tools(&tools)
+        .unwrap();
+    let session = LocalSession::start(server).await.unwrap();
+    let result = session
+        .call("airbnb_cache_stats", serde_json::Map::new())
+        .await
+        .unwrap();
+    assert_eq!(result.is_error, Some(true));
+    session.close().await;
+}
@@ -4,6 +4,7 @@ use std::time::Duration;
 
 use proptest::prelude::*;
 
+use mcp_airbnb::adapters::cache::keys::CacheKeys;
 use mcp_airbnb::adapters::cache::memory_cache::MemoryCache;
 use mcp_airbnb::domain::analytics::{compute_neighborhood_stats, compute_occupancy_estimate};
 use mcp_airbnb::domain::calendar::{CalendarDay, PriceCalendar};
@@ -351,8 +352,8 @@ proptest! {
         value in "[a-zA-Z0-9]{1,100}",
     ) {
         let cache = MemoryCache::new(100);
-        cache.set(&key, &value, Duration::from_secs(3600));
-        let result = cache.get(&key);
+        cache.set(&key, &value, Duration::from_hours(1));
+        let result = cache.get(&key).map(|hit| hit.value);
         prop_assert_eq!(result, Some(value));
     }
 
@@ -363,7 +364,7 @@ proptest! {
         let capacity = 50;
         let cache = MemoryCache::new(capacity);
         for i in 0..n {
-            cache.set(&format!("k{i}"), &format!("v{i}"), Duration::from_secs(3600));
+            cache.set(&format!("k{i}"), &format!("v{i}"), Duration::from_hours(1));
         }
         // Count how many keys are still present
         let mut found = 0;
@@ -375,3 +376,105 @@ proptest! {
         prop_assert!(found <= capacity, "found {found} > capacity {capacity}");
     }
 }
+
+// ---------------------------------------------------------------------------
+// Cache key properties
+// ---------------------------------------------------------------------------
+
+/// Search params drawn from tiny alphabets (including the key separators), so that
+/// near-identical pairs, the ones most likely to collide, come up often.
+fn arb_search_params() -> impl Strategy<Value = SearchParams> {
+    let text = || prop::option::of("[aB:=%]{0,3}");
+    let count = || prop::option::of(0..3_u32);
+    (
+        (
+            "[aB:=%]{1,3}",
+            text(),
+            text(),
+            count(),
+            count(),
+            count(),
+            count(),
+        ),
+        (count(), count(), text(), text()),
+    )
+        .prop_map(
+            |(
+                (location, checkin, checkout, adults, children, infants, pets),
+                (min_price, max_price, property_type, cursor),
+            )| SearchParams {
+                location,
+                checkin,
+                checkout,
+                adults,
+                children,
+                infants,
+                pets,
+                min_price,
+                max_price,
+                property_type,
+                cursor,
+            },
+        )
+}
+
+/// What a search key must tell apart: every input, with location and property type
+/// compared case-insensitively.
+fn search_identity(params: &SearchParams) -> impl PartialEq + std::fmt::Debug {
+    (
+        params.location.to_lowercase(),
+        params.checkin.clone(),
+        params.checkout.clone(),
+        (params.adults, params.children, params.infants, params.pets),
+        (params.min_price, params.max_price),
+        params.property_type.as_deref().map(str::to_lowercase),
+        params.cursor.clone(),
+    )
+}
+
+proptest! {
+    #[test]
+    fn prop_distinct_search_params_never_collide(
+        a in arb_search_params(),
+        b in arb_search_params(),
+    ) {
+        let keys = CacheKeys::scraper("en", "USD");
+        prop_assert_eq!(
+            keys.search(&a) == keys.search(&b),
+            search_identity(&a) == search_identity(&b),
+            "{} vs {}", keys.search(&a), keys.search(&b)
+        );
+    }
+
+    #[test]
+    fn prop_adapters_locales_and_currencies_never_collide(
+        params in arb_search_params(),
+        locale in "(en|fr)",
+        currency in "(USD|EUR)",
+    ) {
+        let scraper = CacheKeys::scraper("en", "USD");
+        prop_assert_ne!(
+            scraper.search(&params),
+            CacheKeys::graphql("en", "USD").search(&params)
+        );
+        let other = CacheKeys::scraper(&locale, &currency);
+        prop_assert_eq!(
+            scraper.search(&params) == other.search(&params),
+            locale == "en" && currency == "USD"
+        );
+    }
+
+    #[test]
+    fn prop_review_pages_never_collide(
+        id_a in "[0-9:=%]{1,3}",
+        id_b in "[0-9:=%]{1,3}",
+        cursor_a in prop::option::of("[0-9:=%a-z]{0,3}"),
+        cursor_b in prop::option::of("[0-9:=%a-z]{0,3}"),
+    ) {
+        let keys = CacheKeys::graphql("en", "USD");
+        prop_assert_eq!(
+            keys.reviews(&id_a, cursor_a.as_deref()) == keys.reviews(&id_b, cursor_b.as_deref()),
+            id_a == id_b && cursor_a == cursor_b
+        );
+    }
+}
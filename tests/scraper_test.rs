@@ -229,6 +229,38 @@ async fn scraper_caches_results() {
     // wiremock will verify expect(1) on drop
 }
 
+#[tokio::test]
+async fn scraper_coalesces_concurrent_identical_requests() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path_regex("/rooms/.*"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_string(detail_html())
+                .set_delay(std::time::Duration::from_millis(100)),
+        )
+        .expect(1) // Both callers share the one in-flight request
+        .mount(&mock_server)
+        .await;
+
+    let cache = Arc::new(MemoryCache::new(100));
+    let api_key_mgr = test_api_key_manager(&mock_server.uri());
+    let scraper = AirbnbScraper::new(
+        fast_scraper_config(&mock_server.uri()),
+        test_cache_config(),
+        cache,
+        api_key_mgr,
+    )
+    .unwrap();
+
+    let (detail1, detail2) = tokio::join!(
+        scraper.get_listing_detail("501"),
+        scraper.get_listing_detail("501"),
+    );
+    assert_eq!(detail1.unwrap().name, detail2.unwrap().name);
+}
+
 #[tokio::test]
 async fn scraper_retries_on_server_error() {
     let mock_server = MockServer::start().await;